@@ -14,6 +14,7 @@ use tokio::time::timeout;
 
 use crate::backend::events::{AppServerEvent, EventSink};
 use crate::shared::process_core::{kill_child_process_tree, tokio_command};
+use crate::shared::watcher_core;
 use crate::codex::args::parse_codex_args;
 use crate::types::WorkspaceEntry;
 
@@ -448,6 +449,7 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
         }),
     };
     event_sink.emit_app_server_event(payload);
+    watcher_core::watch_workspace(entry.id.clone(), PathBuf::from(&entry.path), event_sink);
 
     Ok(session)
 }
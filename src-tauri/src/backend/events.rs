@@ -1,6 +1,8 @@
 use serde::Serialize;
 use serde_json::Value;
 
+use crate::types::{GitHubChecksResponse, WorkspaceSearchMatch};
+
 #[derive(Serialize, Clone)]
 pub(crate) struct AppServerEvent {
     pub(crate) workspace_id: String,
@@ -22,10 +24,39 @@ pub(crate) struct TerminalExit {
     pub(crate) workspace_id: String,
     #[serde(rename = "terminalId")]
     pub(crate) terminal_id: String,
+    pub(crate) reason: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct WorktreeSetupOutput {
+    #[serde(rename = "workspaceId")]
+    pub(crate) workspace_id: String,
+    pub(crate) stream: String,
+    pub(crate) data: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct GitHubChecksFinished {
+    #[serde(rename = "workspaceId")]
+    pub(crate) workspace_id: String,
+    #[serde(flatten)]
+    pub(crate) checks: GitHubChecksResponse,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct WorkspaceSearchMatches {
+    #[serde(rename = "workspaceId")]
+    pub(crate) workspace_id: String,
+    #[serde(rename = "searchId")]
+    pub(crate) search_id: String,
+    pub(crate) matches: Vec<WorkspaceSearchMatch>,
 }
 
 pub(crate) trait EventSink: Clone + Send + Sync + 'static {
     fn emit_app_server_event(&self, event: AppServerEvent);
     fn emit_terminal_output(&self, event: TerminalOutput);
     fn emit_terminal_exit(&self, event: TerminalExit);
+    fn emit_worktree_setup_output(&self, event: WorktreeSetupOutput);
+    fn emit_github_checks_finished(&self, event: GitHubChecksFinished);
+    fn emit_workspace_search_matches(&self, event: WorkspaceSearchMatches);
 }
@@ -7,6 +7,8 @@ mod codex_args;
 mod codex_home;
 #[path = "../codex/config.rs"]
 mod codex_config;
+#[path = "../files/browser.rs"]
+mod file_browser;
 #[path = "../files/io.rs"]
 mod file_io;
 #[path = "../files/ops.rs"]
@@ -41,6 +43,9 @@ mod codex {
 }
 
 mod files {
+    pub(crate) mod browser {
+        pub(crate) use crate::file_browser::*;
+    }
     pub(crate) mod io {
         pub(crate) use crate::file_io::*;
     }
@@ -70,13 +75,19 @@ use tokio::sync::{broadcast, mpsc, Mutex};
 use backend::app_server::{
     spawn_workspace_session, WorkspaceSession,
 };
-use backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
+use backend::events::{
+    AppServerEvent, EventSink, GitHubChecksFinished, TerminalExit, TerminalOutput,
+    WorkspaceSearchMatches, WorktreeSetupOutput,
+};
 use storage::{read_settings, read_workspaces};
-use shared::{codex_core, files_core, git_core, settings_core, workspaces_core, worktree_core};
+use shared::{
+    codex_core, files_core, git_core, search_core, settings_core, workspaces_core, worktree_core,
+};
 use shared::codex_core::CodexLoginCancelState;
 use workspace_settings::apply_workspace_settings_update;
 use types::{
-    AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceSettings, WorktreeSetupStatus,
+    AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspacePathMatch, WorkspaceSearchQuery,
+    WorkspaceSearchSummary, WorkspaceSettings, WorktreeSetupRunResult, WorktreeSetupStatus,
 };
 
 const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1:4732";
@@ -111,6 +122,8 @@ enum DaemonEvent {
     TerminalOutput(TerminalOutput),
     #[allow(dead_code)]
     TerminalExit(TerminalExit),
+    WorktreeSetupOutput(WorktreeSetupOutput),
+    WorkspaceSearchMatches(WorkspaceSearchMatches),
 }
 
 impl EventSink for DaemonEventSink {
@@ -125,6 +138,17 @@ impl EventSink for DaemonEventSink {
     fn emit_terminal_exit(&self, event: TerminalExit) {
         let _ = self.tx.send(DaemonEvent::TerminalExit(event));
     }
+
+    fn emit_worktree_setup_output(&self, event: WorktreeSetupOutput) {
+        let _ = self.tx.send(DaemonEvent::WorktreeSetupOutput(event));
+    }
+
+    // The daemon exposes no GitHub checks RPC, so nothing is ever watched.
+    fn emit_github_checks_finished(&self, _event: GitHubChecksFinished) {}
+
+    fn emit_workspace_search_matches(&self, event: WorkspaceSearchMatches) {
+        let _ = self.tx.send(DaemonEvent::WorkspaceSearchMatches(event));
+    }
 }
 
 struct DaemonConfig {
@@ -218,6 +242,7 @@ impl DaemonState {
             branch,
             name,
             copy_agents_md,
+            None,
             &self.data_dir,
             &self.workspaces,
             &self.sessions,
@@ -257,6 +282,21 @@ impl DaemonState {
             .await
     }
 
+    async fn worktree_setup_run(
+        &self,
+        workspace_id: String,
+        timeout_secs: Option<u64>,
+    ) -> Result<WorktreeSetupRunResult, String> {
+        workspaces_core::worktree_setup_run_core(
+            &self.workspaces,
+            &workspace_id,
+            &self.data_dir,
+            timeout_secs,
+            self.event_sink.clone(),
+        )
+        .await
+    }
+
     async fn worktree_setup_mark_ran(&self, workspace_id: String) -> Result<(), String> {
         workspaces_core::worktree_setup_mark_ran_core(&self.workspaces, &workspace_id, &self.data_dir)
             .await
@@ -490,6 +530,32 @@ impl DaemonState {
         .await
     }
 
+    async fn search_workspace_files(
+        &self,
+        workspace_id: String,
+        search_id: Option<String>,
+        query: WorkspaceSearchQuery,
+    ) -> Result<WorkspaceSearchSummary, String> {
+        let event_sink = self.event_sink.clone();
+        search_core::search_workspace_files_core(
+            &self.workspaces,
+            workspace_id,
+            search_id,
+            query,
+            move |event| event_sink.emit_workspace_search_matches(event),
+        )
+        .await
+    }
+
+    async fn find_workspace_paths(
+        &self,
+        workspace_id: String,
+        query: String,
+        limit: Option<usize>,
+    ) -> Result<Vec<WorkspacePathMatch>, String> {
+        search_core::find_workspace_paths_core(&self.workspaces, &workspace_id, query, limit).await
+    }
+
     async fn file_read(
         &self,
         scope: file_policy::FileScope,
@@ -868,6 +934,14 @@ fn build_event_notification(event: DaemonEvent) -> Option<String> {
             "method": "terminal-exit",
             "params": payload,
         }),
+        DaemonEvent::WorktreeSetupOutput(payload) => json!({
+            "method": "worktree-setup-output",
+            "params": payload,
+        }),
+        DaemonEvent::WorkspaceSearchMatches(payload) => json!({
+            "method": "workspace-search-matches",
+            "params": payload,
+        }),
     };
     serde_json::to_string(&payload).ok()
 }
@@ -964,6 +1038,17 @@ struct FileWriteRequest {
     content: String,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkspaceTextFileWriteRequest {
+    workspace_id: String,
+    path: String,
+    content: String,
+    encoding: Option<String>,
+    expected_hash: Option<String>,
+    expected_modified_ms: Option<i64>,
+}
+
 fn parse_file_read_request(params: &Value) -> Result<FileReadRequest, String> {
     serde_json::from_value(params.clone()).map_err(|err| err.to_string())
 }
@@ -1010,6 +1095,12 @@ async fn handle_rpc_request(
             let status = state.worktree_setup_status(workspace_id).await?;
             serde_json::to_value(status).map_err(|err| err.to_string())
         }
+        "worktree_setup_run" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let timeout_secs = parse_optional_u32(&params, "timeoutSecs").map(u64::from);
+            let result = state.worktree_setup_run(workspace_id, timeout_secs).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
         "worktree_setup_mark_ran" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             state.worktree_setup_mark_ran(workspace_id).await?;
@@ -1075,6 +1166,89 @@ async fn handle_rpc_request(
             let response = state.read_workspace_file(workspace_id, path).await?;
             serde_json::to_value(response).map_err(|err| err.to_string())
         }
+        "list_workspace_directory" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_optional_string(&params, "path").unwrap_or_default();
+            let entries =
+                files_core::list_workspace_directory_core(&state.workspaces, &workspace_id, &path)
+                    .await?;
+            serde_json::to_value(entries).map_err(|err| err.to_string())
+        }
+        "read_workspace_text_file" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_string(&params, "path")?;
+            let file =
+                files_core::read_workspace_text_file_core(&state.workspaces, &workspace_id, &path)
+                    .await?;
+            serde_json::to_value(file).map_err(|err| err.to_string())
+        }
+        "write_workspace_text_file" => {
+            let request: WorkspaceTextFileWriteRequest =
+                serde_json::from_value(params.clone()).map_err(|err| err.to_string())?;
+            let stat = files_core::write_workspace_text_file_core(
+                &state.workspaces,
+                &request.workspace_id,
+                &request.path,
+                &request.content,
+                request.encoding.as_deref(),
+                request.expected_hash.as_deref(),
+                request.expected_modified_ms,
+            )
+            .await?;
+            serde_json::to_value(stat).map_err(|err| err.to_string())
+        }
+        "create_workspace_entry" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_string(&params, "path")?;
+            let directory = parse_optional_bool(&params, "directory").unwrap_or(false);
+            let content = parse_optional_string(&params, "content");
+            let entry = files_core::create_workspace_entry_core(
+                &state.workspaces,
+                &workspace_id,
+                &path,
+                directory,
+                content.as_deref(),
+            )
+            .await?;
+            serde_json::to_value(entry).map_err(|err| err.to_string())
+        }
+        "rename_workspace_entry" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let from = parse_string(&params, "from")?;
+            let to = parse_string(&params, "to")?;
+            let entry =
+                files_core::rename_workspace_entry_core(&state.workspaces, &workspace_id, &from, &to)
+                    .await?;
+            serde_json::to_value(entry).map_err(|err| err.to_string())
+        }
+        "delete_workspace_entry" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_string(&params, "path")?;
+            files_core::delete_workspace_entry_core(&state.workspaces, &workspace_id, &path).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "search_workspace_files" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let search_id = parse_optional_string(&params, "searchId");
+            let query: WorkspaceSearchQuery = serde_json::from_value(params.clone())
+                .map_err(|err| format!("invalid search query: {err}"))?;
+            let summary = state
+                .search_workspace_files(workspace_id, search_id, query)
+                .await?;
+            serde_json::to_value(summary).map_err(|err| err.to_string())
+        }
+        "cancel_workspace_search" => {
+            let search_id = parse_string(&params, "searchId")?;
+            let cancelled = search_core::cancel_workspace_search_core(&search_id);
+            serde_json::to_value(cancelled).map_err(|err| err.to_string())
+        }
+        "find_workspace_paths" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let query = parse_string(&params, "query")?;
+            let limit = parse_optional_u32(&params, "limit").map(|value| value as usize);
+            let matches = state.find_workspace_paths(workspace_id, query, limit).await?;
+            serde_json::to_value(matches).map_err(|err| err.to_string())
+        }
         "file_read" => {
             let request = parse_file_read_request(&params)?;
             let response = state
@@ -7,6 +7,8 @@ mod codex_args;
 mod codex_home;
 #[path = "../codex/config.rs"]
 mod codex_config;
+#[path = "../files/browser.rs"]
+mod file_browser;
 #[path = "../files/io.rs"]
 mod file_io;
 #[path = "../files/ops.rs"]
@@ -27,6 +29,9 @@ mod workspace_settings;
 mod git_utils;
 #[path = "../git/mod.rs"]
 mod git;
+#[allow(dead_code)]
+#[path = "../event_sink.rs"]
+mod event_sink;
 #[path = "../prompts.rs"]
 mod prompts;
 #[path = "../local_usage.rs"]
@@ -49,13 +54,17 @@ mod remote_backend {
 
 mod terminal {
     use std::io::Write;
+    use std::sync::Arc;
     use tokio::sync::Mutex;
 
+    use crate::shared::terminal_core::TerminalActivity;
+
     pub(crate) struct TerminalSession {
         pub(crate) id: String,
         pub(crate) master: Mutex<Box<dyn portable_pty::MasterPty + Send>>,
         pub(crate) writer: Mutex<Box<dyn Write + Send>>,
         pub(crate) child: Mutex<Box<dyn portable_pty::Child + Send>>,
+        pub(crate) activity: Arc<TerminalActivity>,
     }
 }
 
@@ -73,6 +82,9 @@ mod codex {
 }
 
 mod files {
+    pub(crate) mod browser {
+        pub(crate) use crate::file_browser::*;
+    }
     pub(crate) mod io {
         pub(crate) use crate::file_io::*;
     }
@@ -107,13 +119,25 @@ use tokio::sync::{broadcast, mpsc, Mutex};
 use tauri::State as TauriState;
 
 use backend::app_server::{spawn_workspace_session, WorkspaceSession};
-use backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
+use backend::events::{
+    AppServerEvent, EventSink, GitHubChecksFinished, TerminalExit, TerminalOutput,
+    WorkspaceSearchMatches, WorktreeSetupOutput,
+};
 use shared::codex_core::CodexLoginCancelState;
-use shared::{codex_core, files_core, git_core, settings_core, workspaces_core, worktree_core};
+use shared::terminal_core::{
+    ensure_terminal_capacity, idle_terminal_keys, now_ms, owned_terminal_keys, split_terminal_key,
+    TerminalActivity, TerminalLimits, TERMINAL_EXIT_REASON_DISCONNECTED,
+    TERMINAL_EXIT_REASON_EXITED, TERMINAL_EXIT_REASON_IDLE, TERMINAL_REAPER_INTERVAL,
+};
+use shared::{
+    codex_core, files_core, git_core, search_core, settings_core, workspaces_core, worktree_core,
+};
 use state::AppState;
 use storage::{read_settings, read_workspaces};
 use types::{
-    AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceSettings, WorktreeSetupStatus,
+    AppSettings, GitHubReviewCommentDraft, GitHunkSelection, IssueRun, PullRequestWorktree, WorkspaceEntry, WorkspaceInfo,
+    WorkspaceIssueLink, WorkspacePathMatch, WorkspaceSearchQuery, WorkspaceSearchSummary,
+    WorkspaceSettings, WorktreeSetupRunResult, WorktreeSetupStatus,
 };
 use workspace_settings::apply_workspace_settings_update;
 
@@ -149,6 +173,9 @@ enum DaemonEvent {
     TerminalOutput(TerminalOutput),
     #[allow(dead_code)]
     TerminalExit(TerminalExit),
+    WorktreeSetupOutput(WorktreeSetupOutput),
+    GitHubChecksFinished(GitHubChecksFinished),
+    WorkspaceSearchMatches(WorkspaceSearchMatches),
 }
 
 impl EventSink for DaemonEventSink {
@@ -163,6 +190,18 @@ impl EventSink for DaemonEventSink {
     fn emit_terminal_exit(&self, event: TerminalExit) {
         let _ = self.tx.send(DaemonEvent::TerminalExit(event));
     }
+
+    fn emit_worktree_setup_output(&self, event: WorktreeSetupOutput) {
+        let _ = self.tx.send(DaemonEvent::WorktreeSetupOutput(event));
+    }
+
+    fn emit_github_checks_finished(&self, event: GitHubChecksFinished) {
+        let _ = self.tx.send(DaemonEvent::GitHubChecksFinished(event));
+    }
+
+    fn emit_workspace_search_matches(&self, event: WorkspaceSearchMatches) {
+        let _ = self.tx.send(DaemonEvent::WorkspaceSearchMatches(event));
+    }
 }
 
 struct DaemonConfig {
@@ -321,6 +360,7 @@ impl DaemonState {
         branch: String,
         name: Option<String>,
         copy_agents_md: bool,
+        pr_number: Option<u64>,
         client_version: String,
     ) -> Result<WorkspaceInfo, String> {
         let client_version = client_version.clone();
@@ -329,6 +369,7 @@ impl DaemonState {
             branch,
             name,
             copy_agents_md,
+            pr_number,
             &self.data_dir,
             &self.workspaces,
             &self.sessions,
@@ -363,11 +404,157 @@ impl DaemonState {
         .await
     }
 
+    async fn add_pr_worktree(
+        &self,
+        parent_id: String,
+        pr_number: u64,
+        start_review: bool,
+        client_version: String,
+    ) -> Result<PullRequestWorktree, String> {
+        let parent_entry = {
+            let workspaces = self.workspaces.lock().await;
+            workspaces
+                .get(&parent_id)
+                .cloned()
+                .ok_or("parent workspace not found")?
+        };
+        let repo_root = git_utils::resolve_git_root(&parent_entry)?;
+        let settings = self.app_settings.lock().await.clone();
+        let prepared = git::prepare_pull_request_branch(&repo_root, &settings, pr_number).await?;
+        let name = format!("#{pr_number} {}", prepared.title)
+            .trim()
+            .to_string();
+        let workspace = self
+            .add_worktree(
+                parent_id,
+                prepared.branch,
+                Some(name),
+                true,
+                Some(pr_number),
+                client_version,
+            )
+            .await?;
+        let review_thread_id = if start_review {
+            match codex_core::start_base_branch_review_core(
+                &self.sessions,
+                workspace.id.clone(),
+                prepared.base_ref,
+                None,
+            )
+            .await
+            {
+                Ok(thread_id) => Some(thread_id),
+                Err(error) => {
+                    eprintln!("add_pr_worktree: review for #{pr_number} failed to start: {error}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        Ok(PullRequestWorktree {
+            workspace,
+            review_thread_id,
+        })
+    }
+
+    async fn start_issue_run(
+        &self,
+        workspace_id: String,
+        issue_number: u64,
+        worktree: bool,
+        client_version: String,
+    ) -> Result<IssueRun, String> {
+        let entry = {
+            let workspaces = self.workspaces.lock().await;
+            workspaces
+                .get(&workspace_id)
+                .cloned()
+                .ok_or("workspace not found")?
+        };
+        let repo_root = git_utils::resolve_git_root(&entry)?;
+        let settings = self.app_settings.lock().await.clone();
+        let issue = git::fetch_issue(&repo_root, &settings, issue_number).await?;
+        let link = WorkspaceIssueLink {
+            number: issue.issue.number,
+            title: issue.issue.title.clone(),
+            url: issue.issue.url.clone(),
+        };
+        let run_workspace_id = if worktree {
+            let name = format!("#{issue_number} {}", link.title).trim().to_string();
+            self.add_worktree(
+                workspace_id,
+                git::issue_branch_name(&issue),
+                Some(name),
+                true,
+                None,
+                client_version,
+            )
+            .await?
+            .id
+        } else {
+            workspace_id
+        };
+        // Only the issue's own worktree keeps the link; a run in place must not
+        // tag every later pull request from this workspace.
+        let workspace = if worktree {
+            workspaces_core::set_workspace_issue_core(
+                run_workspace_id.clone(),
+                Some(link),
+                &self.workspaces,
+                &self.sessions,
+                &self.storage_path,
+            )
+            .await?
+        } else {
+            workspaces_core::workspace_info_core(
+                &run_workspace_id,
+                &self.workspaces,
+                &self.sessions,
+            )
+            .await?
+        };
+        let thread = codex_core::start_thread_with_message_core(
+            &self.sessions,
+            run_workspace_id,
+            git::issue_run_prompt(&issue),
+        )
+        .await;
+        let thread_id = match thread {
+            Ok(thread_id) => Some(thread_id),
+            Err(error) if worktree => {
+                eprintln!("start_issue_run: thread for #{issue_number} failed to start: {error}");
+                None
+            }
+            Err(error) => return Err(error),
+        };
+        Ok(IssueRun {
+            workspace,
+            created_worktree: worktree,
+            thread_id,
+        })
+    }
+
     async fn worktree_setup_status(&self, workspace_id: String) -> Result<WorktreeSetupStatus, String> {
         workspaces_core::worktree_setup_status_core(&self.workspaces, &workspace_id, &self.data_dir)
             .await
     }
 
+    async fn worktree_setup_run(
+        &self,
+        workspace_id: String,
+        timeout_secs: Option<u64>,
+    ) -> Result<WorktreeSetupRunResult, String> {
+        workspaces_core::worktree_setup_run_core(
+            &self.workspaces,
+            &workspace_id,
+            &self.data_dir,
+            timeout_secs,
+            self.event_sink.clone(),
+        )
+        .await
+    }
+
     async fn worktree_setup_mark_ran(&self, workspace_id: String) -> Result<(), String> {
         workspaces_core::worktree_setup_mark_ran_core(&self.workspaces, &workspace_id, &self.data_dir)
             .await
@@ -601,6 +788,32 @@ impl DaemonState {
         .await
     }
 
+    async fn search_workspace_files(
+        &self,
+        workspace_id: String,
+        search_id: Option<String>,
+        query: WorkspaceSearchQuery,
+    ) -> Result<WorkspaceSearchSummary, String> {
+        let event_sink = self.event_sink.clone();
+        search_core::search_workspace_files_core(
+            &self.workspaces,
+            workspace_id,
+            search_id,
+            query,
+            move |event| event_sink.emit_workspace_search_matches(event),
+        )
+        .await
+    }
+
+    async fn find_workspace_paths(
+        &self,
+        workspace_id: String,
+        query: String,
+        limit: Option<usize>,
+    ) -> Result<Vec<WorkspacePathMatch>, String> {
+        search_core::find_workspace_paths_core(&self.workspaces, &workspace_id, query, limit).await
+    }
+
     async fn file_read(
         &self,
         scope: file_policy::FileScope,
@@ -792,17 +1005,20 @@ impl DaemonState {
         terminal_id: String,
         cols: u16,
         rows: u16,
+        owner: &str,
     ) -> Result<Value, String> {
         if terminal_id.trim().is_empty() {
             return Err("Terminal id is required".to_string());
         }
 
         let key = terminal_key(&workspace_id, &terminal_id);
+        let limits = TerminalLimits::from_settings(&*self.app_settings.lock().await);
         {
             let sessions = self.terminal_sessions.lock().await;
             if sessions.contains_key(&key) {
                 return Ok(json!({ "id": terminal_id }));
             }
+            ensure_terminal_capacity(sessions.keys(), &workspace_id, &limits)?;
         }
 
         let cwd = self.workspace_path(&workspace_id).await?;
@@ -844,10 +1060,29 @@ impl DaemonState {
             master: Mutex::new(pair.master),
             writer: Mutex::new(writer),
             child: Mutex::new(child),
+            activity: Arc::new(TerminalActivity::new(Some(owner.to_string()))),
         });
 
         {
             let mut sessions = self.terminal_sessions.lock().await;
+            // Concurrent opens may have taken this key or the last slot while
+            // the shell was starting.
+            let raced = if sessions.contains_key(&key) {
+                Some(Ok(json!({ "id": terminal_id })))
+            } else {
+                ensure_terminal_capacity(sessions.keys(), &workspace_id, &limits)
+                    .err()
+                    .map(Err)
+            };
+            if let Some(result) = raced {
+                drop(sessions);
+                let _ = tokio::task::spawn_blocking(move || {
+                    let mut child = session.child.blocking_lock();
+                    let _ = child.kill();
+                })
+                .await;
+                return result;
+            }
             sessions.insert(key, Arc::clone(&session));
         }
 
@@ -855,6 +1090,7 @@ impl DaemonState {
             self.event_sink.clone(),
             workspace_id,
             terminal_id.clone(),
+            Arc::clone(&session.activity),
             reader,
         );
 
@@ -875,6 +1111,7 @@ impl DaemonState {
                 .cloned()
                 .ok_or_else(|| "Terminal session not found".to_string())?
         };
+        session.activity.touch();
 
         let write_result = tokio::task::spawn_blocking(move || {
             let mut writer = session.writer.blocking_lock();
@@ -961,6 +1198,66 @@ impl DaemonState {
 
         Ok(())
     }
+
+    async fn reap_terminals(&self, keys: Vec<String>, reason: &str) {
+        let reaped = {
+            let mut sessions = self.terminal_sessions.lock().await;
+            keys.into_iter()
+                .filter_map(|key| sessions.remove(&key).map(|session| (key, session)))
+                .collect::<Vec<_>>()
+        };
+        for (key, session) in reaped {
+            if session.activity.mark_exited() {
+                if let Some((workspace_id, terminal_id)) = split_terminal_key(&key) {
+                    self.event_sink.emit_terminal_exit(TerminalExit {
+                        workspace_id: workspace_id.to_string(),
+                        terminal_id: terminal_id.to_string(),
+                        reason: reason.to_string(),
+                    });
+                }
+            }
+            let _ = tokio::task::spawn_blocking(move || {
+                let mut child = session.child.blocking_lock();
+                let _ = child.kill();
+            })
+            .await;
+        }
+    }
+
+    async fn reap_idle_terminals(&self) {
+        let limits = TerminalLimits::from_settings(&*self.app_settings.lock().await);
+        let Some(idle_timeout) = limits.idle_timeout else {
+            return;
+        };
+        let keys = {
+            let sessions = self.terminal_sessions.lock().await;
+            idle_terminal_keys(
+                sessions
+                    .iter()
+                    .map(|(key, session)| (key, session.activity.as_ref())),
+                idle_timeout,
+                now_ms(),
+            )
+        };
+        self.reap_terminals(keys, TERMINAL_EXIT_REASON_IDLE).await;
+    }
+
+    async fn close_client_terminals(&self, owner: &str) {
+        let limits = TerminalLimits::from_settings(&*self.app_settings.lock().await);
+        if !limits.close_on_disconnect {
+            return;
+        }
+        let keys = {
+            let sessions = self.terminal_sessions.lock().await;
+            owned_terminal_keys(
+                sessions
+                    .iter()
+                    .map(|(key, session)| (key, session.activity.as_ref())),
+                owner,
+            )
+        };
+        self.reap_terminals(keys, TERMINAL_EXIT_REASON_DISCONNECTED).await;
+    }
 }
 
 fn terminal_key(workspace_id: &str, terminal_id: &str) -> String {
@@ -997,6 +1294,7 @@ fn spawn_terminal_reader(
     event_sink: DaemonEventSink,
     workspace_id: String,
     terminal_id: String,
+    activity: Arc<TerminalActivity>,
     mut reader: Box<dyn Read + Send>,
 ) {
     std::thread::spawn(move || {
@@ -1006,6 +1304,7 @@ fn spawn_terminal_reader(
             match reader.read(&mut buffer) {
                 Ok(0) => break,
                 Ok(count) => {
+                    activity.touch();
                     pending.extend_from_slice(&buffer[..count]);
                     loop {
                         match std::str::from_utf8(&pending) {
@@ -1052,10 +1351,13 @@ fn spawn_terminal_reader(
                 Err(_) => break,
             }
         }
-        event_sink.emit_terminal_exit(TerminalExit {
-            workspace_id,
-            terminal_id,
-        });
+        if activity.mark_exited() {
+            event_sink.emit_terminal_exit(TerminalExit {
+                workspace_id,
+                terminal_id,
+                reason: TERMINAL_EXIT_REASON_EXITED.to_string(),
+            });
+        }
     });
 }
 
@@ -1330,6 +1632,18 @@ fn build_event_notification(event: DaemonEvent) -> Option<String> {
             "method": "terminal-exit",
             "params": payload,
         }),
+        DaemonEvent::WorktreeSetupOutput(payload) => json!({
+            "method": "worktree-setup-output",
+            "params": payload,
+        }),
+        DaemonEvent::GitHubChecksFinished(payload) => json!({
+            "method": "github-checks-finished",
+            "params": payload,
+        }),
+        DaemonEvent::WorkspaceSearchMatches(payload) => json!({
+            "method": "workspace-search-matches",
+            "params": payload,
+        }),
     };
     serde_json::to_string(&payload).ok()
 }
@@ -1409,6 +1723,11 @@ fn parse_optional_value(value: &Value, key: &str) -> Option<Value> {
     }
 }
 
+fn parse_json_value<T: serde::de::DeserializeOwned>(value: &Value, key: &str) -> Result<T, String> {
+    let raw = parse_optional_value(value, key).ok_or_else(|| format!("missing `{key}`"))?;
+    serde_json::from_value(raw).map_err(|err| format!("invalid `{key}`: {err}"))
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct FileReadRequest {
@@ -1426,6 +1745,17 @@ struct FileWriteRequest {
     content: String,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkspaceTextFileWriteRequest {
+    workspace_id: String,
+    path: String,
+    content: String,
+    encoding: Option<String>,
+    expected_hash: Option<String>,
+    expected_modified_ms: Option<i64>,
+}
+
 fn parse_file_read_request(params: &Value) -> Result<FileReadRequest, String> {
     serde_json::from_value(params.clone()).map_err(|err| err.to_string())
 }
@@ -1503,6 +1833,7 @@ async fn handle_rpc_request(
     method: &str,
     params: Value,
     client_version: String,
+    client_id: &str,
 ) -> Result<Value, String> {
     match method {
         "ping" => Ok(json!({ "ok": true })),
@@ -1527,15 +1858,40 @@ async fn handle_rpc_request(
             let name = parse_optional_string(&params, "name");
             let copy_agents_md = parse_optional_bool(&params, "copyAgentsMd").unwrap_or(true);
             let workspace = state
-                .add_worktree(parent_id, branch, name, copy_agents_md, client_version)
+                .add_worktree(parent_id, branch, name, copy_agents_md, None, client_version)
                 .await?;
             serde_json::to_value(workspace).map_err(|err| err.to_string())
         }
+        "add_pr_worktree" => {
+            let parent_id = parse_string(&params, "parentId")?;
+            let pr_number = parse_optional_u32(&params, "prNumber").ok_or("missing prNumber")?;
+            let start_review = parse_optional_bool(&params, "startReview").unwrap_or(false);
+            let result = state
+                .add_pr_worktree(parent_id, pr_number as u64, start_review, client_version)
+                .await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "start_issue_run" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let issue_number =
+                parse_optional_u32(&params, "issueNumber").ok_or("missing issueNumber")?;
+            let worktree = parse_optional_bool(&params, "worktree").unwrap_or(true);
+            let result = state
+                .start_issue_run(workspace_id, issue_number as u64, worktree, client_version)
+                .await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
         "worktree_setup_status" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let status = state.worktree_setup_status(workspace_id).await?;
             serde_json::to_value(status).map_err(|err| err.to_string())
         }
+        "worktree_setup_run" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let timeout_secs = parse_optional_u32(&params, "timeoutSecs").map(u64::from);
+            let result = state.worktree_setup_run(workspace_id, timeout_secs).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
         "worktree_setup_mark_ran" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             state.worktree_setup_mark_ran(workspace_id).await?;
@@ -1601,6 +1957,89 @@ async fn handle_rpc_request(
             let response = state.read_workspace_file(workspace_id, path).await?;
             serde_json::to_value(response).map_err(|err| err.to_string())
         }
+        "list_workspace_directory" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_optional_string(&params, "path").unwrap_or_default();
+            let entries =
+                files_core::list_workspace_directory_core(&state.workspaces, &workspace_id, &path)
+                    .await?;
+            serde_json::to_value(entries).map_err(|err| err.to_string())
+        }
+        "read_workspace_text_file" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_string(&params, "path")?;
+            let file =
+                files_core::read_workspace_text_file_core(&state.workspaces, &workspace_id, &path)
+                    .await?;
+            serde_json::to_value(file).map_err(|err| err.to_string())
+        }
+        "write_workspace_text_file" => {
+            let request: WorkspaceTextFileWriteRequest =
+                serde_json::from_value(params.clone()).map_err(|err| err.to_string())?;
+            let stat = files_core::write_workspace_text_file_core(
+                &state.workspaces,
+                &request.workspace_id,
+                &request.path,
+                &request.content,
+                request.encoding.as_deref(),
+                request.expected_hash.as_deref(),
+                request.expected_modified_ms,
+            )
+            .await?;
+            serde_json::to_value(stat).map_err(|err| err.to_string())
+        }
+        "create_workspace_entry" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_string(&params, "path")?;
+            let directory = parse_optional_bool(&params, "directory").unwrap_or(false);
+            let content = parse_optional_string(&params, "content");
+            let entry = files_core::create_workspace_entry_core(
+                &state.workspaces,
+                &workspace_id,
+                &path,
+                directory,
+                content.as_deref(),
+            )
+            .await?;
+            serde_json::to_value(entry).map_err(|err| err.to_string())
+        }
+        "rename_workspace_entry" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let from = parse_string(&params, "from")?;
+            let to = parse_string(&params, "to")?;
+            let entry =
+                files_core::rename_workspace_entry_core(&state.workspaces, &workspace_id, &from, &to)
+                    .await?;
+            serde_json::to_value(entry).map_err(|err| err.to_string())
+        }
+        "delete_workspace_entry" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_string(&params, "path")?;
+            files_core::delete_workspace_entry_core(&state.workspaces, &workspace_id, &path).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "search_workspace_files" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let search_id = parse_optional_string(&params, "searchId");
+            let query: WorkspaceSearchQuery = serde_json::from_value(params.clone())
+                .map_err(|err| format!("invalid search query: {err}"))?;
+            let summary = state
+                .search_workspace_files(workspace_id, search_id, query)
+                .await?;
+            serde_json::to_value(summary).map_err(|err| err.to_string())
+        }
+        "cancel_workspace_search" => {
+            let search_id = parse_string(&params, "searchId")?;
+            let cancelled = search_core::cancel_workspace_search_core(&search_id);
+            serde_json::to_value(cancelled).map_err(|err| err.to_string())
+        }
+        "find_workspace_paths" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let query = parse_string(&params, "query")?;
+            let limit = parse_optional_u32(&params, "limit").map(|value| value as usize);
+            let matches = state.find_workspace_paths(workspace_id, query, limit).await?;
+            serde_json::to_value(matches).map_err(|err| err.to_string())
+        }
         "file_read" => {
             let request = parse_file_read_request(&params)?;
             let response = state
@@ -1886,9 +2325,13 @@ async fn handle_rpc_request(
         "get_git_log" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let limit = parse_optional_u32(&params, "limit").map(|value| value as usize);
+            let query = match parse_optional_value(&params, "query") {
+                Some(Value::Null) | None => None,
+                Some(_) => Some(parse_json_value(&params, "query")?),
+            };
             let app_state = state.snapshot_app_state().await;
             let tauri_state = DaemonState::as_tauri_state(&app_state);
-            let result = git::get_git_log(workspace_id, limit, tauri_state).await?;
+            let result = git::get_git_log(workspace_id, limit, query, tauri_state).await?;
             serde_json::to_value(result).map_err(|err| err.to_string())
         }
         "get_git_commit_diff" => {
@@ -1944,13 +2387,41 @@ async fn handle_rpc_request(
             git::revert_git_all(workspace_id, tauri_state).await?;
             Ok(json!({ "ok": true }))
         }
+        "stage_git_hunks" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_string(&params, "path")?;
+            let hunks: Vec<GitHunkSelection> = parse_json_value(&params, "hunks")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::stage_git_hunks(workspace_id, path, hunks, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "unstage_git_hunks" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_string(&params, "path")?;
+            let hunks: Vec<GitHunkSelection> = parse_json_value(&params, "hunks")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::unstage_git_hunks(workspace_id, path, hunks, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "revert_git_hunks" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_string(&params, "path")?;
+            let hunks: Vec<GitHunkSelection> = parse_json_value(&params, "hunks")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::revert_git_hunks(workspace_id, path, hunks, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
         "commit_git" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let message = parse_string(&params, "message")?;
+            let no_verify = parse_optional_bool(&params, "noVerify");
             let app_state = state.snapshot_app_state().await;
             let tauri_state = DaemonState::as_tauri_state(&app_state);
-            git::commit_git(workspace_id, message, tauri_state).await?;
-            Ok(json!({ "ok": true }))
+            let result = git::commit_git(workspace_id, message, no_verify, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
         }
         "push_git" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
@@ -1987,6 +2458,16 @@ async fn handle_rpc_request(
             let result = git::get_github_issues(workspace_id, tauri_state).await?;
             serde_json::to_value(result).map_err(|err| err.to_string())
         }
+        "get_github_issue" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let issue_number =
+                parse_optional_u32(&params, "issueNumber").ok_or("missing issueNumber")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result =
+                git::get_github_issue(workspace_id, issue_number as u64, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
         "get_github_pull_requests" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let app_state = state.snapshot_app_state().await;
@@ -2010,6 +2491,146 @@ async fn handle_rpc_request(
             let result = git::get_github_pull_request_comments(workspace_id, pr_number as u64, tauri_state).await?;
             serde_json::to_value(result).map_err(|err| err.to_string())
         }
+        "get_github_rate_limit" => {
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::get_github_rate_limit(tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "create_github_pull_request" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let title = parse_optional_string(&params, "title");
+            let body = parse_optional_string(&params, "body");
+            let base = parse_optional_string(&params, "base");
+            let draft = parse_optional_bool(&params, "draft");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::create_github_pull_request(workspace_id, title, body, base, draft, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "update_github_pull_request" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let pr_number = parse_optional_u32(&params, "prNumber").ok_or("missing prNumber")?;
+            let title = parse_optional_string(&params, "title");
+            let body = parse_optional_string(&params, "body");
+            let base = parse_optional_string(&params, "base");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::update_github_pull_request(workspace_id, pr_number as u64, title, body, base, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "mark_github_pull_request_ready" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let pr_number = parse_optional_u32(&params, "prNumber").ok_or("missing prNumber")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::mark_github_pull_request_ready(workspace_id, pr_number as u64, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "request_github_pull_request_reviewers" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let pr_number = parse_optional_u32(&params, "prNumber").ok_or("missing prNumber")?;
+            let reviewers = parse_string_array(&params, "reviewers")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::request_github_pull_request_reviewers(workspace_id, pr_number as u64, reviewers, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "get_github_pull_request_review_comments" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let pr_number = parse_optional_u32(&params, "prNumber").ok_or("missing prNumber")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let comments = git::get_github_pull_request_review_comments(workspace_id, pr_number as u64, tauri_state).await?;
+            serde_json::to_value(comments).map_err(|err| err.to_string())
+        }
+        "post_github_pull_request_comment" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let pr_number = parse_optional_u32(&params, "prNumber").ok_or("missing prNumber")?;
+            let body = parse_string(&params, "body")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let comment = git::post_github_pull_request_comment(workspace_id, pr_number as u64, body, tauri_state).await?;
+            serde_json::to_value(comment).map_err(|err| err.to_string())
+        }
+        "post_github_review_comment" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let pr_number = parse_optional_u32(&params, "prNumber").ok_or("missing prNumber")?;
+            let comment: GitHubReviewCommentDraft = parse_json_value(&params, "comment")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let comment = git::post_github_review_comment(workspace_id, pr_number as u64, comment, tauri_state).await?;
+            serde_json::to_value(comment).map_err(|err| err.to_string())
+        }
+        "reply_github_review_comment" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let pr_number = parse_optional_u32(&params, "prNumber").ok_or("missing prNumber")?;
+            let comment_id: u64 = parse_json_value(&params, "commentId")?;
+            let body = parse_string(&params, "body")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let comment = git::reply_github_review_comment(workspace_id, pr_number as u64, comment_id, body, tauri_state).await?;
+            serde_json::to_value(comment).map_err(|err| err.to_string())
+        }
+        "submit_github_pull_request_review" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let pr_number = parse_optional_u32(&params, "prNumber").ok_or("missing prNumber")?;
+            let event = parse_string(&params, "event")?;
+            let body = parse_optional_string(&params, "body");
+            let comments: Option<Vec<GitHubReviewCommentDraft>> = match parse_optional_value(&params, "comments") {
+                Some(Value::Null) | None => None,
+                Some(_) => Some(parse_json_value(&params, "comments")?),
+            };
+            let review_text = parse_optional_string(&params, "reviewText");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let review = git::submit_github_pull_request_review(
+                workspace_id,
+                pr_number as u64,
+                event,
+                body,
+                comments,
+                review_text,
+                tauri_state,
+            )
+            .await?;
+            serde_json::to_value(review).map_err(|err| err.to_string())
+        }
+        "get_github_checks" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let git_ref = parse_optional_string(&params, "gitRef");
+            let pr_number = parse_optional_u32(&params, "prNumber").map(u64::from);
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let checks = git::get_github_checks(workspace_id, git_ref, pr_number, tauri_state).await?;
+            serde_json::to_value(checks).map_err(|err| err.to_string())
+        }
+        "get_github_check_logs" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let git_ref = parse_optional_string(&params, "gitRef");
+            let pr_number = parse_optional_u32(&params, "prNumber").map(u64::from);
+            let check_id = match parse_optional_value(&params, "checkId") {
+                Some(Value::Null) | None => None,
+                Some(_) => Some(parse_json_value::<u64>(&params, "checkId")?),
+            };
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let logs = git::get_github_check_logs(workspace_id, git_ref, pr_number, check_id, tauri_state).await?;
+            serde_json::to_value(logs).map_err(|err| err.to_string())
+        }
+        "watch_github_checks" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let git_ref = parse_optional_string(&params, "gitRef");
+            let pr_number = parse_optional_u32(&params, "prNumber").map(u64::from);
+            let event_sink = state.event_sink.clone();
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let checks = git::watch_github_checks_with(workspace_id, git_ref, pr_number, tauri_state, move |event| {
+                event_sink.emit_github_checks_finished(event)
+            })
+            .await?;
+            serde_json::to_value(checks).map_err(|err| err.to_string())
+        }
         "local_usage_snapshot" => {
             let days = parse_optional_u32(&params, "days");
             let workspace_path = parse_optional_string(&params, "workspacePath");
@@ -2116,17 +2737,359 @@ async fn handle_rpc_request(
         "create_git_branch" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let name = parse_string(&params, "name")?;
+            let start_point = parse_optional_string(&params, "startPoint");
+            let checkout = parse_optional_bool(&params, "checkout");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::create_git_branch(workspace_id, name, start_point, checkout, tauri_state)
+                .await?;
+            Ok(json!({ "ok": true }))
+        }
+        "list_git_stashes" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::list_git_stashes(workspace_id, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "stash_git_push" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let message = parse_optional_string(&params, "message");
+            let include_untracked = parse_optional_bool(&params, "includeUntracked");
+            let paths = parse_optional_string_array(&params, "paths");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::stash_git_push(
+                workspace_id,
+                message,
+                include_untracked,
+                paths,
+                tauri_state,
+            )
+            .await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "stash_git_apply" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let index: usize = parse_json_value(&params, "index")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::stash_git_apply(workspace_id, index, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "stash_git_pop" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let index: usize = parse_json_value(&params, "index")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::stash_git_pop(workspace_id, index, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "stash_git_drop" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let index: usize = parse_json_value(&params, "index")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::stash_git_drop(workspace_id, index, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "get_git_stash_diff" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let index: usize = parse_json_value(&params, "index")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::get_git_stash_diff(workspace_id, index, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "get_git_conflicts" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::get_git_conflicts(workspace_id, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "resolve_git_conflict" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_string(&params, "path")?;
+            let resolution = parse_string(&params, "resolution")?;
+            let content = parse_optional_string(&params, "content");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::resolve_git_conflict(workspace_id, path, resolution, content, tauri_state)
+                .await?;
+            Ok(json!({ "ok": true }))
+        }
+        "abort_git_merge" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::abort_git_merge(workspace_id, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "continue_git_merge" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::continue_git_merge(workspace_id, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "abort_git_rebase" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::abort_git_rebase(workspace_id, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "continue_git_rebase" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::continue_git_rebase(workspace_id, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "get_git_range_diff" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let base = parse_string(&params, "base")?;
+            let head = parse_string(&params, "head")?;
+            let three_dot = parse_optional_bool(&params, "threeDot");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result =
+                git::get_git_range_diff(workspace_id, base, head, three_dot, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "get_git_branch_comparison" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let base = parse_string(&params, "base")?;
+            let head = parse_string(&params, "head")?;
+            let limit = parse_optional_u32(&params, "limit").map(|value| value as usize);
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result =
+                git::get_git_branch_comparison(workspace_id, base, head, limit, tauri_state)
+                    .await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "get_git_file_log" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_string(&params, "path")?;
+            let limit = parse_optional_u32(&params, "limit").map(|value| value as usize);
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::get_git_file_log(workspace_id, path, limit, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "get_git_blame" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_string(&params, "path")?;
+            let rev = parse_optional_string(&params, "rev");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::get_git_blame(workspace_id, path, rev, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "amend_git_commit" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let message = parse_optional_string(&params, "message");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::amend_git_commit(workspace_id, message, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "fixup_git_commit" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let sha = parse_string(&params, "sha")?;
             let app_state = state.snapshot_app_state().await;
             let tauri_state = DaemonState::as_tauri_state(&app_state);
-            git::create_git_branch(workspace_id, name, tauri_state).await?;
+            git::fixup_git_commit(workspace_id, sha, tauri_state).await?;
             Ok(json!({ "ok": true }))
         }
+        "autosquash_git_rebase" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let base = parse_string(&params, "base")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::autosquash_git_rebase(workspace_id, base, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "squash_worktree_commits" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let message = parse_optional_string(&params, "message");
+            let base = parse_optional_string(&params, "base");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::squash_worktree_commits(workspace_id, message, base, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "cherry_pick_git_commits" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let shas = parse_string_array(&params, "shas")?;
+            let source_workspace_id = parse_optional_string(&params, "sourceWorkspaceId");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::cherry_pick_git_commits(
+                workspace_id,
+                shas,
+                source_workspace_id,
+                tauri_state,
+            )
+            .await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "revert_git_commit" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let sha = parse_string(&params, "sha")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::revert_git_commit(workspace_id, sha, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "continue_git_operation" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::continue_git_operation(workspace_id, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "abort_git_operation" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::abort_git_operation(workspace_id, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "list_git_tags" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::list_git_tags(workspace_id, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "create_git_tag" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let name = parse_string(&params, "name")?;
+            let target = parse_optional_string(&params, "target");
+            let message = parse_optional_string(&params, "message");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result =
+                git::create_git_tag(workspace_id, name, target, message, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "delete_git_tag" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let name = parse_string(&params, "name")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::delete_git_tag(workspace_id, name, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "push_git_tag" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let name = parse_string(&params, "name")?;
+            let remote = parse_optional_string(&params, "remote");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::push_git_tag(workspace_id, name, remote, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "get_git_release_notes" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let from_tag = parse_string(&params, "fromTag")?;
+            let to_ref = parse_optional_string(&params, "toRef");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result =
+                git::get_git_release_notes(workspace_id, from_tag, to_ref, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "delete_git_branch" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let name = parse_string(&params, "name")?;
+            let remote = parse_optional_bool(&params, "remote");
+            let force = parse_optional_bool(&params, "force");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::delete_git_branch(workspace_id, name, remote, force, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "rename_git_branch" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let name = parse_string(&params, "name")?;
+            let new_name = parse_string(&params, "newName")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::rename_git_branch(workspace_id, name, new_name, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "set_git_branch_upstream" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let name = parse_optional_string(&params, "name");
+            let upstream = parse_optional_string(&params, "upstream");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            git::set_git_branch_upstream(workspace_id, name, upstream, tauri_state).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "merge_git_branch" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let name = parse_string(&params, "name")?;
+            let no_ff = parse_optional_bool(&params, "noFf");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::merge_git_branch(workspace_id, name, no_ff, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "list_git_submodules" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::list_git_submodules(workspace_id, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "update_git_submodules" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let paths = parse_optional_string_array(&params, "paths");
+            let init = parse_optional_bool(&params, "init");
+            let sync = parse_optional_bool(&params, "sync");
+            let recursive = parse_optional_bool(&params, "recursive");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::update_git_submodules(
+                workspace_id,
+                paths,
+                init,
+                sync,
+                recursive,
+                tauri_state,
+            )
+            .await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "get_git_lfs_locks" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let local = parse_optional_bool(&params, "local");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::get_git_lfs_locks(workspace_id, local, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
+        "check_git_commit_hooks" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let message = parse_optional_string(&params, "message");
+            let app_state = state.snapshot_app_state().await;
+            let tauri_state = DaemonState::as_tauri_state(&app_state);
+            let result = git::check_git_commit_hooks(workspace_id, message, tauri_state).await?;
+            serde_json::to_value(result).map_err(|err| err.to_string())
+        }
         "terminal_open" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let terminal_id = parse_string(&params, "terminalId")?;
             let cols = parse_optional_u32(&params, "cols").unwrap_or(120) as u16;
             let rows = parse_optional_u32(&params, "rows").unwrap_or(40) as u16;
-            state.terminal_open(workspace_id, terminal_id, cols, rows).await
+            state
+                .terminal_open(workspace_id, terminal_id, cols, rows, client_id)
+                .await
         }
         "terminal_write" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
@@ -2298,6 +3261,7 @@ async fn workspace_file_route(
 }
 
 async fn handle_ws_client(socket: WebSocket, runtime: Arc<RuntimeState>, mut authenticated: bool) {
+    let client_id = uuid::Uuid::new_v4().to_string();
     let (mut sender, mut receiver) = socket.split();
     let (out_tx, mut out_rx) = mpsc::unbounded_channel::<String>();
 
@@ -2379,8 +3343,14 @@ async fn handle_ws_client(socket: WebSocket, runtime: Arc<RuntimeState>, mut aut
         }
 
         let client_version = format!("web-{}", env!("CARGO_PKG_VERSION"));
-        let result =
-            handle_rpc_request(&runtime.daemon_state, &method, params, client_version).await;
+        let result = handle_rpc_request(
+            &runtime.daemon_state,
+            &method,
+            params,
+            client_version,
+            &client_id,
+        )
+        .await;
         let response = match result {
             Ok(result) => build_result_response(id, result),
             Err(message) => build_error_response(id, &message),
@@ -2395,6 +3365,7 @@ async fn handle_ws_client(socket: WebSocket, runtime: Arc<RuntimeState>, mut aut
         task.abort();
     }
     write_task.abort();
+    runtime.daemon_state.close_client_terminals(&client_id).await;
 }
 
 fn main() {
@@ -2419,6 +3390,14 @@ fn main() {
         let daemon_state = Arc::new(DaemonState::load(&config, event_sink));
         let config = Arc::new(config);
 
+        let reaper_state = Arc::clone(&daemon_state);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(TERMINAL_REAPER_INTERVAL).await;
+                reaper_state.reap_idle_terminals().await;
+            }
+        });
+
         let runtime_state = Arc::new(RuntimeState {
             config: Arc::clone(&config),
             daemon_state: Arc::clone(&daemon_state),
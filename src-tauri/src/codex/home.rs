@@ -200,6 +200,7 @@ mod tests {
         let worktree = if kind.is_worktree() {
             Some(WorktreeInfo {
                 branch: "feature/test".to_string(),
+                pr_number: None,
             })
         } else {
             None
@@ -1,6 +1,9 @@
 use tauri::{AppHandle, Emitter};
 
-use crate::backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
+use crate::backend::events::{
+    AppServerEvent, EventSink, GitHubChecksFinished, TerminalExit, TerminalOutput,
+    WorkspaceSearchMatches, WorktreeSetupOutput,
+};
 
 #[derive(Clone)]
 pub(crate) struct TauriEventSink {
@@ -25,4 +28,16 @@ impl EventSink for TauriEventSink {
     fn emit_terminal_exit(&self, event: TerminalExit) {
         let _ = self.app.emit("terminal-exit", event);
     }
+
+    fn emit_worktree_setup_output(&self, event: WorktreeSetupOutput) {
+        let _ = self.app.emit("worktree-setup-output", event);
+    }
+
+    fn emit_github_checks_finished(&self, event: GitHubChecksFinished) {
+        let _ = self.app.emit("github-checks-finished", event);
+    }
+
+    fn emit_workspace_search_matches(&self, event: WorkspaceSearchMatches) {
+        let _ = self.app.emit("workspace-search-matches", event);
+    }
 }
@@ -0,0 +1,663 @@
+use std::fs::{File, Metadata, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use git2::{ObjectType, Oid, Repository, Status, StatusOptions};
+
+use crate::types::{WorkspaceDirEntry, WorkspaceFileStat, WorkspaceTextFile};
+use crate::utils::normalize_git_path;
+
+const MAX_EDITOR_FILE_BYTES: u64 = 5_000_000;
+const BINARY_SNIFF_BYTES: usize = 8_000;
+pub(crate) const FILE_CHANGED_ERROR: &str = "File changed on disk since it was read";
+
+fn canonical_root(root: &Path) -> Result<PathBuf, String> {
+    let canonical = root
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve workspace root: {err}"))?;
+    if !canonical.is_dir() {
+        return Err("workspace root is not a directory".to_string());
+    }
+    Ok(canonical)
+}
+
+/// Resolves `relative_path` under the canonical workspace root. Existing
+/// parent directories are canonicalized so a symlinked directory cannot lead
+/// outside the root; the final component is left as-is so symlinks can be
+/// renamed or deleted without touching their target.
+fn confined_path(root: &Path, relative_path: &str) -> Result<PathBuf, String> {
+    let mut parts = Vec::new();
+    for component in Path::new(relative_path.trim()).components() {
+        match component {
+            Component::Normal(part) => parts.push(part),
+            Component::CurDir => {}
+            _ => return Err("Invalid file path".to_string()),
+        }
+    }
+    let Some((name, parents)) = parts.split_last() else {
+        return Ok(root.to_path_buf());
+    };
+
+    let mut existing = root.to_path_buf();
+    let mut missing_from = 0;
+    for part in parents {
+        let next = existing.join(part);
+        if std::fs::symlink_metadata(&next).is_err() {
+            break;
+        }
+        existing = next;
+        missing_from += 1;
+    }
+    let mut path = existing
+        .canonicalize()
+        .map_err(|err| format!("Failed to resolve path: {err}"))?;
+    if !path.starts_with(root) {
+        return Err("Invalid file path".to_string());
+    }
+    for part in &parents[missing_from..] {
+        path.push(part);
+    }
+    path.push(name);
+    Ok(path)
+}
+
+/// Follows symlinks for reads and writes, rejecting targets outside the root.
+fn resolve_existing(root: &Path, path: &Path) -> Result<PathBuf, String> {
+    let canonical = path
+        .canonicalize()
+        .map_err(|err| format!("Failed to open file: {err}"))?;
+    if !canonical.starts_with(root) {
+        return Err("Invalid file path".to_string());
+    }
+    Ok(canonical)
+}
+
+fn ensure_mutable(root: &Path, path: &Path) -> Result<(), String> {
+    let relative = path
+        .strip_prefix(root)
+        .map_err(|_| "Invalid file path".to_string())?;
+    match relative.components().next() {
+        None => Err("Cannot modify the workspace root".to_string()),
+        Some(Component::Normal(first)) if first == ".git" => {
+            Err("Cannot modify files inside .git".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Also checks where an existing symlink points, so a link into `.git`
+/// cannot be used to write there.
+fn ensure_mutable_target(root: &Path, path: &Path) -> Result<(), String> {
+    ensure_mutable(root, path)?;
+    match path.canonicalize() {
+        Ok(real) if real != root && real.starts_with(root) => ensure_mutable(root, &real),
+        _ => Ok(()),
+    }
+}
+
+fn relative_string(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .map(|relative| normalize_git_path(&relative.to_string_lossy()))
+        .unwrap_or_default()
+}
+
+fn modified_ms(metadata: &Metadata) -> Option<i64> {
+    metadata
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_millis() as i64)
+}
+
+fn file_stat(
+    path: String,
+    real_path: &Path,
+    metadata: &Metadata,
+) -> Result<WorkspaceFileStat, String> {
+    let hash = Oid::hash_file(ObjectType::Blob, real_path)
+        .map_err(|err| format!("Failed to hash file: {err}"))?;
+    Ok(WorkspaceFileStat {
+        path,
+        size: metadata.len(),
+        modified_ms: modified_ms(metadata).unwrap_or(0),
+        hash: hash.to_string(),
+    })
+}
+
+fn status_letter(status: Status) -> Option<&'static str> {
+    if status.contains(Status::CONFLICTED) {
+        Some("U")
+    } else if status.intersects(Status::WT_NEW | Status::INDEX_NEW) {
+        Some("A")
+    } else if status.intersects(Status::WT_MODIFIED | Status::INDEX_MODIFIED) {
+        Some("M")
+    } else if status.intersects(Status::WT_DELETED | Status::INDEX_DELETED) {
+        Some("D")
+    } else if status.intersects(Status::WT_RENAMED | Status::INDEX_RENAMED) {
+        Some("R")
+    } else if status.intersects(Status::WT_TYPECHANGE | Status::INDEX_TYPECHANGE) {
+        Some("T")
+    } else {
+        None
+    }
+}
+
+struct GitStatuses {
+    workdir: PathBuf,
+    entries: Vec<(String, &'static str)>,
+}
+
+impl GitStatuses {
+    fn load(root: &Path) -> Option<Self> {
+        let repo = Repository::discover(root).ok()?;
+        let workdir = repo.workdir()?.canonicalize().ok()?;
+        let mut options = StatusOptions::new();
+        options
+            .include_untracked(true)
+            .recurse_untracked_dirs(false)
+            .include_ignored(false);
+        let statuses = repo.statuses(Some(&mut options)).ok()?;
+        let entries = statuses
+            .iter()
+            .filter_map(|entry| {
+                let path = normalize_git_path(entry.path()?);
+                Some((path, status_letter(entry.status())?))
+            })
+            .collect();
+        Some(Self { workdir, entries })
+    }
+
+    fn status_for(&self, path: &Path, is_dir: bool) -> Option<String> {
+        let relative =
+            normalize_git_path(&path.strip_prefix(&self.workdir).ok()?.to_string_lossy());
+        if !is_dir {
+            return self
+                .entries
+                .iter()
+                .find(|(entry_path, _)| *entry_path == relative)
+                .map(|(_, letter)| letter.to_string());
+        }
+        // Untracked directories are reported once with a trailing slash.
+        let prefix = format!("{relative}/");
+        if self
+            .entries
+            .iter()
+            .any(|(entry_path, _)| *entry_path == prefix)
+        {
+            return Some("A".to_string());
+        }
+        self.entries
+            .iter()
+            .any(|(entry_path, _)| entry_path.starts_with(&prefix))
+            .then(|| "M".to_string())
+    }
+}
+
+fn dir_entry(
+    root: &Path,
+    path: &Path,
+    statuses: Option<&GitStatuses>,
+) -> Option<WorkspaceDirEntry> {
+    let metadata = std::fs::symlink_metadata(path).ok()?;
+    let file_type = metadata.file_type();
+    let kind = if file_type.is_symlink() {
+        "symlink"
+    } else if file_type.is_dir() {
+        "dir"
+    } else {
+        "file"
+    };
+    Some(WorkspaceDirEntry {
+        name: path.file_name()?.to_string_lossy().to_string(),
+        path: relative_string(root, path),
+        kind: kind.to_string(),
+        size: file_type.is_file().then_some(metadata.len()),
+        modified_ms: modified_ms(&metadata),
+        git_status: statuses.and_then(|statuses| statuses.status_for(path, file_type.is_dir())),
+    })
+}
+
+/// Lists one directory level, directories first, with git status per entry.
+pub(crate) fn list_workspace_directory(
+    root: &Path,
+    relative_path: &str,
+) -> Result<Vec<WorkspaceDirEntry>, String> {
+    let root = canonical_root(root)?;
+    let dir = resolve_existing(&root, &confined_path(&root, relative_path)?)?;
+    if !dir.is_dir() {
+        return Err("Path is not a directory".to_string());
+    }
+    let statuses = GitStatuses::load(&root);
+    let read_dir =
+        std::fs::read_dir(&dir).map_err(|err| format!("Failed to read directory: {err}"))?;
+    let mut entries: Vec<WorkspaceDirEntry> = read_dir
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name() != ".git")
+        .filter_map(|entry| dir_entry(&root, &entry.path(), statuses.as_ref()))
+        .collect();
+    entries.sort_by(|a, b| {
+        (b.kind == "dir")
+            .cmp(&(a.kind == "dir"))
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(entries)
+}
+
+fn decode_utf8(bytes: &[u8], truncated: bool) -> Option<String> {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Some(text.to_string()),
+        // A truncated read may end part-way through a character.
+        Err(err) if truncated && err.error_len().is_none() => {
+            Some(String::from_utf8_lossy(&bytes[..err.valid_up_to()]).to_string())
+        }
+        Err(_) => None,
+    }
+}
+
+fn decode_utf16(bytes: &[u8], little_endian: bool) -> Option<String> {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| {
+            if little_endian {
+                u16::from_le_bytes([pair[0], pair[1]])
+            } else {
+                u16::from_be_bytes([pair[0], pair[1]])
+            }
+        })
+        .collect();
+    String::from_utf16(&units).ok()
+}
+
+fn decode_text(bytes: &[u8], truncated: bool) -> (&'static str, String) {
+    let decoded = if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        decode_utf8(rest, truncated).map(|text| ("utf-8-bom", text))
+    } else if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        decode_utf16(rest, true).map(|text| ("utf-16le", text))
+    } else if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        decode_utf16(rest, false).map(|text| ("utf-16be", text))
+    } else if bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0) {
+        None
+    } else {
+        decode_utf8(bytes, truncated).map(|text| ("utf-8", text))
+    };
+    decoded.unwrap_or(("binary", String::new()))
+}
+
+fn encode_text(content: &str, encoding: &str) -> Result<Vec<u8>, String> {
+    match encoding {
+        "utf-8" => Ok(content.as_bytes().to_vec()),
+        "utf-8-bom" => Ok([&[0xEF, 0xBB, 0xBF], content.as_bytes()].concat()),
+        "utf-16le" => Ok([0xFF, 0xFE]
+            .into_iter()
+            .chain(content.encode_utf16().flat_map(u16::to_le_bytes))
+            .collect()),
+        "utf-16be" => Ok([0xFE, 0xFF]
+            .into_iter()
+            .chain(content.encode_utf16().flat_map(u16::to_be_bytes))
+            .collect()),
+        "binary" => Err("Binary files cannot be edited".to_string()),
+        other => Err(format!("Unsupported encoding `{other}`")),
+    }
+}
+
+/// Reads a file for editing. Content past the size limit is dropped and
+/// flagged as truncated; `hash` always covers the whole file.
+pub(crate) fn read_workspace_text_file(
+    root: &Path,
+    relative_path: &str,
+) -> Result<WorkspaceTextFile, String> {
+    let root = canonical_root(root)?;
+    let path = confined_path(&root, relative_path)?;
+    let real_path = resolve_existing(&root, &path)?;
+    let metadata = std::fs::metadata(&real_path)
+        .map_err(|err| format!("Failed to read file metadata: {err}"))?;
+    if !metadata.is_file() {
+        return Err("Path is not a file".to_string());
+    }
+    let stat = file_stat(relative_string(&root, &path), &real_path, &metadata)?;
+
+    let file = File::open(&real_path).map_err(|err| format!("Failed to open file: {err}"))?;
+    let mut buffer = Vec::new();
+    file.take(MAX_EDITOR_FILE_BYTES + 1)
+        .read_to_end(&mut buffer)
+        .map_err(|err| format!("Failed to read file: {err}"))?;
+    let truncated = buffer.len() as u64 > MAX_EDITOR_FILE_BYTES;
+    if truncated {
+        buffer.truncate(MAX_EDITOR_FILE_BYTES as usize);
+    }
+    let (encoding, content) = decode_text(&buffer, truncated);
+    Ok(WorkspaceTextFile {
+        stat,
+        content,
+        encoding: encoding.to_string(),
+        truncated,
+    })
+}
+
+/// Writes a text file, refusing when it no longer matches the `hash` or
+/// `modifiedMs` the caller read. With neither given the write is
+/// unconditional.
+pub(crate) fn write_workspace_text_file(
+    root: &Path,
+    relative_path: &str,
+    content: &str,
+    encoding: Option<&str>,
+    expected_hash: Option<&str>,
+    expected_modified_ms: Option<i64>,
+) -> Result<WorkspaceFileStat, String> {
+    let root = canonical_root(root)?;
+    let path = confined_path(&root, relative_path)?;
+    ensure_mutable(&root, &path)?;
+    let relative = relative_string(&root, &path);
+    let target = if std::fs::symlink_metadata(&path).is_ok() {
+        resolve_existing(&root, &path)?
+    } else {
+        path.clone()
+    };
+    ensure_mutable(&root, &target)?;
+    // Reads of larger files are truncated, so saving one would drop the rest.
+    if std::fs::metadata(&target).is_ok_and(|metadata| metadata.len() > MAX_EDITOR_FILE_BYTES) {
+        return Err(format!("{relative} is too large to edit"));
+    }
+
+    if expected_hash.is_some() || expected_modified_ms.is_some() {
+        let current = std::fs::metadata(&target)
+            .ok()
+            .filter(|metadata| metadata.is_file())
+            .ok_or_else(|| format!("{FILE_CHANGED_ERROR}: {relative} no longer exists"))?;
+        if let Some(expected) = expected_modified_ms {
+            if modified_ms(&current) != Some(expected) {
+                return Err(format!("{FILE_CHANGED_ERROR}: {relative}"));
+            }
+        }
+        if let Some(expected) = expected_hash {
+            let current_hash = Oid::hash_file(ObjectType::Blob, &target)
+                .map_err(|err| format!("Failed to hash file: {err}"))?;
+            if current_hash.to_string() != expected {
+                return Err(format!("{FILE_CHANGED_ERROR}: {relative}"));
+            }
+        }
+    }
+    if target.is_dir() {
+        return Err("Path is a directory".to_string());
+    }
+
+    let bytes = encode_text(content, encoding.unwrap_or("utf-8"))?;
+    std::fs::write(&target, bytes).map_err(|err| format!("Failed to write file: {err}"))?;
+    let metadata =
+        std::fs::metadata(&target).map_err(|err| format!("Failed to read file metadata: {err}"))?;
+    file_stat(relative, &target, &metadata)
+}
+
+/// Creates a file or directory, including any missing parent directories.
+pub(crate) fn create_workspace_entry(
+    root: &Path,
+    relative_path: &str,
+    directory: bool,
+    content: Option<&str>,
+) -> Result<WorkspaceDirEntry, String> {
+    let root = canonical_root(root)?;
+    let path = confined_path(&root, relative_path)?;
+    ensure_mutable_target(&root, &path)?;
+    if std::fs::symlink_metadata(&path).is_ok() {
+        return Err(format!("{} already exists", relative_string(&root, &path)));
+    }
+    if directory {
+        std::fs::create_dir_all(&path)
+            .map_err(|err| format!("Failed to create directory: {err}"))?;
+    } else {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| format!("Failed to create directory: {err}"))?;
+        }
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|err| format!("Failed to create file: {err}"))?;
+        file.write_all(content.unwrap_or("").as_bytes())
+            .map_err(|err| format!("Failed to write file: {err}"))?;
+    }
+    dir_entry(&root, &path, GitStatuses::load(&root).as_ref())
+        .ok_or_else(|| "Failed to read created entry".to_string())
+}
+
+/// Renames or moves an entry within the workspace. The destination must not
+/// exist; missing parent directories are created.
+pub(crate) fn rename_workspace_entry(
+    root: &Path,
+    from: &str,
+    to: &str,
+) -> Result<WorkspaceDirEntry, String> {
+    let root = canonical_root(root)?;
+    let source = confined_path(&root, from)?;
+    let destination = confined_path(&root, to)?;
+    ensure_mutable_target(&root, &source)?;
+    ensure_mutable_target(&root, &destination)?;
+    if std::fs::symlink_metadata(&source).is_err() {
+        return Err(format!(
+            "{} does not exist",
+            relative_string(&root, &source)
+        ));
+    }
+    if std::fs::symlink_metadata(&destination).is_ok() {
+        return Err(format!(
+            "{} already exists",
+            relative_string(&root, &destination)
+        ));
+    }
+    if destination.starts_with(&source) {
+        return Err("Cannot move a directory into itself".to_string());
+    }
+    if let Some(parent) = destination.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create directory: {err}"))?;
+    }
+    std::fs::rename(&source, &destination).map_err(|err| format!("Failed to rename: {err}"))?;
+    dir_entry(&root, &destination, GitStatuses::load(&root).as_ref())
+        .ok_or_else(|| "Failed to read renamed entry".to_string())
+}
+
+/// Deletes a file, symlink or directory tree. Symlinks are removed without
+/// touching their target.
+pub(crate) fn delete_workspace_entry(root: &Path, relative_path: &str) -> Result<(), String> {
+    let root = canonical_root(root)?;
+    let path = confined_path(&root, relative_path)?;
+    ensure_mutable(&root, &path)?;
+    let metadata =
+        std::fs::symlink_metadata(&path).map_err(|err| format!("Failed to delete: {err}"))?;
+    if metadata.file_type().is_dir() {
+        std::fs::remove_dir_all(&path)
+    } else {
+        std::fs::remove_file(&path)
+    }
+    .map_err(|err| format!("Failed to delete: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-browser-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn rejects_paths_outside_root() {
+        let root = temp_dir();
+        assert!(read_workspace_text_file(&root, "../outside.txt").is_err());
+        assert!(write_workspace_text_file(&root, "/etc/passwd", "x", None, None, None).is_err());
+        assert!(delete_workspace_entry(&root, "").is_err());
+        assert!(create_workspace_entry(&root, ".git/config", false, None).is_err());
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_symlinked_directory_escape() {
+        use std::os::unix::fs::symlink;
+
+        let root = temp_dir();
+        let outside = temp_dir();
+        std::fs::write(outside.join("secret.txt"), "secret").expect("seed outside file");
+        symlink(&outside, root.join("link")).expect("create symlink");
+
+        assert!(read_workspace_text_file(&root, "link/secret.txt").is_err());
+        assert!(create_workspace_entry(&root, "link/new.txt", false, None).is_err());
+        delete_workspace_entry(&root, "link").expect("delete symlink");
+        assert!(outside.join("secret.txt").exists());
+
+        let _ = std::fs::remove_dir_all(root);
+        let _ = std::fs::remove_dir_all(outside);
+    }
+
+    #[test]
+    fn write_detects_concurrent_changes() {
+        let root = temp_dir();
+        std::fs::write(root.join("notes.md"), "first").expect("seed file");
+
+        let read = read_workspace_text_file(&root, "notes.md").expect("read");
+        assert_eq!(read.content, "first");
+        assert_eq!(read.encoding, "utf-8");
+
+        std::fs::write(root.join("notes.md"), "changed elsewhere").expect("external write");
+        let error =
+            write_workspace_text_file(&root, "notes.md", "mine", None, Some(&read.stat.hash), None)
+                .expect_err("stale hash should be rejected");
+        assert!(error.starts_with(FILE_CHANGED_ERROR));
+
+        let current = read_workspace_text_file(&root, "notes.md").expect("reread");
+        let written = write_workspace_text_file(
+            &root,
+            "notes.md",
+            "mine",
+            None,
+            Some(&current.stat.hash),
+            Some(current.stat.modified_ms),
+        )
+        .expect("fresh hash should be accepted");
+        assert_eq!(written.size, 4);
+        assert_eq!(
+            std::fs::read_to_string(root.join("notes.md")).expect("read"),
+            "mine"
+        );
+
+        let large = "x".repeat(MAX_EDITOR_FILE_BYTES as usize + 1);
+        std::fs::write(root.join("large.txt"), &large).expect("seed large file");
+        let read = read_workspace_text_file(&root, "large.txt").expect("read large");
+        assert!(read.truncated);
+        let error = write_workspace_text_file(
+            &root,
+            "large.txt",
+            &read.content,
+            None,
+            Some(&read.stat.hash),
+            None,
+        )
+        .expect_err("truncated content must not be saved");
+        assert!(error.contains("too large"));
+        assert_eq!(
+            std::fs::metadata(root.join("large.txt"))
+                .expect("stat")
+                .len(),
+            large.len() as u64
+        );
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_cannot_reach_into_git_dir() {
+        let root = temp_dir();
+        std::fs::create_dir_all(root.join(".git/hooks")).expect("create hooks dir");
+        std::fs::write(root.join(".git/hooks/pre-commit"), "").expect("seed hook");
+        std::os::unix::fs::symlink(root.join(".git/hooks/pre-commit"), root.join("hook"))
+            .expect("link hook");
+        std::os::unix::fs::symlink(root.join(".git/hooks"), root.join("hooks"))
+            .expect("link hooks dir");
+
+        let error = write_workspace_text_file(&root, "hook", "#!/bin/sh", None, None, None)
+            .expect_err("write through link into .git");
+        assert!(error.contains(".git"));
+        assert!(create_workspace_entry(&root, "hooks/post-commit", false, Some("x")).is_err());
+        assert!(rename_workspace_entry(&root, "hook", "renamed").is_err());
+        assert_eq!(
+            std::fs::read_to_string(root.join(".git/hooks/pre-commit")).expect("read hook"),
+            ""
+        );
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn preserves_utf16_and_flags_binary() {
+        let root = temp_dir();
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("héllo".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        std::fs::write(root.join("wide.txt"), &utf16).expect("seed utf16");
+        std::fs::write(root.join("blob.bin"), [0u8, 1, 2, 3]).expect("seed binary");
+
+        let wide = read_workspace_text_file(&root, "wide.txt").expect("read utf16");
+        assert_eq!(wide.encoding, "utf-16le");
+        assert_eq!(wide.content, "héllo");
+        write_workspace_text_file(&root, "wide.txt", "bye", Some(&wide.encoding), None, None)
+            .expect("write utf16");
+        let reread = read_workspace_text_file(&root, "wide.txt").expect("reread utf16");
+        assert_eq!(reread.encoding, "utf-16le");
+        assert_eq!(reread.content, "bye");
+
+        let binary = read_workspace_text_file(&root, "blob.bin").expect("read binary");
+        assert_eq!(binary.encoding, "binary");
+        assert!(binary.content.is_empty());
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn create_rename_delete_and_list_with_git_status() {
+        let root = temp_dir();
+        let repo = Repository::init(&root).expect("init repo");
+        std::fs::write(root.join("tracked.txt"), "v1").expect("seed tracked");
+        let mut index = repo.index().expect("index");
+        index.add_path(Path::new("tracked.txt")).expect("stage");
+        index.write().expect("write index");
+        let tree = repo
+            .find_tree(index.write_tree().expect("tree"))
+            .expect("find tree");
+        let signature = git2::Signature::now("Test", "test@example.com").expect("signature");
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .expect("commit");
+        std::fs::write(root.join("tracked.txt"), "v2").expect("modify tracked");
+
+        let created = create_workspace_entry(&root, "src/new.rs", false, Some("fn main() {}"))
+            .expect("create");
+        assert_eq!(created.path, "src/new.rs");
+        assert_eq!(created.kind, "file");
+        assert!(create_workspace_entry(&root, "src/new.rs", false, None).is_err());
+
+        let listing = list_workspace_directory(&root, "").expect("list");
+        let names: Vec<&str> = listing.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(names, vec!["src", "tracked.txt"]);
+        assert_eq!(listing[0].git_status.as_deref(), Some("A"));
+        assert_eq!(listing[1].git_status.as_deref(), Some("M"));
+
+        let renamed = rename_workspace_entry(&root, "src/new.rs", "lib/main.rs").expect("rename");
+        assert_eq!(renamed.path, "lib/main.rs");
+        assert!(!root.join("src/new.rs").exists());
+        assert!(rename_workspace_entry(&root, "lib", "lib/nested").is_err());
+
+        delete_workspace_entry(&root, "lib").expect("delete dir");
+        assert!(!root.join("lib").exists());
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -2,11 +2,17 @@ use serde_json::json;
 use tauri::{AppHandle, State};
 
 use crate::remote_backend;
-use crate::shared::files_core::{file_read_core, file_write_core};
+use crate::shared::files_core::{
+    create_workspace_entry_core, delete_workspace_entry_core, file_read_core, file_write_core,
+    list_workspace_directory_core, read_workspace_text_file_core, rename_workspace_entry_core,
+    write_workspace_text_file_core,
+};
 use crate::state::AppState;
+use crate::types::{WorkspaceDirEntry, WorkspaceFileStat, WorkspaceTextFile};
 use self::io::TextFileResponse;
 use self::policy::{FileKind, FileScope};
 
+pub(crate) mod browser;
 pub(crate) mod io;
 pub(crate) mod ops;
 pub(crate) mod policy;
@@ -81,3 +87,165 @@ pub(crate) async fn file_write(
 ) -> Result<(), String> {
     file_write_impl(scope, kind, workspace_id, content, &*state, &app).await
 }
+
+#[tauri::command]
+pub(crate) async fn list_workspace_directory(
+    workspace_id: String,
+    path: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<WorkspaceDirEntry>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "list_workspace_directory",
+            json!({ "workspaceId": workspace_id, "path": path }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    list_workspace_directory_core(&state.workspaces, &workspace_id, path.as_deref().unwrap_or(""))
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn read_workspace_text_file(
+    workspace_id: String,
+    path: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceTextFile, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "read_workspace_text_file",
+            json!({ "workspaceId": workspace_id, "path": path }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    read_workspace_text_file_core(&state.workspaces, &workspace_id, &path).await
+}
+
+#[tauri::command]
+pub(crate) async fn write_workspace_text_file(
+    workspace_id: String,
+    path: String,
+    content: String,
+    encoding: Option<String>,
+    expected_hash: Option<String>,
+    expected_modified_ms: Option<i64>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceFileStat, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "write_workspace_text_file",
+            json!({
+                "workspaceId": workspace_id,
+                "path": path,
+                "content": content,
+                "encoding": encoding,
+                "expectedHash": expected_hash,
+                "expectedModifiedMs": expected_modified_ms,
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    write_workspace_text_file_core(
+        &state.workspaces,
+        &workspace_id,
+        &path,
+        &content,
+        encoding.as_deref(),
+        expected_hash.as_deref(),
+        expected_modified_ms,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn create_workspace_entry(
+    workspace_id: String,
+    path: String,
+    directory: Option<bool>,
+    content: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceDirEntry, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "create_workspace_entry",
+            json!({
+                "workspaceId": workspace_id,
+                "path": path,
+                "directory": directory,
+                "content": content,
+            }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    create_workspace_entry_core(
+        &state.workspaces,
+        &workspace_id,
+        &path,
+        directory.unwrap_or(false),
+        content.as_deref(),
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn rename_workspace_entry(
+    workspace_id: String,
+    from: String,
+    to: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceDirEntry, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "rename_workspace_entry",
+            json!({ "workspaceId": workspace_id, "from": from, "to": to }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    rename_workspace_entry_core(&state.workspaces, &workspace_id, &from, &to).await
+}
+
+#[tauri::command]
+pub(crate) async fn delete_workspace_entry(
+    workspace_id: String,
+    path: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        remote_backend::call_remote(
+            &*state,
+            app,
+            "delete_workspace_entry",
+            json!({ "workspaceId": workspace_id, "path": path }),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    delete_workspace_entry_core(&state.workspaces, &workspace_id, &path).await
+}
@@ -0,0 +1,224 @@
+use std::cmp::Reverse;
+
+use git2::{Branch, BranchType, Oid, Repository};
+
+use crate::types::BranchInfo;
+
+fn branch_tip(branch: &Branch) -> Option<Oid> {
+    branch.get().target()
+}
+
+fn commit_time(repo: &Repository, oid: Option<Oid>) -> i64 {
+    oid.and_then(|oid| repo.find_commit(oid).ok())
+        .map(|commit| commit.time().seconds())
+        .unwrap_or(0)
+}
+
+fn ahead_behind(repo: &Repository, local: Option<Oid>, other: Option<Oid>) -> (usize, usize) {
+    match (local, other) {
+        (Some(local), Some(other)) => repo.graph_ahead_behind(local, other).unwrap_or((0, 0)),
+        _ => (0, 0),
+    }
+}
+
+/// Local branches compare against their upstream; remote branches compare
+/// against HEAD, so the counts answer "what would merging this bring in".
+pub(crate) fn list_branches(
+    repo: &Repository,
+) -> Result<(Vec<BranchInfo>, Vec<BranchInfo>), String> {
+    let head = repo.head().ok();
+    let head_oid = head.as_ref().and_then(|head| head.target());
+    let head_name = head
+        .as_ref()
+        .filter(|head| head.is_branch())
+        .and_then(|head| head.shorthand().map(str::to_string));
+
+    let mut local = Vec::new();
+    for branch_result in repo
+        .branches(Some(BranchType::Local))
+        .map_err(|e| e.to_string())?
+    {
+        let (branch, _) = branch_result.map_err(|e| e.to_string())?;
+        let name = branch.name().ok().flatten().unwrap_or("").to_string();
+        if name.is_empty() {
+            continue;
+        }
+        let tip = branch_tip(&branch);
+        let upstream = branch.upstream().ok();
+        let upstream_name = upstream
+            .as_ref()
+            .and_then(|upstream| upstream.name().ok().flatten().map(str::to_string));
+        let (ahead, behind) = ahead_behind(repo, tip, upstream.as_ref().and_then(branch_tip));
+        local.push(BranchInfo {
+            current: head_name.as_deref() == Some(name.as_str()),
+            last_commit: commit_time(repo, tip),
+            name,
+            upstream: upstream_name,
+            ahead,
+            behind,
+            remote: None,
+        });
+    }
+
+    let mut remote = Vec::new();
+    for branch_result in repo
+        .branches(Some(BranchType::Remote))
+        .map_err(|e| e.to_string())?
+    {
+        let (branch, _) = branch_result.map_err(|e| e.to_string())?;
+        let name = branch.name().ok().flatten().unwrap_or("").to_string();
+        let Some((remote_name, short_name)) = name.split_once('/') else {
+            continue;
+        };
+        // `origin/HEAD` is a symbolic alias of the default branch.
+        if short_name == "HEAD" || branch.get().symbolic_target().is_some() {
+            continue;
+        }
+        let tip = branch_tip(&branch);
+        let (ahead, behind) = ahead_behind(repo, tip, head_oid);
+        remote.push(BranchInfo {
+            last_commit: commit_time(repo, tip),
+            remote: Some(remote_name.to_string()),
+            name,
+            upstream: None,
+            ahead,
+            behind,
+            current: false,
+        });
+    }
+
+    local.sort_by_key(|branch| Reverse(branch.last_commit));
+    remote.sort_by_key(|branch| Reverse(branch.last_commit));
+    Ok((local, remote))
+}
+
+/// Resolves `name` to a remote-tracking branch, accepting both `origin/foo`
+/// and a bare `foo` that exists on exactly one remote.
+pub(crate) fn find_remote_branch<'repo>(
+    repo: &'repo Repository,
+    name: &str,
+) -> Result<Option<Branch<'repo>>, String> {
+    if let Ok(branch) = repo.find_branch(name, BranchType::Remote) {
+        return Ok(Some(branch));
+    }
+    let mut matches = Vec::new();
+    for branch_result in repo
+        .branches(Some(BranchType::Remote))
+        .map_err(|e| e.to_string())?
+    {
+        let (branch, _) = branch_result.map_err(|e| e.to_string())?;
+        let matched = branch
+            .name()
+            .ok()
+            .flatten()
+            .and_then(|full| full.split_once('/'))
+            .is_some_and(|(_, short)| short == name);
+        if matched {
+            matches.push(branch);
+        }
+    }
+    match matches.len() {
+        0 => Ok(None),
+        1 => Ok(matches.pop()),
+        _ => Err(format!(
+            "{name} exists on several remotes; pick one (e.g. origin/{name})."
+        )),
+    }
+}
+
+/// Returns the local branch to check out for `name`, creating one that tracks
+/// the matching remote branch when no local branch exists yet.
+pub(crate) fn local_branch_for_checkout(repo: &Repository, name: &str) -> Result<String, String> {
+    if repo.find_branch(name, BranchType::Local).is_ok() {
+        return Ok(name.to_string());
+    }
+    let Some(remote_branch) = find_remote_branch(repo, name)? else {
+        return Err(format!("Branch {name} does not exist."));
+    };
+    let remote_name = remote_branch
+        .name()
+        .map_err(|e| e.to_string())?
+        .ok_or("Remote branch name is not valid UTF-8.")?
+        .to_string();
+    let local_name = remote_name
+        .split_once('/')
+        .map(|(_, short)| short.to_string())
+        .unwrap_or_else(|| remote_name.clone());
+    if repo.find_branch(&local_name, BranchType::Local).is_ok() {
+        return Err(format!(
+            "A local branch named {local_name} already exists; check it out instead."
+        ));
+    }
+    let commit = remote_branch
+        .get()
+        .peel_to_commit()
+        .map_err(|e| e.to_string())?;
+    let mut local = repo
+        .branch(&local_name, &commit, false)
+        .map_err(|e| e.to_string())?;
+    local
+        .set_upstream(Some(&remote_name))
+        .map_err(|e| e.to_string())?;
+    Ok(local_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkout_creates_tracking_branch_from_remote() {
+        let root = std::env::temp_dir().join(format!(
+            "codex-monitor-branches-test-{}",
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::create_dir_all(&root).expect("create temp repo root");
+        let repo = Repository::init(&root).expect("init repo");
+        let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+        let tree_id = repo.index().expect("index").write_tree().expect("tree");
+        let tree = repo.find_tree(tree_id).expect("find tree");
+        let oid = repo
+            .commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .expect("commit");
+        for remote in ["origin", "upstream"] {
+            repo.remote(remote, &format!("https://example.com/{remote}.git"))
+                .expect("add remote");
+        }
+        for remote_ref in [
+            "refs/remotes/origin/feature",
+            "refs/remotes/upstream/shared",
+        ] {
+            repo.reference(remote_ref, oid, false, "test")
+                .expect("remote ref");
+        }
+        repo.reference("refs/remotes/origin/shared", oid, false, "test")
+            .expect("remote ref");
+
+        let local = local_branch_for_checkout(&repo, "feature").expect("tracking branch");
+        assert_eq!(local, "feature");
+        let branch = repo
+            .find_branch("feature", BranchType::Local)
+            .expect("local branch");
+        let upstream = branch.upstream().expect("upstream");
+        assert_eq!(upstream.name().ok().flatten(), Some("origin/feature"));
+
+        assert!(local_branch_for_checkout(&repo, "shared").is_err());
+        assert_eq!(
+            local_branch_for_checkout(&repo, "upstream/shared").expect("explicit remote"),
+            "shared"
+        );
+        assert!(local_branch_for_checkout(&repo, "missing").is_err());
+
+        let (local_branches, remote_branches) = list_branches(&repo).expect("branches");
+        let feature = local_branches
+            .iter()
+            .find(|branch| branch.name == "feature")
+            .expect("feature listed");
+        assert_eq!(feature.upstream.as_deref(), Some("origin/feature"));
+        assert_eq!((feature.ahead, feature.behind), (0, 0));
+        assert_eq!(remote_branches.len(), 3);
+        assert!(remote_branches
+            .iter()
+            .all(|branch| branch.remote.is_some() && !branch.current));
+    }
+}
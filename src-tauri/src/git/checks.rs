@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use git2::Repository;
+
+use super::github::{
+    checks_state, gh_check_logs, gh_checks, gh_commit_sha, gh_pull_request_head_sha, GitHubClient,
+};
+use crate::backend::events::GitHubChecksFinished;
+use crate::types::{GitHubCheckLog, GitHubChecksResponse};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(20);
+/// CI can take a while to pick up a push; give up if nothing reports by then.
+const FIRST_REPORT_TIMEOUT: Duration = Duration::from_secs(180);
+const WATCH_TIMEOUT: Duration = Duration::from_secs(2 * 60 * 60);
+const MAX_POLL_ERRORS: usize = 5;
+
+pub(crate) struct ChecksTarget {
+    pub(crate) label: String,
+    pub(crate) sha: String,
+}
+
+/// `pr_number` selects the pull request head. Otherwise `git_ref` (HEAD by
+/// default) is resolved locally, falling back to the remote for refs that
+/// only exist there.
+pub(crate) async fn resolve_checks_target(
+    client: &GitHubClient,
+    repo_root: &Path,
+    repo_name: &str,
+    git_ref: Option<&str>,
+    pr_number: Option<u64>,
+) -> Result<ChecksTarget, String> {
+    if let Some(pr_number) = pr_number {
+        let sha = match client.pull_request_head_sha(repo_name, pr_number).await {
+            Ok(sha) => sha,
+            Err(api_error) => gh_pull_request_head_sha(repo_root, repo_name, pr_number)
+                .await
+                .map_err(|_| api_error)?,
+        };
+        return Ok(ChecksTarget {
+            label: format!("#{pr_number}"),
+            sha,
+        });
+    }
+
+    let git_ref = git_ref
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or("HEAD");
+    let local = {
+        let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+        let branch = repo
+            .head()
+            .ok()
+            .filter(|head| git_ref == "HEAD" && head.is_branch())
+            .and_then(|head| head.shorthand().map(str::to_string));
+        repo.revparse_single(git_ref)
+            .and_then(|object| object.peel_to_commit())
+            .ok()
+            .map(|commit| ChecksTarget {
+                label: branch.unwrap_or_else(|| git_ref.to_string()),
+                sha: commit.id().to_string(),
+            })
+    };
+    if let Some(target) = local {
+        return Ok(target);
+    }
+    let sha = match client.commit_sha(repo_name, git_ref).await {
+        Ok(sha) => sha,
+        Err(api_error) => gh_commit_sha(repo_root, repo_name, git_ref)
+            .await
+            .map_err(|_| api_error)?,
+    };
+    Ok(ChecksTarget {
+        label: git_ref.to_string(),
+        sha,
+    })
+}
+
+pub(crate) async fn fetch_checks(
+    client: &GitHubClient,
+    repo_root: &Path,
+    repo_name: &str,
+    target: &ChecksTarget,
+) -> Result<GitHubChecksResponse, String> {
+    let checks = match client.checks(repo_name, &target.sha).await {
+        Ok(checks) => checks,
+        Err(api_error) => gh_checks(repo_root, repo_name, &target.sha)
+            .await
+            .map_err(|_| api_error)?,
+    };
+    Ok(GitHubChecksResponse {
+        git_ref: target.label.clone(),
+        sha: target.sha.clone(),
+        state: checks_state(&checks).to_string(),
+        checks,
+        rate_limit: client.last_rate_limit(),
+    })
+}
+
+pub(crate) async fn fetch_check_logs(
+    client: &GitHubClient,
+    repo_root: &Path,
+    repo_name: &str,
+    target: &ChecksTarget,
+    check_id: Option<u64>,
+) -> Result<Vec<GitHubCheckLog>, String> {
+    match client.check_logs(repo_name, &target.sha, check_id).await {
+        Ok(logs) => Ok(logs),
+        Err(api_error) => gh_check_logs(repo_root, repo_name, &target.sha, check_id)
+            .await
+            .map_err(|_| api_error),
+    }
+}
+
+fn watched_commits() -> &'static Mutex<HashSet<String>> {
+    static WATCHED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    WATCHED.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// Polls in the background until no check is pending, then hands the final
+/// result to `emit`. Only one watcher runs per workspace and commit.
+pub(crate) fn watch_checks<F>(
+    client: GitHubClient,
+    repo_root: PathBuf,
+    repo_name: String,
+    target: ChecksTarget,
+    workspace_id: String,
+    emit: F,
+) where
+    F: Fn(GitHubChecksFinished) + Send + 'static,
+{
+    let key = format!("{workspace_id}:{}", target.sha);
+    let newly_watched = watched_commits()
+        .lock()
+        .map(|mut watched| watched.insert(key.clone()))
+        .unwrap_or(true);
+    if !newly_watched {
+        return;
+    }
+    tokio::spawn(async move {
+        let started = Instant::now();
+        let mut errors = 0;
+        let mut last = None;
+        loop {
+            match fetch_checks(&client, &repo_root, &repo_name, &target).await {
+                Ok(response) => {
+                    errors = 0;
+                    let waiting = match response.state.as_str() {
+                        "pending" => started.elapsed() < WATCH_TIMEOUT,
+                        "none" => started.elapsed() < FIRST_REPORT_TIMEOUT,
+                        _ => false,
+                    };
+                    last = Some(response);
+                    if !waiting {
+                        break;
+                    }
+                }
+                Err(error) => {
+                    errors += 1;
+                    if errors >= MAX_POLL_ERRORS {
+                        eprintln!("watch_checks: giving up on {}: {error}", target.sha);
+                        break;
+                    }
+                }
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        if let Ok(mut watched) = watched_commits().lock() {
+            watched.remove(&key);
+        }
+        if let Some(checks) = last {
+            emit(GitHubChecksFinished {
+                workspace_id,
+                checks,
+            });
+        }
+    });
+}
@@ -0,0 +1,170 @@
+use std::path::Path;
+
+use git2::{IndexEntry, Repository, RepositoryState};
+
+use super::bytes_look_binary;
+use crate::types::{GitConflictFile, GitConflictMarker};
+use crate::utils::normalize_git_path;
+
+pub(crate) fn operation_for_state(state: RepositoryState) -> Option<&'static str> {
+    match state {
+        RepositoryState::Merge => Some("merge"),
+        RepositoryState::Rebase
+        | RepositoryState::RebaseInteractive
+        | RepositoryState::RebaseMerge
+        | RepositoryState::ApplyMailboxOrRebase => Some("rebase"),
+        RepositoryState::CherryPick | RepositoryState::CherryPickSequence => Some("cherry-pick"),
+        RepositoryState::Revert | RepositoryState::RevertSequence => Some("revert"),
+        _ => None,
+    }
+}
+
+/// Finds `<<<<<<<` / `|||||||` / `=======` / `>>>>>>>` blocks; line numbers are 1-based.
+pub(crate) fn parse_conflict_markers(content: &str) -> Vec<GitConflictMarker> {
+    let mut markers = Vec::new();
+    let mut current: Option<GitConflictMarker> = None;
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        if line.starts_with("<<<<<<<") {
+            current = Some(GitConflictMarker {
+                start_line: line_number,
+                base_line: None,
+                separator_line: 0,
+                end_line: 0,
+            });
+            continue;
+        }
+        let Some(marker) = current.as_mut() else {
+            continue;
+        };
+        if line.starts_with("|||||||") && marker.separator_line == 0 {
+            marker.base_line = Some(line_number);
+        } else if line.starts_with("=======") && marker.separator_line == 0 {
+            marker.separator_line = line_number;
+        } else if line.starts_with(">>>>>>>") && marker.separator_line != 0 {
+            marker.end_line = line_number;
+            markers.extend(current.take());
+        }
+    }
+    markers
+}
+
+fn entry_content(repo: &Repository, entry: Option<&IndexEntry>) -> (Option<String>, bool) {
+    let Some(blob) = entry.and_then(|entry| repo.find_blob(entry.id).ok()) else {
+        return (None, false);
+    };
+    if bytes_look_binary(blob.content()) {
+        return (None, true);
+    }
+    (
+        Some(String::from_utf8_lossy(blob.content()).to_string()),
+        false,
+    )
+}
+
+pub(crate) fn conflicted_paths(repo: &Repository) -> Vec<String> {
+    let Ok(index) = repo.index() else {
+        return Vec::new();
+    };
+    if !index.has_conflicts() {
+        return Vec::new();
+    }
+    let Ok(conflicts) = index.conflicts() else {
+        return Vec::new();
+    };
+    conflicts
+        .filter_map(Result::ok)
+        .filter_map(|conflict| conflict.our.or(conflict.their).or(conflict.ancestor))
+        .map(|entry| normalize_git_path(&String::from_utf8_lossy(&entry.path)))
+        .collect()
+}
+
+pub(crate) fn collect_conflicts(
+    repo: &Repository,
+    repo_root: &Path,
+) -> Result<Vec<GitConflictFile>, String> {
+    let index = repo.index().map_err(|e| e.to_string())?;
+    if !index.has_conflicts() {
+        return Ok(Vec::new());
+    }
+
+    let mut files = Vec::new();
+    for conflict in index.conflicts().map_err(|e| e.to_string())? {
+        let conflict = conflict.map_err(|e| e.to_string())?;
+        let Some(path_bytes) = conflict
+            .our
+            .as_ref()
+            .or(conflict.their.as_ref())
+            .or(conflict.ancestor.as_ref())
+            .map(|entry| entry.path.clone())
+        else {
+            continue;
+        };
+        let path = String::from_utf8_lossy(&path_bytes).to_string();
+        let (base, base_binary) = entry_content(repo, conflict.ancestor.as_ref());
+        let (ours, ours_binary) = entry_content(repo, conflict.our.as_ref());
+        let (theirs, theirs_binary) = entry_content(repo, conflict.their.as_ref());
+
+        let working_bytes = std::fs::read(repo_root.join(&path)).ok();
+        let working_binary = working_bytes
+            .as_deref()
+            .map(bytes_look_binary)
+            .unwrap_or(false);
+        let working = working_bytes
+            .filter(|_| !working_binary)
+            .map(|bytes| String::from_utf8_lossy(&bytes).to_string());
+        let markers = working
+            .as_deref()
+            .map(parse_conflict_markers)
+            .unwrap_or_default();
+
+        files.push(GitConflictFile {
+            path: normalize_git_path(&path),
+            base,
+            ours,
+            theirs,
+            working,
+            markers,
+            ours_deleted: conflict.our.is_none(),
+            theirs_deleted: conflict.their.is_none(),
+            is_binary: base_binary || ours_binary || theirs_binary || working_binary,
+        });
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_two_and_three_way_markers() {
+        let content = concat!(
+            "intro\n",
+            "<<<<<<< HEAD\n",
+            "ours\n",
+            "=======\n",
+            "theirs\n",
+            ">>>>>>> feature\n",
+            "middle\n",
+            "<<<<<<< HEAD\n",
+            "ours again\n",
+            "||||||| base\n",
+            "original\n",
+            "=======\n",
+            "theirs again\n",
+            ">>>>>>> feature\n",
+            "<<<<<<< dangling\n",
+        );
+        let markers = parse_conflict_markers(content);
+        assert_eq!(markers.len(), 2);
+        assert_eq!(markers[0].start_line, 2);
+        assert_eq!(markers[0].base_line, None);
+        assert_eq!(markers[0].separator_line, 4);
+        assert_eq!(markers[0].end_line, 6);
+        assert_eq!(markers[1].start_line, 8);
+        assert_eq!(markers[1].base_line, Some(10));
+        assert_eq!(markers[1].separator_line, 12);
+        assert_eq!(markers[1].end_line, 14);
+    }
+}
@@ -0,0 +1,906 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use git2::Repository;
+use reqwest::header::{HeaderMap, LINK};
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use super::default_remote_name;
+use super::github::{
+    comment_from_value, gh_issue, gh_list_issues, gh_list_pull_requests, gh_pull_request_comments,
+    gh_pull_request_diff, issue_details_from_value, issue_from_value, optional_string,
+    parse_next_link, pull_request_from_value, string_field, GitHubClient,
+};
+use crate::git_utils::parse_remote_url;
+use crate::types::{
+    AppSettings, ForgeHostMapping, ForgeKind, GitHubIssue, GitHubIssueDetails,
+    GitHubIssuesResponse, GitHubPullRequest, GitHubPullRequestAuthor, GitHubPullRequestComment,
+    GitHubPullRequestsResponse,
+};
+
+const PAGE_SIZE: usize = 50;
+const LIST_LIMIT: usize = 50;
+const COMMENT_LIMIT: usize = 300;
+
+#[derive(Debug)]
+pub(crate) struct ForgeRemote {
+    pub(crate) kind: ForgeKind,
+    pub(crate) host: String,
+    /// `owner/name`; GitLab paths may include nested groups.
+    pub(crate) repo: String,
+    pub(crate) api_base_url: Option<String>,
+    pub(crate) token: Option<String>,
+    /// Whether `GITLAB_TOKEN` and friends may be sent to this host: only
+    /// well-known hosts and hosts mapped in settings, never guessed ones.
+    pub(crate) env_token_allowed: bool,
+}
+
+/// Issues and pull (merge) requests of a hosted repository, in the GitHub
+/// shapes the UI already renders.
+pub(crate) trait Forge {
+    async fn list_issues(&self) -> Result<GitHubIssuesResponse, String>;
+    async fn issue(&self, number: u64) -> Result<GitHubIssueDetails, String>;
+    async fn list_pull_requests(&self) -> Result<GitHubPullRequestsResponse, String>;
+    /// Unified diff text, in `git diff` format.
+    async fn pull_request_diff(&self, number: u64) -> Result<String, String>;
+    async fn pull_request_comments(
+        &self,
+        number: u64,
+    ) -> Result<Vec<GitHubPullRequestComment>, String>;
+}
+
+fn forge_name(kind: ForgeKind) -> &'static str {
+    match kind {
+        ForgeKind::GitHub => "GitHub",
+        ForgeKind::GitLab => "GitLab",
+        ForgeKind::Gitea => "Gitea",
+    }
+}
+
+/// Accepts hosts pasted as URLs, e.g. `https://git.example.com:8443/`.
+fn normalize_host(host: &str) -> String {
+    let host = host.trim();
+    let host = host.split_once("://").map_or(host, |(_, rest)| rest);
+    let host = host.split('/').next().unwrap_or(host);
+    host.split(':').next().unwrap_or(host).to_ascii_lowercase()
+}
+
+fn is_well_known_host(host: &str) -> bool {
+    matches!(
+        host,
+        "github.com" | "www.github.com" | "gitlab.com" | "codeberg.org"
+    )
+}
+
+/// Well-known hosts, plus the `gitlab.` / `gitea.` / `forgejo.` naming most
+/// self-hosted instances use.
+fn guess_kind(host: &str) -> Option<ForgeKind> {
+    if host == "github.com" || host == "www.github.com" {
+        return Some(ForgeKind::GitHub);
+    }
+    if host == "codeberg.org" {
+        return Some(ForgeKind::Gitea);
+    }
+    host.split('.').find_map(|label| match label {
+        "gitlab" => Some(ForgeKind::GitLab),
+        "gitea" | "forgejo" => Some(ForgeKind::Gitea),
+        _ => None,
+    })
+}
+
+/// Host mappings from settings win over guessing from the host name.
+pub(crate) fn detect_forge(
+    remote_url: &str,
+    mappings: &[ForgeHostMapping],
+) -> Result<ForgeRemote, String> {
+    let (host, repo) = parse_remote_url(remote_url)
+        .ok_or_else(|| format!("Could not parse the remote URL `{remote_url}`."))?;
+    let mapping = mappings
+        .iter()
+        .find(|mapping| normalize_host(&mapping.host) == host);
+    let kind = match mapping {
+        Some(mapping) => mapping.kind,
+        None => guess_kind(&host).ok_or_else(|| {
+            format!("Unknown forge host `{host}`. Map it to GitHub, GitLab or Gitea in settings.")
+        })?,
+    };
+    let setting = |value: Option<&String>| {
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    Ok(ForgeRemote {
+        kind,
+        api_base_url: setting(mapping.and_then(|mapping| mapping.api_base_url.as_ref())),
+        token: setting(mapping.and_then(|mapping| mapping.token.as_ref())),
+        env_token_allowed: mapping.is_some() || is_well_known_host(&host),
+        host,
+        repo,
+    })
+}
+
+fn default_api_base_url(kind: ForgeKind, host: &str) -> String {
+    match kind {
+        ForgeKind::GitHub => format!("https://{host}/api/v3"),
+        ForgeKind::GitLab => format!("https://{host}/api/v4"),
+        ForgeKind::Gitea => format!("https://{host}/api/v1"),
+    }
+}
+
+pub(crate) enum WorkspaceForge {
+    GitHub(GitHubForge),
+    GitLab(GitLabForge),
+    Gitea(GiteaForge),
+}
+
+impl WorkspaceForge {
+    pub(crate) fn for_repo(repo_root: &Path, settings: &AppSettings) -> Result<Self, String> {
+        let remote_url = {
+            let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+            let name = default_remote_name(&repo)?;
+            let remote = repo.find_remote(&name).map_err(|e| e.to_string())?;
+            remote
+                .url()
+                .ok_or("Remote has no URL configured.")?
+                .to_string()
+        };
+        let remote = detect_forge(&remote_url, &settings.forge_hosts)?;
+        match remote.kind {
+            ForgeKind::GitHub => {
+                let api_base_url = match (&remote.api_base_url, remote.host.as_str()) {
+                    (Some(url), _) => Some(url.clone()),
+                    (None, "github.com" | "www.github.com") => settings.github_api_base_url.clone(),
+                    (None, host) => Some(default_api_base_url(ForgeKind::GitHub, host)),
+                };
+                let token = remote
+                    .token
+                    .clone()
+                    .or_else(|| GitHubClient::settings_token(settings));
+                Ok(Self::GitHub(GitHubForge {
+                    client: GitHubClient::new(api_base_url.as_deref(), token)?,
+                    repo_root: repo_root.to_path_buf(),
+                    repo: remote.repo,
+                }))
+            }
+            ForgeKind::GitLab => Ok(Self::GitLab(GitLabForge::new(remote)?)),
+            ForgeKind::Gitea => Ok(Self::Gitea(GiteaForge::new(remote)?)),
+        }
+    }
+}
+
+impl Forge for WorkspaceForge {
+    async fn list_issues(&self) -> Result<GitHubIssuesResponse, String> {
+        match self {
+            Self::GitHub(forge) => forge.list_issues().await,
+            Self::GitLab(forge) => forge.list_issues().await,
+            Self::Gitea(forge) => forge.list_issues().await,
+        }
+    }
+
+    async fn issue(&self, number: u64) -> Result<GitHubIssueDetails, String> {
+        match self {
+            Self::GitHub(forge) => forge.issue(number).await,
+            Self::GitLab(forge) => forge.issue(number).await,
+            Self::Gitea(forge) => forge.issue(number).await,
+        }
+    }
+
+    async fn list_pull_requests(&self) -> Result<GitHubPullRequestsResponse, String> {
+        match self {
+            Self::GitHub(forge) => forge.list_pull_requests().await,
+            Self::GitLab(forge) => forge.list_pull_requests().await,
+            Self::Gitea(forge) => forge.list_pull_requests().await,
+        }
+    }
+
+    async fn pull_request_diff(&self, number: u64) -> Result<String, String> {
+        match self {
+            Self::GitHub(forge) => forge.pull_request_diff(number).await,
+            Self::GitLab(forge) => forge.pull_request_diff(number).await,
+            Self::Gitea(forge) => forge.pull_request_diff(number).await,
+        }
+    }
+
+    async fn pull_request_comments(
+        &self,
+        number: u64,
+    ) -> Result<Vec<GitHubPullRequestComment>, String> {
+        match self {
+            Self::GitHub(forge) => forge.pull_request_comments(number).await,
+            Self::GitLab(forge) => forge.pull_request_comments(number).await,
+            Self::Gitea(forge) => forge.pull_request_comments(number).await,
+        }
+    }
+}
+
+pub(crate) struct GitHubForge {
+    client: GitHubClient,
+    repo_root: PathBuf,
+    repo: String,
+}
+
+// `gh` may hold credentials the API client lacks; keep the API error when it
+// cannot help.
+impl Forge for GitHubForge {
+    async fn list_issues(&self) -> Result<GitHubIssuesResponse, String> {
+        match self.client.list_issues(&self.repo).await {
+            Ok(response) => Ok(response),
+            Err(api_error) => gh_list_issues(&self.repo_root, &self.repo)
+                .await
+                .map_err(|_| api_error),
+        }
+    }
+
+    async fn issue(&self, number: u64) -> Result<GitHubIssueDetails, String> {
+        match self.client.issue(&self.repo, number).await {
+            Ok(issue) => Ok(issue),
+            Err(api_error) => gh_issue(&self.repo_root, &self.repo, number)
+                .await
+                .map_err(|_| api_error),
+        }
+    }
+
+    async fn list_pull_requests(&self) -> Result<GitHubPullRequestsResponse, String> {
+        match self.client.list_pull_requests(&self.repo).await {
+            Ok(response) => Ok(response),
+            Err(api_error) => gh_list_pull_requests(&self.repo_root, &self.repo)
+                .await
+                .map_err(|_| api_error),
+        }
+    }
+
+    async fn pull_request_diff(&self, number: u64) -> Result<String, String> {
+        match self.client.pull_request_diff(&self.repo, number).await {
+            Ok(diff_text) => Ok(diff_text),
+            Err(api_error) => gh_pull_request_diff(&self.repo_root, &self.repo, number)
+                .await
+                .map_err(|_| api_error),
+        }
+    }
+
+    async fn pull_request_comments(
+        &self,
+        number: u64,
+    ) -> Result<Vec<GitHubPullRequestComment>, String> {
+        match self.client.pull_request_comments(&self.repo, number).await {
+            Ok(comments) => Ok(comments),
+            Err(api_error) => gh_pull_request_comments(&self.repo_root, &self.repo, number)
+                .await
+                .map_err(|_| api_error),
+        }
+    }
+}
+
+struct ForgeResponse {
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+/// Minimal REST client shared by GitLab and Gitea; both paginate with
+/// `Link: rel="next"` and report totals in a header.
+struct ForgeHttp {
+    http: reqwest::Client,
+    kind: ForgeKind,
+    base_url: String,
+    token: Option<String>,
+}
+
+impl ForgeHttp {
+    fn new(remote: &ForgeRemote, token_env: &[&str]) -> Result<Self, String> {
+        let base_url = remote
+            .api_base_url
+            .clone()
+            .unwrap_or_else(|| default_api_base_url(remote.kind, &remote.host))
+            .trim_end_matches('/')
+            .to_string();
+        let token = remote.token.clone().or_else(|| {
+            if !remote.env_token_allowed {
+                return None;
+            }
+            token_env
+                .iter()
+                .find_map(|name| std::env::var(name).ok())
+                .filter(|token| !token.trim().is_empty())
+        });
+        let http = reqwest::Client::builder()
+            .user_agent("CodexMonitor")
+            .connect_timeout(Duration::from_secs(10))
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| {
+                format!(
+                    "Failed to configure {} client: {e}",
+                    forge_name(remote.kind)
+                )
+            })?;
+        Ok(Self {
+            http,
+            kind: remote.kind,
+            base_url,
+            token,
+        })
+    }
+
+    /// The web root next to the API, e.g. `https://gitlab.example.com`.
+    fn web_base_url(&self) -> &str {
+        self.base_url
+            .strip_suffix("/api/v4")
+            .or_else(|| self.base_url.strip_suffix("/api/v1"))
+            .unwrap_or(&self.base_url)
+    }
+
+    async fn get(&self, path: &str) -> Result<ForgeResponse, String> {
+        let name = forge_name(self.kind);
+        let url = if path.starts_with("http://") || path.starts_with("https://") {
+            path.to_string()
+        } else {
+            format!("{}{path}", self.base_url)
+        };
+        let mut request = self.http.get(&url);
+        if let Some(token) = &self.token {
+            request = match self.kind {
+                ForgeKind::GitLab => request.header("PRIVATE-TOKEN", token),
+                _ => request.header("Authorization", format!("token {token}")),
+            };
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("{name} API request failed: {e}"))?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| format!("{name} API request failed: {e}"))?
+            .to_vec();
+        if !status.is_success() {
+            return Err(forge_api_error(self.kind, status, &body));
+        }
+        Ok(ForgeResponse { headers, body })
+    }
+
+    async fn get_json(&self, path: &str) -> Result<Value, String> {
+        let response = self.get(path).await?;
+        serde_json::from_slice(&response.body).map_err(|e| e.to_string())
+    }
+
+    /// Returns up to `limit` items and the total the server reports, if any.
+    async fn get_paginated(
+        &self,
+        path: &str,
+        limit: usize,
+    ) -> Result<(Vec<Value>, Option<usize>), String> {
+        let mut items = Vec::new();
+        let mut total = None;
+        let mut next = Some(path.to_string());
+        while let Some(url) = next.take() {
+            let response = self.get(&url).await?;
+            if total.is_none() {
+                total = ["x-total", "x-total-count"].iter().find_map(|name| {
+                    response
+                        .headers
+                        .get(*name)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| value.parse().ok())
+                });
+            }
+            let page: Vec<Value> =
+                serde_json::from_slice(&response.body).map_err(|e| e.to_string())?;
+            if page.is_empty() {
+                break;
+            }
+            items.extend(page);
+            if items.len() >= limit {
+                break;
+            }
+            next = response
+                .headers
+                .get(LINK)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_next_link);
+        }
+        items.truncate(limit);
+        Ok((items, total))
+    }
+}
+
+fn forge_api_error(kind: ForgeKind, status: StatusCode, body: &[u8]) -> String {
+    let name = forge_name(kind);
+    // GitLab reports `message` (sometimes an object) or `error`.
+    let message = serde_json::from_slice::<Value>(body)
+        .ok()
+        .and_then(|value| {
+            let message = value.get("message").or_else(|| value.get("error"))?;
+            Some(match message.as_str() {
+                Some(text) => text.to_string(),
+                None => message.to_string(),
+            })
+        })
+        .unwrap_or_else(|| {
+            status
+                .canonical_reason()
+                .unwrap_or("Request failed")
+                .to_string()
+        });
+    match status {
+        StatusCode::UNAUTHORIZED => {
+            format!(
+                "{name} rejected the token ({message}). Check the forge host token in settings."
+            )
+        }
+        StatusCode::NOT_FOUND => {
+            format!("{name} API: {message}. Private repositories need a token in the forge host settings.")
+        }
+        _ => format!("{name} API error ({}): {message}", status.as_u16()),
+    }
+}
+
+pub(crate) struct GitLabForge {
+    http: ForgeHttp,
+    repo: String,
+    /// URL-encoded project path, GitLab's alternative to numeric project ids.
+    project: String,
+}
+
+impl GitLabForge {
+    fn new(remote: ForgeRemote) -> Result<Self, String> {
+        Ok(Self {
+            http: ForgeHttp::new(&remote, &["GITLAB_TOKEN"])?,
+            project: remote.repo.replace('/', "%2F"),
+            repo: remote.repo,
+        })
+    }
+
+    /// Discussion notes on an issue or merge request (`noun` is the path
+    /// segment), with links anchored under `url`.
+    async fn notes(
+        &self,
+        noun: &str,
+        iid: u64,
+        url: &str,
+    ) -> Result<Vec<GitHubPullRequestComment>, String> {
+        let (notes, _) = self
+            .http
+            .get_paginated(
+                &format!(
+                    "/projects/{}/{noun}/{iid}/notes?sort=asc&order_by=created_at&per_page={PAGE_SIZE}",
+                    self.project
+                ),
+                COMMENT_LIMIT,
+            )
+            .await?;
+        // System notes record pushes and label changes, not discussion.
+        Ok(notes
+            .iter()
+            .filter(|note| !note.get("system").and_then(Value::as_bool).unwrap_or(false))
+            .filter_map(|note| gitlab_note(note, url))
+            .collect())
+    }
+
+    fn merge_request_url(&self, iid: u64) -> String {
+        format!(
+            "{}/{}/-/merge_requests/{iid}",
+            self.http.web_base_url(),
+            self.repo
+        )
+    }
+}
+
+fn gitlab_author(value: &Value) -> Option<GitHubPullRequestAuthor> {
+    let username = value.get("author")?.get("username")?.as_str()?;
+    Some(GitHubPullRequestAuthor {
+        login: username.to_string(),
+    })
+}
+
+fn gitlab_issue(value: &Value) -> Option<GitHubIssue> {
+    Some(GitHubIssue {
+        number: value.get("iid")?.as_u64()?,
+        title: string_field(value, "title"),
+        url: string_field(value, "web_url"),
+        updated_at: string_field(value, "updated_at"),
+    })
+}
+
+fn gitlab_merge_request(value: &Value) -> Option<GitHubPullRequest> {
+    let flag = |key: &str| value.get(key).and_then(Value::as_bool).unwrap_or(false);
+    Some(GitHubPullRequest {
+        number: value.get("iid")?.as_u64()?,
+        title: string_field(value, "title"),
+        url: string_field(value, "web_url"),
+        updated_at: string_field(value, "updated_at"),
+        created_at: string_field(value, "created_at"),
+        body: string_field(value, "description"),
+        head_ref_name: string_field(value, "source_branch"),
+        base_ref_name: string_field(value, "target_branch"),
+        // Older GitLab releases only report `work_in_progress`.
+        is_draft: flag("draft") || flag("work_in_progress"),
+        author: gitlab_author(value),
+    })
+}
+
+/// Notes on diff lines carry a `position`; new-side lines map to `RIGHT`.
+fn gitlab_note(value: &Value, merge_request_url: &str) -> Option<GitHubPullRequestComment> {
+    let id = value.get("id")?.as_u64()?;
+    let position = value.get("position").filter(|position| !position.is_null());
+    let line = |key: &str| {
+        position
+            .and_then(|position| position.get(key))
+            .and_then(Value::as_u64)
+            .map(|line| line as u32)
+    };
+    let new_line = line("new_line");
+    let old_line = line("old_line");
+    Some(GitHubPullRequestComment {
+        id,
+        body: string_field(value, "body"),
+        created_at: string_field(value, "created_at"),
+        url: format!("{merge_request_url}#note_{id}"),
+        author: gitlab_author(value),
+        path: position.and_then(|position| {
+            optional_string(position, "new_path").or_else(|| optional_string(position, "old_path"))
+        }),
+        line: new_line.or(old_line),
+        start_line: None,
+        original_line: old_line,
+        side: position.map(|_| if new_line.is_some() { "RIGHT" } else { "LEFT" }.to_string()),
+        diff_hunk: None,
+        in_reply_to_id: None,
+    })
+}
+
+/// GitLab returns per-file hunks without git headers; rebuild them so the
+/// shared diff parser sees adds, deletes and renames.
+fn gitlab_diff_text(changes: &[Value]) -> String {
+    let mut text = String::new();
+    for change in changes {
+        let flag = |key: &str| change.get(key).and_then(Value::as_bool).unwrap_or(false);
+        let old_path = string_field(change, "old_path");
+        let new_path = string_field(change, "new_path");
+        text.push_str(&format!("diff --git a/{old_path} b/{new_path}\n"));
+        if flag("new_file") {
+            text.push_str(&format!(
+                "new file mode {}\n",
+                string_field(change, "b_mode")
+            ));
+        } else if flag("deleted_file") {
+            text.push_str(&format!(
+                "deleted file mode {}\n",
+                string_field(change, "a_mode")
+            ));
+        } else if flag("renamed_file") {
+            text.push_str(&format!("rename from {old_path}\nrename to {new_path}\n"));
+        }
+        let diff = string_field(change, "diff");
+        if !diff.is_empty() {
+            let old = if flag("new_file") {
+                "/dev/null".to_string()
+            } else {
+                format!("a/{old_path}")
+            };
+            let new = if flag("deleted_file") {
+                "/dev/null".to_string()
+            } else {
+                format!("b/{new_path}")
+            };
+            text.push_str(&format!("--- {old}\n+++ {new}\n{diff}"));
+            if !diff.ends_with('\n') {
+                text.push('\n');
+            }
+        }
+    }
+    text
+}
+
+impl Forge for GitLabForge {
+    async fn list_issues(&self) -> Result<GitHubIssuesResponse, String> {
+        let (items, total) = self
+            .http
+            .get_paginated(
+                &format!(
+                    "/projects/{}/issues?state=opened&order_by=updated_at&per_page={PAGE_SIZE}",
+                    self.project
+                ),
+                LIST_LIMIT,
+            )
+            .await?;
+        let issues: Vec<GitHubIssue> = items.iter().filter_map(gitlab_issue).collect();
+        Ok(GitHubIssuesResponse {
+            total: total.unwrap_or(issues.len()),
+            issues,
+            rate_limit: None,
+        })
+    }
+
+    async fn issue(&self, number: u64) -> Result<GitHubIssueDetails, String> {
+        let value = self
+            .http
+            .get_json(&format!("/projects/{}/issues/{number}", self.project))
+            .await?;
+        let issue =
+            gitlab_issue(&value).ok_or_else(|| format!("Issue #{number} was not found."))?;
+        let comments = self.notes("issues", number, &issue.url).await?;
+        // GitLab says `opened` where GitHub and Gitea say `open`.
+        let state = match string_field(&value, "state").as_str() {
+            "opened" => "open".to_string(),
+            state => state.to_string(),
+        };
+        Ok(GitHubIssueDetails {
+            body: string_field(&value, "description"),
+            state,
+            labels: value
+                .get("labels")
+                .and_then(Value::as_array)
+                .map(|labels| {
+                    labels
+                        .iter()
+                        .filter_map(|label| label.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            author: gitlab_author(&value),
+            comments,
+            issue,
+        })
+    }
+
+    async fn list_pull_requests(&self) -> Result<GitHubPullRequestsResponse, String> {
+        let (items, total) = self
+            .http
+            .get_paginated(
+                &format!(
+                    "/projects/{}/merge_requests?state=opened&order_by=updated_at&per_page={PAGE_SIZE}",
+                    self.project
+                ),
+                LIST_LIMIT,
+            )
+            .await?;
+        let pull_requests: Vec<GitHubPullRequest> =
+            items.iter().filter_map(gitlab_merge_request).collect();
+        Ok(GitHubPullRequestsResponse {
+            total: total.unwrap_or(pull_requests.len()),
+            pull_requests,
+            rate_limit: None,
+        })
+    }
+
+    async fn pull_request_diff(&self, number: u64) -> Result<String, String> {
+        let diffs = self
+            .http
+            .get_paginated(
+                &format!(
+                    "/projects/{}/merge_requests/{number}/diffs?per_page={PAGE_SIZE}",
+                    self.project
+                ),
+                usize::MAX,
+            )
+            .await;
+        let changes = match diffs {
+            Ok((changes, _)) => changes,
+            // `/diffs` arrived in GitLab 15.7; older servers only have the
+            // unpaginated `/changes`.
+            Err(diffs_error) => self
+                .http
+                .get_json(&format!(
+                    "/projects/{}/merge_requests/{number}/changes",
+                    self.project
+                ))
+                .await
+                .map_err(|_| diffs_error)?
+                .get("changes")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default(),
+        };
+        Ok(gitlab_diff_text(&changes))
+    }
+
+    async fn pull_request_comments(
+        &self,
+        number: u64,
+    ) -> Result<Vec<GitHubPullRequestComment>, String> {
+        self.notes("merge_requests", number, &self.merge_request_url(number))
+            .await
+    }
+}
+
+/// Gitea and Forgejo mirror GitHub's REST shapes closely enough to reuse its
+/// value mappers.
+pub(crate) struct GiteaForge {
+    http: ForgeHttp,
+    repo: String,
+}
+
+impl GiteaForge {
+    fn new(remote: ForgeRemote) -> Result<Self, String> {
+        Ok(Self {
+            http: ForgeHttp::new(&remote, &["GITEA_TOKEN", "FORGEJO_TOKEN"])?,
+            repo: remote.repo,
+        })
+    }
+}
+
+impl Forge for GiteaForge {
+    async fn list_issues(&self) -> Result<GitHubIssuesResponse, String> {
+        let (items, total) = self
+            .http
+            .get_paginated(
+                &format!(
+                    "/repos/{}/issues?state=open&type=issues&limit={PAGE_SIZE}",
+                    self.repo
+                ),
+                LIST_LIMIT,
+            )
+            .await?;
+        let issues: Vec<GitHubIssue> = items.iter().filter_map(issue_from_value).collect();
+        Ok(GitHubIssuesResponse {
+            total: total.unwrap_or(issues.len()),
+            issues,
+            rate_limit: None,
+        })
+    }
+
+    async fn issue(&self, number: u64) -> Result<GitHubIssueDetails, String> {
+        let value = self
+            .http
+            .get_json(&format!("/repos/{}/issues/{number}", self.repo))
+            .await?;
+        let comments = self.pull_request_comments(number).await?;
+        issue_details_from_value(&value, comments)
+            .ok_or_else(|| format!("Issue #{number} was not found."))
+    }
+
+    async fn list_pull_requests(&self) -> Result<GitHubPullRequestsResponse, String> {
+        let (items, total) = self
+            .http
+            .get_paginated(
+                &format!("/repos/{}/pulls?state=open&limit={PAGE_SIZE}", self.repo),
+                LIST_LIMIT,
+            )
+            .await?;
+        let pull_requests: Vec<GitHubPullRequest> = items
+            .iter()
+            .filter_map(pull_request_from_value)
+            .map(|mut pull_request| {
+                // Gitea has no draft flag; drafts are marked by title prefix.
+                let title = pull_request.title.to_ascii_lowercase();
+                pull_request.is_draft = pull_request.is_draft
+                    || ["wip:", "[wip]", "draft:", "[draft]"]
+                        .iter()
+                        .any(|prefix| title.starts_with(prefix));
+                pull_request
+            })
+            .collect();
+        Ok(GitHubPullRequestsResponse {
+            total: total.unwrap_or(pull_requests.len()),
+            pull_requests,
+            rate_limit: None,
+        })
+    }
+
+    async fn pull_request_diff(&self, number: u64) -> Result<String, String> {
+        let response = self
+            .http
+            .get(&format!("/repos/{}/pulls/{number}.diff", self.repo))
+            .await?;
+        Ok(String::from_utf8_lossy(&response.body).to_string())
+    }
+
+    async fn pull_request_comments(
+        &self,
+        number: u64,
+    ) -> Result<Vec<GitHubPullRequestComment>, String> {
+        let (comments, _) = self
+            .http
+            .get_paginated(
+                &format!(
+                    "/repos/{}/issues/{number}/comments?limit={PAGE_SIZE}",
+                    self.repo
+                ),
+                COMMENT_LIMIT,
+            )
+            .await?;
+        Ok(comments.iter().filter_map(comment_from_value).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse_pr_diff;
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn forges_are_detected_from_hosts_and_mappings() {
+        let mappings = vec![ForgeHostMapping {
+            host: "https://git.example.com/".to_string(),
+            kind: ForgeKind::Gitea,
+            api_base_url: Some(" ".to_string()),
+            token: Some("secret".to_string()),
+        }];
+
+        let github = detect_forge("git@github.com:openai/codex.git", &mappings).expect("github");
+        assert_eq!(github.kind, ForgeKind::GitHub);
+        assert_eq!(github.repo, "openai/codex");
+
+        let gitlab = detect_forge(
+            "ssh://git@gitlab.example.com:2222/group/sub/project.git",
+            &mappings,
+        )
+        .expect("gitlab");
+        assert_eq!(gitlab.kind, ForgeKind::GitLab);
+        assert_eq!(gitlab.host, "gitlab.example.com");
+        assert_eq!(gitlab.repo, "group/sub/project");
+        assert!(!gitlab.env_token_allowed);
+
+        let mapped = detect_forge("https://GIT.example.com/team/app", &mappings).expect("mapped");
+        assert_eq!(mapped.kind, ForgeKind::Gitea);
+        assert_eq!(mapped.api_base_url, None);
+        assert_eq!(mapped.token.as_deref(), Some("secret"));
+        assert!(mapped.env_token_allowed);
+
+        let codeberg = detect_forge("https://codeberg.org/forgejo/forgejo", &[]).expect("codeberg");
+        assert_eq!(codeberg.kind, ForgeKind::Gitea);
+        assert!(codeberg.env_token_allowed);
+
+        assert!(detect_forge("git@git.example.com:team/app.git", &[]).is_err());
+        assert!(detect_forge("/srv/git/app.git", &[]).is_err());
+    }
+
+    #[test]
+    fn gitlab_responses_map_to_github_shapes() {
+        let text = gitlab_diff_text(&[
+            json!({
+                "old_path": "src/old.rs",
+                "new_path": "src/new.rs",
+                "renamed_file": true,
+                "diff": "@@ -1 +1 @@\n-a\n+b\n",
+            }),
+            json!({
+                "old_path": "docs/added.md",
+                "new_path": "docs/added.md",
+                "new_file": true,
+                "b_mode": "100644",
+                "diff": "@@ -0,0 +1 @@\n+hello",
+            }),
+        ]);
+        let diffs = parse_pr_diff(&text);
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(
+            (diffs[0].path.as_str(), diffs[0].status.as_str()),
+            ("src/new.rs", "R")
+        );
+        assert_eq!(
+            (diffs[1].path.as_str(), diffs[1].status.as_str()),
+            ("docs/added.md", "A")
+        );
+        assert!(diffs[1]
+            .diff
+            .contains("--- /dev/null\n+++ b/docs/added.md\n@@"));
+
+        let note = gitlab_note(
+            &json!({
+                "id": 7,
+                "body": "Typo here",
+                "created_at": "2024-01-01T00:00:00Z",
+                "author": { "username": "reviewer" },
+                "position": { "new_path": "src/new.rs", "new_line": 3, "old_line": null },
+            }),
+            "https://gitlab.example.com/group/project/-/merge_requests/4",
+        )
+        .expect("note");
+        assert_eq!(note.path.as_deref(), Some("src/new.rs"));
+        assert_eq!(note.line, Some(3));
+        assert_eq!(note.side.as_deref(), Some("RIGHT"));
+        assert_eq!(
+            note.url,
+            "https://gitlab.example.com/group/project/-/merge_requests/4#note_7"
+        );
+        assert_eq!(
+            note.author.map(|author| author.login).as_deref(),
+            Some("reviewer")
+        );
+    }
+}
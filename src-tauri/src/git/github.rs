@@ -0,0 +1,2020 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use git2::{Oid, Repository, Sort};
+use reqwest::header::{HeaderMap, ACCEPT, CONTENT_TYPE, ETAG, IF_NONE_MATCH, LINK};
+use reqwest::{Method, StatusCode};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::io::AsyncWriteExt;
+
+use crate::shared::process_core::tokio_command;
+use crate::types::{
+    AppSettings, GitHubCheck, GitHubCheckLog, GitHubIssue, GitHubIssueDetails,
+    GitHubIssuesResponse, GitHubPullRequest, GitHubPullRequestAuthor, GitHubPullRequestComment,
+    GitHubPullRequestReview, GitHubPullRequestsResponse, GitHubRateLimit, GitHubReviewCommentDraft,
+};
+
+pub(crate) const DEFAULT_GITHUB_API_BASE_URL: &str = "https://api.github.com";
+const JSON_ACCEPT: &str = "application/vnd.github+json";
+const DIFF_ACCEPT: &str = "application/vnd.github.v3.diff";
+const SHA_ACCEPT: &str = "application/vnd.github.sha";
+const API_VERSION: &str = "2022-11-28";
+const UNREACHABLE_ERROR: &str = "Could not reach the GitHub API";
+const PAGE_SIZE: usize = 100;
+const LIST_LIMIT: usize = 50;
+const COMMENT_LIMIT: usize = 300;
+const MAX_CACHED_RESPONSES: usize = 256;
+const LOG_CONTEXT_LINES: usize = 30;
+const MAX_LOG_LINES: usize = 200;
+const FAILING_CONCLUSIONS: &[&str] = &[
+    "failure",
+    "timed_out",
+    "cancelled",
+    "action_required",
+    "startup_failure",
+    "error",
+];
+const GH_PULL_REQUEST_FIELDS: &str =
+    "number,title,url,updatedAt,createdAt,body,headRefName,baseRefName,isDraft,author";
+
+struct CachedResponse {
+    etag: String,
+    body: Vec<u8>,
+    next: Option<String>,
+}
+
+/// Shared across clients so repeated polls can be answered with 304s, which
+/// do not count against the rate limit.
+fn etag_cache() -> &'static Mutex<HashMap<String, CachedResponse>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, CachedResponse>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+struct ApiResponse {
+    body: Vec<u8>,
+    next: Option<String>,
+}
+
+pub(crate) struct PullRequestDraft {
+    pub(crate) title: String,
+    pub(crate) body: String,
+    pub(crate) head: String,
+    pub(crate) base: String,
+    pub(crate) draft: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct PullRequestRefs {
+    #[serde(rename = "headRefName")]
+    pub(crate) head_ref: String,
+    #[serde(rename = "baseRefName")]
+    pub(crate) base_ref: String,
+    #[serde(rename = "isCrossRepository")]
+    pub(crate) cross_repository: bool,
+    pub(crate) title: String,
+}
+
+#[derive(Default)]
+pub(crate) struct PullRequestUpdate {
+    pub(crate) title: Option<String>,
+    pub(crate) body: Option<String>,
+    pub(crate) base: Option<String>,
+}
+
+pub(crate) struct GitHubClient {
+    http: reqwest::Client,
+    base_url: String,
+    token: Option<String>,
+    rate_limit: Mutex<Option<GitHubRateLimit>>,
+}
+
+impl GitHubClient {
+    pub(crate) fn new(base_url: Option<&str>, token: Option<String>) -> Result<Self, String> {
+        let base_url = base_url
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or(DEFAULT_GITHUB_API_BASE_URL)
+            .trim_end_matches('/')
+            .to_string();
+        let http = reqwest::Client::builder()
+            .user_agent("CodexMonitor")
+            .connect_timeout(Duration::from_secs(10))
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(|e| format!("Failed to configure GitHub client: {e}"))?;
+        Ok(Self {
+            http,
+            base_url,
+            token: token
+                .map(|token| token.trim().to_string())
+                .filter(|token| !token.is_empty()),
+            rate_limit: Mutex::new(None),
+        })
+    }
+
+    pub(crate) fn from_settings(settings: &AppSettings) -> Result<Self, String> {
+        Self::new(
+            settings.github_api_base_url.as_deref(),
+            Self::settings_token(settings),
+        )
+    }
+
+    /// The token from settings, then `GITHUB_TOKEN` / `GH_TOKEN`.
+    pub(crate) fn settings_token(settings: &AppSettings) -> Option<String> {
+        settings
+            .github_token
+            .clone()
+            .filter(|token| !token.trim().is_empty())
+            .or_else(|| std::env::var("GITHUB_TOKEN").ok())
+            .or_else(|| std::env::var("GH_TOKEN").ok())
+    }
+
+    /// Whether a failed write may be retried through `gh`: only when this
+    /// client had no token, or the request never reached the server. A
+    /// rejected or timed-out write is reported as is, since `gh` could post
+    /// it twice or hide the real error.
+    pub(crate) fn write_falls_back_to_gh(&self, api_error: &str) -> bool {
+        self.token.is_none() || api_error.starts_with(UNREACHABLE_ERROR)
+    }
+
+    /// The limits reported by the most recent response from this client.
+    pub(crate) fn last_rate_limit(&self) -> Option<GitHubRateLimit> {
+        self.rate_limit.lock().ok().and_then(|limit| limit.clone())
+    }
+
+    fn url_for(&self, path: &str) -> String {
+        if path.starts_with("http://") || path.starts_with("https://") {
+            path.to_string()
+        } else {
+            format!("{}{path}", self.base_url)
+        }
+    }
+
+    /// GitHub Enterprise serves REST under `/api/v3` and GraphQL under
+    /// `/api/graphql`; github.com uses `/graphql` next to the REST root.
+    fn graphql_url(&self) -> String {
+        match self.base_url.strip_suffix("/api/v3") {
+            Some(host) => format!("{host}/api/graphql"),
+            None => format!("{}/graphql", self.base_url),
+        }
+    }
+
+    fn record_rate_limit(&self, headers: &HeaderMap) -> Option<GitHubRateLimit> {
+        let rate_limit = parse_rate_limit(headers);
+        if let (Some(limit), Ok(mut last)) = (rate_limit.clone(), self.rate_limit.lock()) {
+            *last = Some(limit);
+        }
+        rate_limit
+    }
+
+    async fn get(&self, path: &str, accept: &str) -> Result<ApiResponse, String> {
+        let url = self.url_for(path);
+        let cache_key = format!("{accept} {url}");
+        let cached_etag = etag_cache()
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(&cache_key).map(|cached| cached.etag.clone()));
+
+        let mut request = self
+            .http
+            .get(&url)
+            .header(ACCEPT, accept)
+            .header("X-GitHub-Api-Version", API_VERSION);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(etag) = &cached_etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("GitHub API request failed: {e}"))?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let rate_limit = self.record_rate_limit(&headers);
+
+        if status == StatusCode::NOT_MODIFIED {
+            let cache = etag_cache().lock().map_err(|e| e.to_string())?;
+            return cache
+                .get(&cache_key)
+                .map(|cached| ApiResponse {
+                    body: cached.body.clone(),
+                    next: cached.next.clone(),
+                })
+                .ok_or_else(|| "GitHub API returned 304 for an uncached request.".to_string());
+        }
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| format!("GitHub API request failed: {e}"))?
+            .to_vec();
+        if !status.is_success() {
+            return Err(api_error(status, &body, rate_limit.as_ref()));
+        }
+
+        let next = headers
+            .get(LINK)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_next_link);
+        let etag = headers.get(ETAG).and_then(|value| value.to_str().ok());
+        if let (Some(etag), Ok(mut cache)) = (etag, etag_cache().lock()) {
+            if cache.len() >= MAX_CACHED_RESPONSES {
+                cache.clear();
+            }
+            cache.insert(
+                cache_key,
+                CachedResponse {
+                    etag: etag.to_string(),
+                    body: body.clone(),
+                    next: next.clone(),
+                },
+            );
+        }
+        Ok(ApiResponse { body, next })
+    }
+
+    async fn get_json(&self, path: &str) -> Result<Value, String> {
+        let response = self.get(path, JSON_ACCEPT).await?;
+        serde_json::from_slice(&response.body).map_err(|e| e.to_string())
+    }
+
+    async fn send_json(&self, method: Method, path: &str, body: &Value) -> Result<Value, String> {
+        let payload = serde_json::to_vec(body).map_err(|e| e.to_string())?;
+        let mut request = self
+            .http
+            .request(method, self.url_for(path))
+            .header(ACCEPT, JSON_ACCEPT)
+            .header(CONTENT_TYPE, "application/json")
+            .header("X-GitHub-Api-Version", API_VERSION)
+            .body(payload);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await.map_err(|e| {
+            if e.is_connect() || e.is_builder() {
+                format!("{UNREACHABLE_ERROR}: {e}")
+            } else {
+                format!("GitHub API request failed: {e}")
+            }
+        })?;
+        let status = response.status();
+        let rate_limit = self.record_rate_limit(response.headers());
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| format!("GitHub API request failed: {e}"))?;
+        if !status.is_success() {
+            return Err(api_error(status, &body, rate_limit.as_ref()));
+        }
+        if body.is_empty() {
+            return Ok(Value::Null);
+        }
+        serde_json::from_slice(&body).map_err(|e| e.to_string())
+    }
+
+    /// Follows `Link: rel="next"` until `limit` items pass `keep`.
+    async fn get_paginated(
+        &self,
+        path: &str,
+        limit: usize,
+        keep: impl Fn(&Value) -> bool,
+    ) -> Result<Vec<Value>, String> {
+        let mut items = Vec::new();
+        let mut next = Some(path.to_string());
+        while let Some(url) = next.take() {
+            let response = self.get(&url, JSON_ACCEPT).await?;
+            let page: Vec<Value> =
+                serde_json::from_slice(&response.body).map_err(|e| e.to_string())?;
+            if page.is_empty() {
+                break;
+            }
+            items.extend(page.into_iter().filter(|item| keep(item)));
+            if items.len() >= limit {
+                break;
+            }
+            next = response.next;
+        }
+        items.truncate(limit);
+        Ok(items)
+    }
+
+    async fn search_total(&self, query: &str) -> Option<usize> {
+        let value = self
+            .get_json(&format!(
+                "/search/issues?q={}&per_page=1",
+                query.replace(' ', "+")
+            ))
+            .await
+            .ok()?;
+        value
+            .get("total_count")?
+            .as_u64()
+            .map(|total| total as usize)
+    }
+
+    pub(crate) async fn list_issues(&self, repo: &str) -> Result<GitHubIssuesResponse, String> {
+        // The issues endpoint also returns pull requests; those carry a
+        // `pull_request` key.
+        let issues: Vec<GitHubIssue> = self
+            .get_paginated(
+                &format!("/repos/{repo}/issues?state=open&per_page={PAGE_SIZE}"),
+                LIST_LIMIT,
+                |item| item.get("pull_request").is_none(),
+            )
+            .await?
+            .iter()
+            .filter_map(issue_from_value)
+            .collect();
+        let total = self
+            .search_total(&format!("repo:{repo} is:issue is:open"))
+            .await
+            .unwrap_or(issues.len());
+        Ok(GitHubIssuesResponse {
+            total,
+            issues,
+            rate_limit: self.last_rate_limit(),
+        })
+    }
+
+    pub(crate) async fn issue(
+        &self,
+        repo: &str,
+        number: u64,
+    ) -> Result<GitHubIssueDetails, String> {
+        let value = self
+            .get_json(&format!("/repos/{repo}/issues/{number}"))
+            .await?;
+        let comments = self.pull_request_comments(repo, number).await?;
+        issue_details_from_value(&value, comments)
+            .ok_or_else(|| format!("Issue #{number} was not found."))
+    }
+
+    pub(crate) async fn list_pull_requests(
+        &self,
+        repo: &str,
+    ) -> Result<GitHubPullRequestsResponse, String> {
+        let pull_requests: Vec<GitHubPullRequest> = self
+            .get_paginated(
+                &format!("/repos/{repo}/pulls?state=open&per_page={LIST_LIMIT}"),
+                LIST_LIMIT,
+                |_| true,
+            )
+            .await?
+            .iter()
+            .filter_map(pull_request_from_value)
+            .collect();
+        let total = self
+            .search_total(&format!("repo:{repo} is:pr is:open"))
+            .await
+            .unwrap_or(pull_requests.len());
+        Ok(GitHubPullRequestsResponse {
+            total,
+            pull_requests,
+            rate_limit: self.last_rate_limit(),
+        })
+    }
+
+    pub(crate) async fn pull_request_diff(
+        &self,
+        repo: &str,
+        number: u64,
+    ) -> Result<String, String> {
+        let response = self
+            .get(&format!("/repos/{repo}/pulls/{number}"), DIFF_ACCEPT)
+            .await?;
+        Ok(String::from_utf8_lossy(&response.body).to_string())
+    }
+
+    pub(crate) async fn pull_request_comments(
+        &self,
+        repo: &str,
+        number: u64,
+    ) -> Result<Vec<GitHubPullRequestComment>, String> {
+        Ok(self
+            .get_paginated(
+                &format!("/repos/{repo}/issues/{number}/comments?per_page={PAGE_SIZE}"),
+                COMMENT_LIMIT,
+                |_| true,
+            )
+            .await?
+            .iter()
+            .filter_map(comment_from_value)
+            .collect())
+    }
+
+    pub(crate) async fn pull_request_review_comments(
+        &self,
+        repo: &str,
+        number: u64,
+    ) -> Result<Vec<GitHubPullRequestComment>, String> {
+        Ok(self
+            .get_paginated(
+                &format!("/repos/{repo}/pulls/{number}/comments?per_page={PAGE_SIZE}"),
+                COMMENT_LIMIT,
+                |_| true,
+            )
+            .await?
+            .iter()
+            .filter_map(comment_from_value)
+            .collect())
+    }
+
+    pub(crate) async fn create_issue_comment(
+        &self,
+        repo: &str,
+        number: u64,
+        body: &str,
+    ) -> Result<GitHubPullRequestComment, String> {
+        let value = self
+            .send_json(
+                Method::POST,
+                &format!("/repos/{repo}/issues/{number}/comments"),
+                &json!({ "body": body }),
+            )
+            .await?;
+        comment_from_value(&value).ok_or_else(unexpected_comment)
+    }
+
+    pub(crate) async fn pull_request_head_sha(
+        &self,
+        repo: &str,
+        number: u64,
+    ) -> Result<String, String> {
+        let value = self
+            .get_json(&format!("/repos/{repo}/pulls/{number}"))
+            .await?;
+        value
+            .get("head")
+            .and_then(|head| head.get("sha"))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| "GitHub returned an unexpected pull request response.".to_string())
+    }
+
+    /// Inline comments must name a commit; they are anchored to the current
+    /// head of the pull request.
+    pub(crate) async fn create_review_comment(
+        &self,
+        repo: &str,
+        number: u64,
+        draft: &GitHubReviewCommentDraft,
+    ) -> Result<GitHubPullRequestComment, String> {
+        let mut payload = review_comment_payload(draft);
+        payload["commit_id"] = json!(self.pull_request_head_sha(repo, number).await?);
+        let value = self
+            .send_json(
+                Method::POST,
+                &format!("/repos/{repo}/pulls/{number}/comments"),
+                &payload,
+            )
+            .await?;
+        comment_from_value(&value).ok_or_else(unexpected_comment)
+    }
+
+    pub(crate) async fn reply_to_review_comment(
+        &self,
+        repo: &str,
+        number: u64,
+        comment_id: u64,
+        body: &str,
+    ) -> Result<GitHubPullRequestComment, String> {
+        let value = self
+            .send_json(
+                Method::POST,
+                &format!("/repos/{repo}/pulls/{number}/comments/{comment_id}/replies"),
+                &json!({ "body": body }),
+            )
+            .await?;
+        comment_from_value(&value).ok_or_else(unexpected_comment)
+    }
+
+    pub(crate) async fn submit_review(
+        &self,
+        repo: &str,
+        number: u64,
+        event: &str,
+        body: &str,
+        comments: &[GitHubReviewCommentDraft],
+    ) -> Result<GitHubPullRequestReview, String> {
+        let value = self
+            .send_json(
+                Method::POST,
+                &format!("/repos/{repo}/pulls/{number}/reviews"),
+                &review_payload(event, body, comments),
+            )
+            .await?;
+        review_from_value(&value, comments.len())
+            .ok_or_else(|| "GitHub returned an unexpected review response.".to_string())
+    }
+
+    pub(crate) async fn default_branch(&self, repo: &str) -> Result<String, String> {
+        let value = self.get_json(&format!("/repos/{repo}")).await?;
+        value
+            .get("default_branch")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| format!("GitHub did not report a default branch for {repo}."))
+    }
+
+    pub(crate) async fn pull_request(
+        &self,
+        repo: &str,
+        number: u64,
+    ) -> Result<GitHubPullRequest, String> {
+        let value = self
+            .get_json(&format!("/repos/{repo}/pulls/{number}"))
+            .await?;
+        pull_request_from_value(&value)
+            .ok_or_else(|| "GitHub returned an unexpected pull request response.".to_string())
+    }
+
+    pub(crate) async fn pull_request_refs(
+        &self,
+        repo: &str,
+        number: u64,
+    ) -> Result<PullRequestRefs, String> {
+        let value = self
+            .get_json(&format!("/repos/{repo}/pulls/{number}"))
+            .await?;
+        let head = value.get("head");
+        let repo_name = |side: Option<&Value>| {
+            side.and_then(|side| side.get("repo"))
+                .and_then(|repo| repo.get("full_name"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        };
+        Ok(PullRequestRefs {
+            head_ref: head
+                .map(|head| string_field(head, "ref"))
+                .ok_or("GitHub returned an unexpected pull request response.")?,
+            base_ref: value
+                .get("base")
+                .map(|base| string_field(base, "ref"))
+                .ok_or("GitHub returned an unexpected pull request response.")?,
+            // A deleted fork reports no head repo; treat it as cross-repository.
+            cross_repository: repo_name(head) != repo_name(value.get("base")),
+            title: string_field(&value, "title"),
+        })
+    }
+
+    pub(crate) async fn create_pull_request(
+        &self,
+        repo: &str,
+        draft: &PullRequestDraft,
+    ) -> Result<GitHubPullRequest, String> {
+        let value = self
+            .send_json(
+                Method::POST,
+                &format!("/repos/{repo}/pulls"),
+                &json!({
+                    "title": draft.title,
+                    "body": draft.body,
+                    "head": draft.head,
+                    "base": draft.base,
+                    "draft": draft.draft,
+                }),
+            )
+            .await?;
+        pull_request_from_value(&value)
+            .ok_or_else(|| "GitHub returned an unexpected pull request response.".to_string())
+    }
+
+    /// Edits only the fields that are provided.
+    pub(crate) async fn update_pull_request(
+        &self,
+        repo: &str,
+        number: u64,
+        update: &PullRequestUpdate,
+    ) -> Result<GitHubPullRequest, String> {
+        let mut fields = serde_json::Map::new();
+        if let Some(title) = &update.title {
+            fields.insert("title".to_string(), json!(title));
+        }
+        if let Some(body) = &update.body {
+            fields.insert("body".to_string(), json!(body));
+        }
+        if let Some(base) = &update.base {
+            fields.insert("base".to_string(), json!(base));
+        }
+        if fields.is_empty() {
+            return self.pull_request(repo, number).await;
+        }
+        let value = self
+            .send_json(
+                Method::PATCH,
+                &format!("/repos/{repo}/pulls/{number}"),
+                &Value::Object(fields),
+            )
+            .await?;
+        pull_request_from_value(&value)
+            .ok_or_else(|| "GitHub returned an unexpected pull request response.".to_string())
+    }
+
+    /// REST cannot leave draft state, so this goes through GraphQL.
+    pub(crate) async fn mark_ready_for_review(
+        &self,
+        repo: &str,
+        number: u64,
+    ) -> Result<GitHubPullRequest, String> {
+        let value = self
+            .get_json(&format!("/repos/{repo}/pulls/{number}"))
+            .await?;
+        if !value.get("draft").and_then(Value::as_bool).unwrap_or(false) {
+            return pull_request_from_value(&value)
+                .ok_or_else(|| "GitHub returned an unexpected pull request response.".to_string());
+        }
+        let node_id = value
+            .get("node_id")
+            .and_then(Value::as_str)
+            .ok_or("GitHub did not return the pull request node id.")?;
+        let response = self
+            .send_json(
+                Method::POST,
+                &self.graphql_url(),
+                &json!({
+                    "query": "mutation($id: ID!) { markPullRequestReadyForReview(input: {pullRequestId: $id}) { pullRequest { isDraft } } }",
+                    "variables": { "id": node_id },
+                }),
+            )
+            .await?;
+        if let Some(message) = graphql_error(&response) {
+            return Err(format!("GitHub API: {message}"));
+        }
+        self.pull_request(repo, number).await
+    }
+
+    /// `org/team` entries are requested as teams, everything else as users.
+    pub(crate) async fn request_reviewers(
+        &self,
+        repo: &str,
+        number: u64,
+        reviewers: &[String],
+    ) -> Result<GitHubPullRequest, String> {
+        let (teams, users): (Vec<&str>, Vec<&str>) = reviewers
+            .iter()
+            .map(|reviewer| reviewer.trim().trim_start_matches('@'))
+            .filter(|reviewer| !reviewer.is_empty())
+            .partition(|reviewer| reviewer.contains('/'));
+        let team_slugs: Vec<&str> = teams
+            .iter()
+            .filter_map(|team| team.split_once('/').map(|(_, slug)| slug))
+            .collect();
+        if users.is_empty() && team_slugs.is_empty() {
+            return Err("No reviewers given.".to_string());
+        }
+        let value = self
+            .send_json(
+                Method::POST,
+                &format!("/repos/{repo}/pulls/{number}/requested_reviewers"),
+                &json!({ "reviewers": users, "team_reviewers": team_slugs }),
+            )
+            .await?;
+        pull_request_from_value(&value)
+            .ok_or_else(|| "GitHub returned an unexpected pull request response.".to_string())
+    }
+
+    /// Resolves a branch, tag or sha as the remote sees it.
+    pub(crate) async fn commit_sha(&self, repo: &str, git_ref: &str) -> Result<String, String> {
+        let response = self
+            .get(&format!("/repos/{repo}/commits/{git_ref}"), SHA_ACCEPT)
+            .await?;
+        Ok(String::from_utf8_lossy(&response.body).trim().to_string())
+    }
+
+    /// Check runs and commit statuses; polling is cheap because unchanged
+    /// results come back as 304s.
+    pub(crate) async fn checks(&self, repo: &str, sha: &str) -> Result<Vec<GitHubCheck>, String> {
+        let runs = self
+            .get_json(&format!(
+                "/repos/{repo}/commits/{sha}/check-runs?per_page={PAGE_SIZE}"
+            ))
+            .await?;
+        let statuses = self
+            .get_json(&format!(
+                "/repos/{repo}/commits/{sha}/status?per_page={PAGE_SIZE}"
+            ))
+            .await?;
+        Ok(checks_from_values(&runs, &statuses))
+    }
+
+    /// Log excerpts for the failing check runs on `sha`, or for `check_id`
+    /// alone when given.
+    pub(crate) async fn check_logs(
+        &self,
+        repo: &str,
+        sha: &str,
+        check_id: Option<u64>,
+    ) -> Result<Vec<GitHubCheckLog>, String> {
+        let runs = self
+            .get_json(&format!(
+                "/repos/{repo}/commits/{sha}/check-runs?per_page={PAGE_SIZE}"
+            ))
+            .await?;
+        let mut logs = Vec::new();
+        for run in selected_runs(&runs, check_id) {
+            let Some(id) = run.get("id").and_then(Value::as_u64) else {
+                continue;
+            };
+            let log = self.job_log(repo, id).await.ok();
+            logs.extend(check_log(run, log.as_deref()));
+        }
+        Ok(logs)
+    }
+
+    /// Not cached: the endpoint redirects to a short-lived download URL, and
+    /// reqwest drops the token when following it to another host.
+    async fn job_log(&self, repo: &str, job_id: u64) -> Result<String, String> {
+        let mut request = self
+            .http
+            .get(self.url_for(&format!("/repos/{repo}/actions/jobs/{job_id}/logs")))
+            .header("X-GitHub-Api-Version", API_VERSION);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("GitHub API request failed: {e}"))?;
+        let status = response.status();
+        let rate_limit = self.record_rate_limit(response.headers());
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| format!("GitHub API request failed: {e}"))?;
+        if !status.is_success() {
+            return Err(api_error(status, &body, rate_limit.as_ref()));
+        }
+        Ok(String::from_utf8_lossy(&body).to_string())
+    }
+
+    /// Queries `/rate_limit`, which does not count against the limit.
+    pub(crate) async fn rate_limit(&self) -> Result<GitHubRateLimit, String> {
+        let value = self.get_json("/rate_limit").await?;
+        let core = value
+            .get("resources")
+            .and_then(|resources| resources.get("core"))
+            .ok_or("GitHub API returned an unexpected rate limit response.")?;
+        Ok(GitHubRateLimit {
+            limit: core.get("limit").and_then(Value::as_u64).unwrap_or(0),
+            remaining: core.get("remaining").and_then(Value::as_u64).unwrap_or(0),
+            reset_at: core.get("reset").and_then(Value::as_i64).unwrap_or(0),
+            resource: Some("core".to_string()),
+        })
+    }
+}
+
+fn graphql_error(response: &Value) -> Option<String> {
+    let errors = response.get("errors")?.as_array()?;
+    let first = errors.first()?;
+    Some(
+        first
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("GraphQL request failed")
+            .to_string(),
+    )
+}
+
+fn humanize_branch(branch: &str) -> String {
+    let name = branch.rsplit('/').next().unwrap_or(branch);
+    let words = name.replace(['-', '_'], " ");
+    let mut chars = words.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => branch.to_string(),
+    }
+}
+
+/// Mirrors `gh pr create --fill`: a single commit supplies the title and
+/// body, several commits become a list under the humanized branch name.
+pub(crate) fn pull_request_fill(
+    repo: &Repository,
+    base: Option<Oid>,
+    branch: &str,
+) -> Result<(String, String), String> {
+    let head = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .map_err(|e| e.to_string())?;
+    let mut messages = Vec::new();
+    match base {
+        Some(base) => {
+            let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+            revwalk.push(head.id()).map_err(|e| e.to_string())?;
+            revwalk.hide(base).map_err(|e| e.to_string())?;
+            revwalk
+                .set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)
+                .map_err(|e| e.to_string())?;
+            for oid in revwalk {
+                let commit = repo
+                    .find_commit(oid.map_err(|e| e.to_string())?)
+                    .map_err(|e| e.to_string())?;
+                if commit.parent_count() > 1 {
+                    continue;
+                }
+                messages.push(String::from_utf8_lossy(commit.message_bytes()).to_string());
+            }
+        }
+        // Without a known base there is no range; the tip describes the change.
+        None => messages.push(String::from_utf8_lossy(head.message_bytes()).to_string()),
+    }
+    match messages.as_slice() {
+        [single] => {
+            let (title, body) = single.split_once('\n').unwrap_or((single.as_str(), ""));
+            Ok((title.trim().to_string(), body.trim().to_string()))
+        }
+        _ => {
+            let body = messages
+                .iter()
+                .filter_map(|message| message.lines().next())
+                .map(|summary| format!("- {}", summary.trim()))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Ok((humanize_branch(branch), body))
+        }
+    }
+}
+
+pub(crate) fn string_field(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn author_from_value(value: &Value) -> Option<GitHubPullRequestAuthor> {
+    let login = value.get("user")?.get("login")?.as_str()?;
+    Some(GitHubPullRequestAuthor {
+        login: login.to_string(),
+    })
+}
+
+pub(crate) fn issue_from_value(value: &Value) -> Option<GitHubIssue> {
+    Some(GitHubIssue {
+        number: value.get("number")?.as_u64()?,
+        title: string_field(value, "title"),
+        url: string_field(value, "html_url"),
+        updated_at: string_field(value, "updated_at"),
+    })
+}
+
+/// GitHub and Gitea both return labels as objects with a `name`.
+pub(crate) fn issue_details_from_value(
+    value: &Value,
+    comments: Vec<GitHubPullRequestComment>,
+) -> Option<GitHubIssueDetails> {
+    let labels = value
+        .get("labels")
+        .and_then(Value::as_array)
+        .map(|labels| {
+            labels
+                .iter()
+                .filter_map(|label| label.get("name")?.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(GitHubIssueDetails {
+        issue: issue_from_value(value)?,
+        body: string_field(value, "body"),
+        state: string_field(value, "state"),
+        labels,
+        author: author_from_value(value),
+        comments,
+    })
+}
+
+pub(crate) fn pull_request_from_value(value: &Value) -> Option<GitHubPullRequest> {
+    let branch = |side: &str| {
+        value
+            .get(side)
+            .map(|branch| string_field(branch, "ref"))
+            .unwrap_or_default()
+    };
+    Some(GitHubPullRequest {
+        number: value.get("number")?.as_u64()?,
+        title: string_field(value, "title"),
+        url: string_field(value, "html_url"),
+        updated_at: string_field(value, "updated_at"),
+        created_at: string_field(value, "created_at"),
+        body: string_field(value, "body"),
+        head_ref_name: branch("head"),
+        base_ref_name: branch("base"),
+        is_draft: value.get("draft").and_then(Value::as_bool).unwrap_or(false),
+        author: author_from_value(value),
+    })
+}
+
+pub(crate) fn comment_from_value(value: &Value) -> Option<GitHubPullRequestComment> {
+    let text = |key: &str| value.get(key).and_then(Value::as_str).map(str::to_string);
+    let line = |key: &str| {
+        value
+            .get(key)
+            .and_then(Value::as_u64)
+            .map(|line| line as u32)
+    };
+    Some(GitHubPullRequestComment {
+        id: value.get("id")?.as_u64()?,
+        body: string_field(value, "body"),
+        created_at: string_field(value, "created_at"),
+        url: string_field(value, "html_url"),
+        author: author_from_value(value),
+        path: text("path"),
+        line: line("line"),
+        start_line: line("start_line"),
+        original_line: line("original_line"),
+        side: text("side"),
+        diff_hunk: text("diff_hunk"),
+        in_reply_to_id: value.get("in_reply_to_id").and_then(Value::as_u64),
+    })
+}
+
+pub(crate) fn optional_string(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+fn duration_secs(started_at: Option<&str>, completed_at: Option<&str>) -> Option<u64> {
+    let started_at = chrono::DateTime::parse_from_rfc3339(started_at?).ok()?;
+    let completed_at = chrono::DateTime::parse_from_rfc3339(completed_at?).ok()?;
+    u64::try_from((completed_at - started_at).num_seconds()).ok()
+}
+
+fn check_from_run(value: &Value) -> Option<GitHubCheck> {
+    let started_at = optional_string(value, "started_at");
+    let completed_at = optional_string(value, "completed_at");
+    Some(GitHubCheck {
+        id: Some(value.get("id")?.as_u64()?),
+        name: string_field(value, "name"),
+        kind: "checkRun".to_string(),
+        status: string_field(value, "status"),
+        conclusion: optional_string(value, "conclusion"),
+        url: string_field(value, "html_url"),
+        duration_secs: duration_secs(started_at.as_deref(), completed_at.as_deref()),
+        started_at,
+        completed_at,
+    })
+}
+
+/// Commit statuses only have a state, so pending maps to in progress and
+/// anything else to a completed check with that state as its conclusion.
+fn check_from_status(value: &Value) -> Option<GitHubCheck> {
+    let state = value.get("state")?.as_str()?;
+    let completed = state != "pending";
+    let started_at = optional_string(value, "created_at");
+    let completed_at = optional_string(value, "updated_at").filter(|_| completed);
+    Some(GitHubCheck {
+        id: None,
+        name: string_field(value, "context"),
+        kind: "status".to_string(),
+        status: if completed {
+            "completed"
+        } else {
+            "in_progress"
+        }
+        .to_string(),
+        conclusion: completed.then(|| state.to_string()),
+        url: string_field(value, "target_url"),
+        duration_secs: duration_secs(started_at.as_deref(), completed_at.as_deref()),
+        started_at,
+        completed_at,
+    })
+}
+
+fn checks_from_values(runs: &Value, statuses: &Value) -> Vec<GitHubCheck> {
+    let items = |value: &Value, key: &str| {
+        value
+            .get(key)
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default()
+    };
+    let mut checks: Vec<GitHubCheck> = items(runs, "check_runs")
+        .iter()
+        .filter_map(check_from_run)
+        .collect();
+    checks.extend(
+        items(statuses, "statuses")
+            .iter()
+            .filter_map(check_from_status),
+    );
+    checks.sort_by(|a, b| a.name.cmp(&b.name));
+    checks
+}
+
+pub(crate) fn is_failing_conclusion(conclusion: Option<&str>) -> bool {
+    conclusion.is_some_and(|conclusion| FAILING_CONCLUSIONS.contains(&conclusion))
+}
+
+pub(crate) fn checks_state(checks: &[GitHubCheck]) -> &'static str {
+    if checks.is_empty() {
+        "none"
+    } else if checks.iter().any(|check| check.status != "completed") {
+        "pending"
+    } else if checks
+        .iter()
+        .any(|check| is_failing_conclusion(check.conclusion.as_deref()))
+    {
+        "failure"
+    } else {
+        "success"
+    }
+}
+
+fn selected_runs(runs: &Value, check_id: Option<u64>) -> Vec<&Value> {
+    runs.get("check_runs")
+        .and_then(Value::as_array)
+        .map(|runs| {
+            runs.iter()
+                .filter(|run| match check_id {
+                    Some(check_id) => run.get("id").and_then(Value::as_u64) == Some(check_id),
+                    None => is_failing_conclusion(run.get("conclusion").and_then(Value::as_str)),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Checks from apps other than Actions have no job log; their output
+/// summary is the closest thing.
+fn check_log(run: &Value, log: Option<&str>) -> Option<GitHubCheckLog> {
+    let (excerpt, truncated) = match log {
+        Some(log) => log_excerpt(log),
+        None => {
+            let output = run.get("output");
+            let parts: Vec<String> = ["title", "summary", "text"]
+                .iter()
+                .filter_map(|key| output.and_then(|output| optional_string(output, key)))
+                .filter(|part| !part.trim().is_empty())
+                .collect();
+            (parts.join("\n\n"), false)
+        }
+    };
+    Some(GitHubCheckLog {
+        id: run.get("id")?.as_u64()?,
+        name: string_field(run, "name"),
+        conclusion: optional_string(run, "conclusion"),
+        url: string_field(run, "html_url"),
+        excerpt,
+        truncated,
+    })
+}
+
+/// Actions prefixes every log line with an RFC 3339 timestamp.
+fn strip_log_timestamp(line: &str) -> &str {
+    let line = line.trim_start_matches('\u{feff}');
+    match line.split_once(' ') {
+        Some((stamp, rest))
+            if stamp.len() >= 20 && stamp.ends_with('Z') && stamp.as_bytes()[10] == b'T' =>
+        {
+            rest
+        }
+        _ => line,
+    }
+}
+
+/// Keeps the lines around `##[error]` markers, or the tail of the log when
+/// there are none, capped at `MAX_LOG_LINES`.
+pub(crate) fn log_excerpt(log: &str) -> (String, bool) {
+    let lines: Vec<&str> = log.lines().map(strip_log_timestamp).collect();
+    let errors: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.contains("##[error]"))
+        .map(|(index, _)| index)
+        .collect();
+    let (start, end) = match (errors.first(), errors.last()) {
+        (Some(first), Some(last)) => (
+            first.saturating_sub(LOG_CONTEXT_LINES),
+            (last + 6).min(lines.len()),
+        ),
+        _ => (0, lines.len()),
+    };
+    // The last errors are usually the fatal ones, so trim from the front.
+    let start = start.max(end.saturating_sub(MAX_LOG_LINES));
+    (lines[start..end].join("\n"), start > 0 || end < lines.len())
+}
+
+fn unexpected_comment() -> String {
+    "GitHub returned an unexpected comment response.".to_string()
+}
+
+fn review_from_value(value: &Value, comment_count: usize) -> Option<GitHubPullRequestReview> {
+    Some(GitHubPullRequestReview {
+        id: value.get("id")?.as_u64()?,
+        state: string_field(value, "state"),
+        body: string_field(value, "body"),
+        url: string_field(value, "html_url"),
+        submitted_at: value
+            .get("submitted_at")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        author: author_from_value(value),
+        comment_count,
+    })
+}
+
+fn review_comment_payload(draft: &GitHubReviewCommentDraft) -> Value {
+    let side = draft
+        .side
+        .as_deref()
+        .unwrap_or("RIGHT")
+        .to_ascii_uppercase();
+    let mut payload = json!({
+        "path": draft.path,
+        "line": draft.line,
+        "side": side,
+        "body": draft.body,
+    });
+    if let Some(start_line) = draft.start_line.filter(|start| *start < draft.line) {
+        payload["start_line"] = json!(start_line);
+        payload["start_side"] = json!(side);
+    }
+    payload
+}
+
+fn review_payload(event: &str, body: &str, comments: &[GitHubReviewCommentDraft]) -> Value {
+    let mut payload = json!({
+        "event": event,
+        "comments": comments.iter().map(review_comment_payload).collect::<Vec<_>>(),
+    });
+    if !body.trim().is_empty() {
+        payload["body"] = json!(body);
+    }
+    payload
+}
+
+/// Normalizes `approve`, `request changes` and friends to the REST event name.
+pub(crate) fn review_event(event: &str) -> Result<&'static str, String> {
+    match event
+        .trim()
+        .to_ascii_lowercase()
+        .replace(['-', ' '], "_")
+        .as_str()
+    {
+        "approve" => Ok("APPROVE"),
+        "request_changes" => Ok("REQUEST_CHANGES"),
+        "comment" => Ok("COMMENT"),
+        _ => Err(format!(
+            "Unknown review event {event}; use approve, request_changes or comment."
+        )),
+    }
+}
+
+/// Turns the findings of a Codex review summary into inline comments. Each
+/// finding reads `- <title> — <path>:<start>-<end>` followed by an indented
+/// body; findings outside `repo_root` are skipped.
+pub(crate) fn review_findings_to_comments(
+    text: &str,
+    repo_root: &Path,
+) -> Vec<GitHubReviewCommentDraft> {
+    let mut comments = Vec::new();
+    let mut current: Option<(GitHubReviewCommentDraft, Vec<String>)> = None;
+    let mut finish = |current: Option<(GitHubReviewCommentDraft, Vec<String>)>| {
+        if let Some((mut draft, body)) = current {
+            let body = body.join("\n").trim().to_string();
+            if !body.is_empty() {
+                draft.body = format!("{}\n\n{body}", draft.body);
+            }
+            comments.push(draft);
+        }
+    };
+    for line in text.lines() {
+        if let Some(finding) = line.strip_prefix("- ") {
+            finish(current.take());
+            current = parse_finding(finding, repo_root).map(|draft| (draft, Vec::new()));
+        } else if let Some((_, body)) = current.as_mut() {
+            if line.starts_with(' ') || line.trim().is_empty() {
+                body.push(line.strip_prefix("  ").unwrap_or(line).to_string());
+            } else {
+                finish(current.take());
+            }
+        }
+    }
+    finish(current);
+    comments
+}
+
+fn parse_finding(line: &str, repo_root: &Path) -> Option<GitHubReviewCommentDraft> {
+    let (title, location) = line.rsplit_once(" — ")?;
+    let (path, range) = location.trim().rsplit_once(':')?;
+    let (start, end) = match range.split_once('-') {
+        Some((start, end)) => (start.parse::<u32>().ok()?, end.parse::<u32>().ok()?),
+        None => {
+            let line = range.parse::<u32>().ok()?;
+            (line, line)
+        }
+    };
+    let path = Path::new(path);
+    let relative = if path.is_absolute() {
+        path.strip_prefix(repo_root).ok()?
+    } else {
+        path
+    };
+    Some(GitHubReviewCommentDraft {
+        path: relative.to_string_lossy().replace('\\', "/"),
+        line: end.max(start),
+        start_line: (start < end).then_some(start),
+        side: None,
+        body: format!("**{}**", title.trim()),
+    })
+}
+
+pub(crate) fn parse_next_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|param| param.trim() == "rel=\"next\"")
+            .then(|| {
+                url.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+    })
+}
+
+fn parse_rate_limit(headers: &HeaderMap) -> Option<GitHubRateLimit> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    Some(GitHubRateLimit {
+        limit: header("x-ratelimit-limit")?.parse().ok()?,
+        remaining: header("x-ratelimit-remaining")?.parse().ok()?,
+        reset_at: header("x-ratelimit-reset")?.parse().ok()?,
+        resource: header("x-ratelimit-resource").map(str::to_string),
+    })
+}
+
+fn api_error(status: StatusCode, body: &[u8], rate_limit: Option<&GitHubRateLimit>) -> String {
+    let exhausted = rate_limit.filter(|limit| limit.remaining == 0);
+    if let (StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS, Some(limit)) =
+        (status, exhausted)
+    {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs() as i64)
+            .unwrap_or(0);
+        let minutes = (limit.reset_at - now).max(0) / 60 + 1;
+        return format!("GitHub API rate limit exceeded; it resets in about {minutes} min.");
+    }
+    let message = serde_json::from_slice::<Value>(body)
+        .ok()
+        .and_then(|value| value.get("message")?.as_str().map(str::to_string))
+        .unwrap_or_else(|| {
+            status
+                .canonical_reason()
+                .unwrap_or("Request failed")
+                .to_string()
+        });
+    match status {
+        StatusCode::UNAUTHORIZED => {
+            format!("GitHub rejected the token ({message}). Check the GitHub token in settings.")
+        }
+        StatusCode::NOT_FOUND => {
+            format!("GitHub API: {message}. Private repositories need a GitHub token in settings.")
+        }
+        _ => format!("GitHub API error ({}): {message}", status.as_u16()),
+    }
+}
+
+/// Runs `gh` and returns stdout, turning failures into its stderr message.
+async fn run_gh(repo_root: &Path, args: &[&str]) -> Result<Vec<u8>, String> {
+    run_gh_with_input(repo_root, args, None).await
+}
+
+async fn run_gh_with_input(
+    repo_root: &Path,
+    args: &[&str],
+    input: Option<&[u8]>,
+) -> Result<Vec<u8>, String> {
+    let mut child = tokio_command("gh")
+        .args(args)
+        .current_dir(repo_root)
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        stdin
+            .write_all(input)
+            .await
+            .map_err(|e| format!("Failed to run gh: {e}"))?;
+    }
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("Failed to run gh: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let detail = if stderr.trim().is_empty() {
+            stdout.trim()
+        } else {
+            stderr.trim()
+        };
+        if detail.is_empty() {
+            return Err("GitHub CLI command failed.".to_string());
+        }
+        return Err(detail.to_string());
+    }
+    Ok(output.stdout)
+}
+
+async fn gh_search_total(repo_root: &Path, query: &str) -> Option<usize> {
+    let endpoint = format!("/search/issues?q={}", query.replace(' ', "+"));
+    let output = run_gh(repo_root, &["api", &endpoint, "--jq", ".total_count"])
+        .await
+        .ok()?;
+    String::from_utf8_lossy(&output)
+        .trim()
+        .parse::<usize>()
+        .ok()
+}
+
+pub(crate) async fn gh_list_issues(
+    repo_root: &Path,
+    repo_name: &str,
+) -> Result<GitHubIssuesResponse, String> {
+    let output = run_gh(
+        repo_root,
+        &[
+            "issue",
+            "list",
+            "--repo",
+            repo_name,
+            "--limit",
+            "50",
+            "--json",
+            "number,title,url,updatedAt",
+        ],
+    )
+    .await?;
+    let issues: Vec<GitHubIssue> = serde_json::from_slice(&output).map_err(|e| e.to_string())?;
+    let total = gh_search_total(repo_root, &format!("repo:{repo_name} is:issue is:open"))
+        .await
+        .unwrap_or(issues.len());
+    Ok(GitHubIssuesResponse {
+        total,
+        issues,
+        rate_limit: None,
+    })
+}
+
+pub(crate) async fn gh_list_pull_requests(
+    repo_root: &Path,
+    repo_name: &str,
+) -> Result<GitHubPullRequestsResponse, String> {
+    let output = run_gh(
+        repo_root,
+        &[
+            "pr",
+            "list",
+            "--repo",
+            repo_name,
+            "--state",
+            "open",
+            "--limit",
+            "50",
+            "--json",
+            GH_PULL_REQUEST_FIELDS,
+        ],
+    )
+    .await?;
+    let pull_requests: Vec<GitHubPullRequest> =
+        serde_json::from_slice(&output).map_err(|e| e.to_string())?;
+    let total = gh_search_total(repo_root, &format!("repo:{repo_name} is:pr is:open"))
+        .await
+        .unwrap_or(pull_requests.len());
+    Ok(GitHubPullRequestsResponse {
+        total,
+        pull_requests,
+        rate_limit: None,
+    })
+}
+
+pub(crate) async fn gh_pull_request_diff(
+    repo_root: &Path,
+    repo_name: &str,
+    pr_number: u64,
+) -> Result<String, String> {
+    let output = run_gh(
+        repo_root,
+        &[
+            "pr",
+            "diff",
+            &pr_number.to_string(),
+            "--repo",
+            repo_name,
+            "--color",
+            "never",
+        ],
+    )
+    .await?;
+    Ok(String::from_utf8_lossy(&output).to_string())
+}
+
+pub(crate) async fn gh_pull_request_comments(
+    repo_root: &Path,
+    repo_name: &str,
+    pr_number: u64,
+) -> Result<Vec<GitHubPullRequestComment>, String> {
+    let comments_endpoint = format!("/repos/{repo_name}/issues/{pr_number}/comments?per_page=30");
+    let jq_filter = r#"[.[] | {id, body, createdAt: .created_at, url: .html_url, author: (if .user then {login: .user.login} else null end)}]"#;
+    let output = run_gh(repo_root, &["api", &comments_endpoint, "--jq", jq_filter]).await?;
+    serde_json::from_slice(&output).map_err(|e| e.to_string())
+}
+
+/// Sends a JSON body through `gh api`, for writes the REST client could not
+/// make (usually because no token is configured).
+async fn gh_send_json(
+    repo_root: &Path,
+    method: &str,
+    endpoint: &str,
+    body: &Value,
+) -> Result<Value, String> {
+    let payload = serde_json::to_vec(body).map_err(|e| e.to_string())?;
+    let output = run_gh_with_input(
+        repo_root,
+        &["api", "--method", method, endpoint, "--input", "-"],
+        Some(&payload),
+    )
+    .await?;
+    serde_json::from_slice(&output).map_err(|e| e.to_string())
+}
+
+pub(crate) async fn gh_pull_request_review_comments(
+    repo_root: &Path,
+    repo_name: &str,
+    pr_number: u64,
+) -> Result<Vec<GitHubPullRequestComment>, String> {
+    let endpoint = format!("/repos/{repo_name}/pulls/{pr_number}/comments?per_page={PAGE_SIZE}");
+    let output = run_gh(repo_root, &["api", &endpoint]).await?;
+    let comments: Vec<Value> = serde_json::from_slice(&output).map_err(|e| e.to_string())?;
+    Ok(comments.iter().filter_map(comment_from_value).collect())
+}
+
+pub(crate) async fn gh_create_issue_comment(
+    repo_root: &Path,
+    repo_name: &str,
+    pr_number: u64,
+    body: &str,
+) -> Result<GitHubPullRequestComment, String> {
+    let value = gh_send_json(
+        repo_root,
+        "POST",
+        &format!("/repos/{repo_name}/issues/{pr_number}/comments"),
+        &json!({ "body": body }),
+    )
+    .await?;
+    comment_from_value(&value).ok_or_else(unexpected_comment)
+}
+
+pub(crate) async fn gh_issue(
+    repo_root: &Path,
+    repo_name: &str,
+    number: u64,
+) -> Result<GitHubIssueDetails, String> {
+    let value = gh_get_json(repo_root, &format!("/repos/{repo_name}/issues/{number}")).await?;
+    let comments = gh_pull_request_comments(repo_root, repo_name, number).await?;
+    issue_details_from_value(&value, comments)
+        .ok_or_else(|| format!("Issue #{number} was not found."))
+}
+
+async fn gh_get_json(repo_root: &Path, endpoint: &str) -> Result<Value, String> {
+    let output = run_gh(repo_root, &["api", endpoint]).await?;
+    serde_json::from_slice(&output).map_err(|e| e.to_string())
+}
+
+async fn gh_jq(repo_root: &Path, endpoint: &str, filter: &str) -> Result<String, String> {
+    let output = run_gh(repo_root, &["api", endpoint, "--jq", filter]).await?;
+    Ok(String::from_utf8_lossy(&output).trim().to_string())
+}
+
+pub(crate) async fn gh_pull_request_head_sha(
+    repo_root: &Path,
+    repo_name: &str,
+    pr_number: u64,
+) -> Result<String, String> {
+    gh_jq(
+        repo_root,
+        &format!("/repos/{repo_name}/pulls/{pr_number}"),
+        ".head.sha",
+    )
+    .await
+}
+
+pub(crate) async fn gh_commit_sha(
+    repo_root: &Path,
+    repo_name: &str,
+    git_ref: &str,
+) -> Result<String, String> {
+    gh_jq(
+        repo_root,
+        &format!("/repos/{repo_name}/commits/{git_ref}"),
+        ".sha",
+    )
+    .await
+}
+
+pub(crate) async fn gh_checks(
+    repo_root: &Path,
+    repo_name: &str,
+    sha: &str,
+) -> Result<Vec<GitHubCheck>, String> {
+    let runs = gh_get_json(
+        repo_root,
+        &format!("/repos/{repo_name}/commits/{sha}/check-runs?per_page={PAGE_SIZE}"),
+    )
+    .await?;
+    let statuses = gh_get_json(
+        repo_root,
+        &format!("/repos/{repo_name}/commits/{sha}/status?per_page={PAGE_SIZE}"),
+    )
+    .await?;
+    Ok(checks_from_values(&runs, &statuses))
+}
+
+pub(crate) async fn gh_check_logs(
+    repo_root: &Path,
+    repo_name: &str,
+    sha: &str,
+    check_id: Option<u64>,
+) -> Result<Vec<GitHubCheckLog>, String> {
+    let runs = gh_get_json(
+        repo_root,
+        &format!("/repos/{repo_name}/commits/{sha}/check-runs?per_page={PAGE_SIZE}"),
+    )
+    .await?;
+    let mut logs = Vec::new();
+    for run in selected_runs(&runs, check_id) {
+        let Some(id) = run.get("id").and_then(Value::as_u64) else {
+            continue;
+        };
+        let endpoint = format!("/repos/{repo_name}/actions/jobs/{id}/logs");
+        let log = run_gh(repo_root, &["api", &endpoint])
+            .await
+            .ok()
+            .map(|output| String::from_utf8_lossy(&output).to_string());
+        logs.extend(check_log(run, log.as_deref()));
+    }
+    Ok(logs)
+}
+
+pub(crate) async fn gh_create_review_comment(
+    repo_root: &Path,
+    repo_name: &str,
+    pr_number: u64,
+    draft: &GitHubReviewCommentDraft,
+) -> Result<GitHubPullRequestComment, String> {
+    let mut payload = review_comment_payload(draft);
+    payload["commit_id"] = json!(gh_pull_request_head_sha(repo_root, repo_name, pr_number).await?);
+    let value = gh_send_json(
+        repo_root,
+        "POST",
+        &format!("/repos/{repo_name}/pulls/{pr_number}/comments"),
+        &payload,
+    )
+    .await?;
+    comment_from_value(&value).ok_or_else(unexpected_comment)
+}
+
+pub(crate) async fn gh_reply_to_review_comment(
+    repo_root: &Path,
+    repo_name: &str,
+    pr_number: u64,
+    comment_id: u64,
+    body: &str,
+) -> Result<GitHubPullRequestComment, String> {
+    let value = gh_send_json(
+        repo_root,
+        "POST",
+        &format!("/repos/{repo_name}/pulls/{pr_number}/comments/{comment_id}/replies"),
+        &json!({ "body": body }),
+    )
+    .await?;
+    comment_from_value(&value).ok_or_else(unexpected_comment)
+}
+
+pub(crate) async fn gh_submit_review(
+    repo_root: &Path,
+    repo_name: &str,
+    pr_number: u64,
+    event: &str,
+    body: &str,
+    comments: &[GitHubReviewCommentDraft],
+) -> Result<GitHubPullRequestReview, String> {
+    let value = gh_send_json(
+        repo_root,
+        "POST",
+        &format!("/repos/{repo_name}/pulls/{pr_number}/reviews"),
+        &review_payload(event, body, comments),
+    )
+    .await?;
+    review_from_value(&value, comments.len())
+        .ok_or_else(|| "GitHub returned an unexpected review response.".to_string())
+}
+
+async fn gh_view_pull_request(
+    repo_root: &Path,
+    repo_name: &str,
+    selector: &str,
+) -> Result<GitHubPullRequest, String> {
+    let output = run_gh(
+        repo_root,
+        &[
+            "pr",
+            "view",
+            selector,
+            "--repo",
+            repo_name,
+            "--json",
+            GH_PULL_REQUEST_FIELDS,
+        ],
+    )
+    .await?;
+    serde_json::from_slice(&output).map_err(|e| e.to_string())
+}
+
+pub(crate) async fn gh_pull_request_refs(
+    repo_root: &Path,
+    repo_name: &str,
+    pr_number: u64,
+) -> Result<PullRequestRefs, String> {
+    let output = run_gh(
+        repo_root,
+        &[
+            "pr",
+            "view",
+            &pr_number.to_string(),
+            "--repo",
+            repo_name,
+            "--json",
+            "headRefName,baseRefName,isCrossRepository,title",
+        ],
+    )
+    .await?;
+    serde_json::from_slice(&output).map_err(|e| e.to_string())
+}
+
+pub(crate) async fn gh_create_pull_request(
+    repo_root: &Path,
+    repo_name: &str,
+    draft: &PullRequestDraft,
+) -> Result<GitHubPullRequest, String> {
+    let mut args = vec![
+        "pr",
+        "create",
+        "--repo",
+        repo_name,
+        "--head",
+        draft.head.as_str(),
+        "--base",
+        draft.base.as_str(),
+        "--title",
+        draft.title.as_str(),
+        "--body",
+        draft.body.as_str(),
+    ];
+    if draft.draft {
+        args.push("--draft");
+    }
+    let output = run_gh(repo_root, &args).await?;
+    // `gh pr create` prints the new pull request URL last.
+    let output = String::from_utf8_lossy(&output);
+    let url = output
+        .lines()
+        .map(str::trim)
+        .rev()
+        .find(|line| !line.is_empty())
+        .ok_or("GitHub CLI did not report the new pull request.")?;
+    gh_view_pull_request(repo_root, repo_name, url).await
+}
+
+pub(crate) async fn gh_update_pull_request(
+    repo_root: &Path,
+    repo_name: &str,
+    pr_number: u64,
+    update: &PullRequestUpdate,
+) -> Result<GitHubPullRequest, String> {
+    let number = pr_number.to_string();
+    let mut args = vec!["pr", "edit", number.as_str(), "--repo", repo_name];
+    if let Some(title) = &update.title {
+        args.extend(["--title", title.as_str()]);
+    }
+    if let Some(body) = &update.body {
+        args.extend(["--body", body.as_str()]);
+    }
+    if let Some(base) = &update.base {
+        args.extend(["--base", base.as_str()]);
+    }
+    run_gh(repo_root, &args).await?;
+    gh_view_pull_request(repo_root, repo_name, &number).await
+}
+
+pub(crate) async fn gh_mark_ready_for_review(
+    repo_root: &Path,
+    repo_name: &str,
+    pr_number: u64,
+) -> Result<GitHubPullRequest, String> {
+    let number = pr_number.to_string();
+    run_gh(
+        repo_root,
+        &["pr", "ready", number.as_str(), "--repo", repo_name],
+    )
+    .await?;
+    gh_view_pull_request(repo_root, repo_name, &number).await
+}
+
+pub(crate) async fn gh_request_reviewers(
+    repo_root: &Path,
+    repo_name: &str,
+    pr_number: u64,
+    reviewers: &[String],
+) -> Result<GitHubPullRequest, String> {
+    let number = pr_number.to_string();
+    let reviewers = reviewers.join(",");
+    run_gh(
+        repo_root,
+        &[
+            "pr",
+            "edit",
+            number.as_str(),
+            "--repo",
+            repo_name,
+            "--add-reviewer",
+            reviewers.as_str(),
+        ],
+    )
+    .await?;
+    gh_view_pull_request(repo_root, repo_name, &number).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn mock_response(request: &str, base: &str, not_modified: &AtomicUsize) -> String {
+        let path = request.split_whitespace().nth(1).unwrap_or("");
+        let header = |name: &str| {
+            request.lines().find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                key.eq_ignore_ascii_case(name)
+                    .then(|| value.trim().to_string())
+            })
+        };
+        let rate = "x-ratelimit-limit: 5000\r\nx-ratelimit-remaining: 4990\r\nx-ratelimit-reset: 1700000000\r\nx-ratelimit-resource: core\r\n";
+        if header("authorization").as_deref() != Some("Bearer test-token") {
+            let body = r#"{"message":"Bad credentials"}"#;
+            return format!(
+                "HTTP/1.1 401 Unauthorized\r\n{rate}content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+        if path == "/limited" {
+            let body = r#"{"message":"API rate limit exceeded"}"#;
+            return format!(
+                "HTTP/1.1 403 Forbidden\r\nx-ratelimit-limit: 60\r\nx-ratelimit-remaining: 0\r\nx-ratelimit-reset: 0\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+        if path.starts_with("/repos/octo/demo/issues?state=open")
+            && header("if-none-match").as_deref() == Some("\"page-1\"")
+        {
+            not_modified.fetch_add(1, Ordering::SeqCst);
+            return format!("HTTP/1.1 304 Not Modified\r\n{rate}connection: close\r\n\r\n");
+        }
+        let (extra, body) = if path.starts_with("/repos/octo/demo/issues?state=open") {
+            (
+                format!("etag: \"page-1\"\r\nlink: <{base}/repos/octo/demo/issues?page=2>; rel=\"next\", <{base}/repos/octo/demo/issues?page=2>; rel=\"last\"\r\n"),
+                r#"[{"number":1,"title":"Bug","html_url":"https://github.com/octo/demo/issues/1","updated_at":"2024-01-01T00:00:00Z"},{"number":2,"title":"PR","pull_request":{}}]"#.to_string(),
+            )
+        } else if path == "/repos/octo/demo/issues?page=2" {
+            (
+                String::new(),
+                r#"[{"number":3,"title":"Docs","html_url":"https://github.com/octo/demo/issues/3","updated_at":"2024-01-02T00:00:00Z"}]"#.to_string(),
+            )
+        } else if path.starts_with("/search/issues") {
+            (String::new(), r#"{"total_count":7}"#.to_string())
+        } else if path == "/repos/octo/demo/pulls/5" {
+            (String::new(), "diff --git a/a.txt b/a.txt\n".to_string())
+        } else {
+            (String::new(), r#"{"message":"Not Found"}"#.to_string())
+        };
+        let status = if body.contains("Not Found") {
+            "404 Not Found"
+        } else {
+            "200 OK"
+        };
+        format!(
+            "HTTP/1.1 {status}\r\n{rate}{extra}content-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    #[test]
+    fn client_paginates_caches_and_reports_rate_limits() {
+        let runtime = tokio::runtime::Runtime::new().expect("runtime");
+        runtime.block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+            let base = format!("http://{}", listener.local_addr().expect("addr"));
+            let not_modified = Arc::new(AtomicUsize::new(0));
+            let server_base = base.clone();
+            let server_not_modified = not_modified.clone();
+            tokio::spawn(async move {
+                while let Ok((mut socket, _)) = listener.accept().await {
+                    let mut buffer = vec![0u8; 8192];
+                    let read = socket.read(&mut buffer).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+                    let response = mock_response(&request, &server_base, &server_not_modified);
+                    let _ = socket.write_all(response.as_bytes()).await;
+                    let _ = socket.shutdown().await;
+                }
+            });
+
+            let client =
+                GitHubClient::new(Some(&format!("{base}/")), Some("test-token".to_string()))
+                    .expect("client");
+            let issues = client.list_issues("octo/demo").await.expect("issues");
+            assert_eq!(issues.total, 7);
+            let numbers: Vec<u64> = issues.issues.iter().map(|issue| issue.number).collect();
+            assert_eq!(numbers, vec![1, 3]);
+            assert_eq!(
+                issues.rate_limit.as_ref().map(|limit| limit.remaining),
+                Some(4990)
+            );
+
+            let cached = client
+                .list_issues("octo/demo")
+                .await
+                .expect("cached issues");
+            assert_eq!(cached.issues.len(), 2);
+            assert_eq!(not_modified.load(Ordering::SeqCst), 1);
+
+            let diff = client
+                .pull_request_diff("octo/demo", 5)
+                .await
+                .expect("diff");
+            assert!(diff.starts_with("diff --git"));
+
+            let missing = client.pull_request_comments("octo/demo", 9).await;
+            assert!(missing.expect_err("not found").contains("Not Found"));
+            let limited = client.get_json("/limited").await;
+            assert!(limited
+                .expect_err("rate limited")
+                .contains("rate limit exceeded"));
+
+            let anonymous = GitHubClient::new(Some(&base), None).expect("client");
+            let rejected = anonymous.list_pull_requests("octo/demo").await;
+            assert!(rejected
+                .expect_err("unauthorized")
+                .contains("Bad credentials"));
+            let rejected = anonymous.create_issue_comment("octo/demo", 1, "hi").await;
+            assert!(anonymous.write_falls_back_to_gh(&rejected.expect_err("unauthorized")));
+
+            let missing = client.create_issue_comment("octo/demo", 1, "hi").await;
+            assert!(!client.write_falls_back_to_gh(&missing.expect_err("not found")));
+            let closed = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+            let closed_base = format!("http://{}", closed.local_addr().expect("addr"));
+            drop(closed);
+            let offline = GitHubClient::new(Some(&closed_base), Some("test-token".to_string()))
+                .expect("client");
+            let unreachable = offline.create_issue_comment("octo/demo", 1, "hi").await;
+            assert!(offline.write_falls_back_to_gh(&unreachable.expect_err("unreachable")));
+        });
+    }
+
+    #[test]
+    fn fill_uses_single_commit_or_lists_several() {
+        let root = std::env::temp_dir().join(format!(
+            "codex-monitor-pr-fill-test-{}",
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::create_dir_all(&root).expect("create temp repo root");
+        let repo = Repository::init(&root).expect("init repo");
+        let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+        let tree_id = repo.index().expect("index").write_tree().expect("tree");
+        let tree = repo.find_tree(tree_id).expect("find tree");
+        let commit = |message: &str| {
+            let parents: Vec<git2::Commit> = repo
+                .head()
+                .ok()
+                .and_then(|head| head.peel_to_commit().ok())
+                .into_iter()
+                .collect();
+            let parents: Vec<&git2::Commit> = parents.iter().collect();
+            repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+                .expect("commit")
+        };
+        let base = commit("init");
+        commit("feat: add login\n\nAdds the login form.\n");
+        assert_eq!(
+            pull_request_fill(&repo, Some(base), "feat/login-form").expect("fill"),
+            (
+                "feat: add login".to_string(),
+                "Adds the login form.".to_string()
+            )
+        );
+
+        commit("fix: redirect loop");
+        let (title, body) = pull_request_fill(&repo, Some(base), "feat/login-form").expect("fill");
+        assert_eq!(title, "Login form");
+        assert_eq!(body, "- feat: add login\n- fix: redirect loop");
+        let (title, _) = pull_request_fill(&repo, None, "feat/login-form").expect("fill");
+        assert_eq!(title, "fix: redirect loop");
+    }
+
+    #[test]
+    fn review_findings_become_inline_comments() {
+        let root = Path::new("/work/demo");
+        let text = "The patch mostly works.\n\nFull review comments:\n\n- [P1] Avoid unwrap on user input — /work/demo/src/main.rs:10-12\n  Parsing can fail here.\n\n  Return an error instead.\n- [P2] Typo — docs/guide.md:4\n  \"teh\" should be \"the\".\n- [P3] Outside — /elsewhere/lib.rs:1-1\n  Ignored.\nTrailing summary.\n";
+        let comments = review_findings_to_comments(text, root);
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].path, "src/main.rs");
+        assert_eq!((comments[0].start_line, comments[0].line), (Some(10), 12));
+        assert_eq!(
+            comments[0].body,
+            "**[P1] Avoid unwrap on user input**\n\nParsing can fail here.\n\nReturn an error instead."
+        );
+        assert_eq!(comments[1].path, "docs/guide.md");
+        assert_eq!((comments[1].start_line, comments[1].line), (None, 4));
+
+        let payload = review_payload(
+            review_event("request changes").expect("event"),
+            "",
+            &comments,
+        );
+        assert_eq!(payload["event"], "REQUEST_CHANGES");
+        assert!(payload.get("body").is_none());
+        assert_eq!(payload["comments"][0]["start_line"], 10);
+        assert_eq!(payload["comments"][0]["side"], "RIGHT");
+        assert!(payload["comments"][1].get("start_line").is_none());
+        assert!(review_event("merge").is_err());
+    }
+
+    #[test]
+    fn checks_summarize_runs_statuses_and_failing_logs() {
+        let runs = json!({"check_runs": [
+            {"id": 11, "name": "test", "status": "completed", "conclusion": "failure",
+             "html_url": "https://github.com/octo/demo/runs/11",
+             "started_at": "2024-01-01T00:00:00Z", "completed_at": "2024-01-01T00:02:05Z"},
+            {"id": 12, "name": "lint", "status": "completed", "conclusion": "success"}
+        ]});
+        let statuses = json!({"statuses": [
+            {"context": "deploy/preview", "state": "pending", "target_url": "https://example.com",
+             "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-01T00:01:00Z"}
+        ]});
+        let mut checks = checks_from_values(&runs, &statuses);
+        let names: Vec<&str> = checks.iter().map(|check| check.name.as_str()).collect();
+        assert_eq!(names, vec!["deploy/preview", "lint", "test"]);
+        assert_eq!(checks[2].duration_secs, Some(125));
+        assert_eq!(checks[0].status, "in_progress");
+        assert_eq!(checks[0].completed_at, None);
+        assert_eq!(checks_state(&checks), "pending");
+        checks.remove(0);
+        assert_eq!(checks_state(&checks), "failure");
+        assert_eq!(checks_state(&[]), "none");
+
+        let failing = selected_runs(&runs, None);
+        assert_eq!(failing.len(), 1);
+        assert_eq!(selected_runs(&runs, Some(12)).len(), 1);
+
+        let mut log =
+            String::from("\u{feff}2024-01-01T00:00:00.1000000Z ##[group]Run cargo test\n");
+        for index in 0..300 {
+            log.push_str(&format!("2024-01-01T00:00:01.0000000Z line {index}\n"));
+        }
+        log.push_str(
+            "2024-01-01T00:00:02.0000000Z ##[error]Process completed with exit code 101.\n",
+        );
+        log.push_str("2024-01-01T00:00:03.0000000Z Cleaning up\n");
+        let (excerpt, truncated) = log_excerpt(&log);
+        assert!(truncated);
+        assert!(excerpt.starts_with("line 270\n"));
+        assert!(excerpt.ends_with("##[error]Process completed with exit code 101.\nCleaning up"));
+        let (short, truncated) = log_excerpt("2024-01-01T00:00:00Z only line\n");
+        assert_eq!(short, "only line");
+        assert!(!truncated);
+
+        let fallback = check_log(
+            &json!({"id": 13, "name": "external", "conclusion": "failure",
+                    "output": {"title": "2 issues", "summary": "", "text": "src/lib.rs:3 unused"}}),
+            None,
+        )
+        .expect("log");
+        assert_eq!(fallback.excerpt, "2 issues\n\nsrc/lib.rs:3 unused");
+    }
+}
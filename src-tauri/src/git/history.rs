@@ -0,0 +1,327 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use git2::{BlameOptions, DiffFindOptions, Oid, Repository, Sort};
+
+use super::{blob_to_lines, resolve_commit};
+use crate::git_utils::commit_to_entry;
+use crate::types::{GitBlameHunk, GitBlameResponse, GitFileLogEntry, GitLogEntry, GitLogQuery};
+use crate::utils::normalize_git_path;
+
+fn tree_entry_id(tree: &git2::Tree, path: &str) -> Option<Oid> {
+    tree.get_path(Path::new(path)).ok().map(|entry| entry.id())
+}
+
+/// True when `commit` changed `path` relative to every parent (git's TREESAME rule).
+fn commit_touches_path(commit: &git2::Commit, path: &str) -> bool {
+    let entry_id = commit
+        .tree()
+        .ok()
+        .and_then(|tree| tree_entry_id(&tree, path));
+    let mut parents = commit.parents().peekable();
+    if parents.peek().is_none() {
+        return entry_id.is_some();
+    }
+    parents.all(|parent| {
+        let parent_id = parent
+            .tree()
+            .ok()
+            .and_then(|tree| tree_entry_id(&tree, path));
+        parent_id != entry_id
+    })
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+struct LogFilter {
+    author: Option<String>,
+    message: Option<String>,
+    path: Option<String>,
+    since: Option<i64>,
+    until: Option<i64>,
+}
+
+impl LogFilter {
+    fn new(query: &GitLogQuery) -> Self {
+        Self {
+            author: non_empty(&query.author).map(|value| value.to_lowercase()),
+            message: non_empty(&query.message).map(|value| value.to_lowercase()),
+            path: non_empty(&query.path)
+                .map(|value| normalize_git_path(&value).trim_end_matches('/').to_string()),
+            since: query.since,
+            until: query.until,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.author.is_none()
+            && self.message.is_none()
+            && self.path.is_none()
+            && self.since.is_none()
+            && self.until.is_none()
+    }
+
+    fn matches(&self, commit: &git2::Commit) -> bool {
+        let time = commit.time().seconds();
+        if self.since.is_some_and(|since| time < since)
+            || self.until.is_some_and(|until| time > until)
+        {
+            return false;
+        }
+        if let Some(author) = self.author.as_deref() {
+            let signature = commit.author();
+            let name = signature.name().unwrap_or("").to_lowercase();
+            let email = signature.email().unwrap_or("").to_lowercase();
+            if !name.contains(author) && !email.contains(author) {
+                return false;
+            }
+        }
+        if let Some(message) = self.message.as_deref() {
+            let text = String::from_utf8_lossy(commit.message_bytes()).to_lowercase();
+            if !text.contains(message) {
+                return false;
+            }
+        }
+        match self.path.as_deref() {
+            Some(path) => commit_touches_path(commit, path),
+            None => true,
+        }
+    }
+}
+
+fn log_revwalk<'repo>(
+    repo: &'repo Repository,
+    query: &GitLogQuery,
+) -> Result<git2::Revwalk<'repo>, String> {
+    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+    if query.all_branches {
+        revwalk
+            .push_glob("refs/heads/*")
+            .map_err(|e| e.to_string())?;
+        revwalk
+            .push_glob("refs/remotes/*")
+            .map_err(|e| e.to_string())?;
+        // Detached HEADs are not under refs/heads; an unborn HEAD has nothing to add.
+        let _ = revwalk.push_head();
+    } else if let Some(branch) = non_empty(&query.branch) {
+        let oid = resolve_commit(repo, &branch)?.id();
+        revwalk.push(oid).map_err(|e| e.to_string())?;
+    } else {
+        revwalk.push_head().map_err(|e| e.to_string())?;
+    }
+    revwalk.set_sorting(Sort::TIME).map_err(|e| e.to_string())?;
+    Ok(revwalk)
+}
+
+/// Returns one page of matching commits and the cursor for the next page.
+pub(crate) fn query_log(
+    repo: &Repository,
+    query: &GitLogQuery,
+    limit: usize,
+) -> Result<(Vec<GitLogEntry>, Option<String>), String> {
+    let filter = LogFilter::new(query);
+    let cursor = match non_empty(&query.cursor) {
+        Some(cursor) => Some(Oid::from_str(&cursor).map_err(|e| e.to_string())?),
+        None => None,
+    };
+    let mut skipping = cursor.is_some();
+    let mut entries: Vec<GitLogEntry> = Vec::new();
+    let mut next_cursor = None;
+    for oid_result in log_revwalk(repo, query)? {
+        let oid = oid_result.map_err(|e| e.to_string())?;
+        if skipping {
+            skipping = Some(oid) != cursor;
+            continue;
+        }
+        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+        if !filter.matches(&commit) {
+            continue;
+        }
+        if entries.len() >= limit {
+            next_cursor = entries.last().map(|entry| entry.sha.clone());
+            break;
+        }
+        entries.push(commit_to_entry(commit));
+    }
+    if skipping {
+        return Err("The log cursor is no longer reachable; reload from the start.".to_string());
+    }
+    Ok((entries, next_cursor))
+}
+
+/// Walks the whole selection, so only call this when a total was requested.
+pub(crate) fn count_log(repo: &Repository, query: &GitLogQuery) -> Result<usize, String> {
+    let filter = LogFilter::new(query);
+    let unfiltered = filter.is_empty();
+    let mut total = 0usize;
+    for oid_result in log_revwalk(repo, query)? {
+        let oid = oid_result.map_err(|e| e.to_string())?;
+        if unfiltered {
+            total += 1;
+            continue;
+        }
+        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+        if filter.matches(&commit) {
+            total += 1;
+        }
+    }
+    Ok(total)
+}
+
+/// Returns the pre-rename path when `commit` renamed something into `path`.
+fn renamed_from(repo: &Repository, commit: &git2::Commit, path: &str) -> Option<String> {
+    let parent_tree = commit.parent(0).ok()?.tree().ok()?;
+    let tree = commit.tree().ok()?;
+    let mut diff = repo
+        .diff_tree_to_tree(Some(&parent_tree), Some(&tree), None)
+        .ok()?;
+    let mut find_options = DiffFindOptions::new();
+    find_options.renames(true);
+    diff.find_similar(Some(&mut find_options)).ok()?;
+    diff.deltas()
+        .filter(|delta| delta.status() == git2::Delta::Renamed)
+        .find(|delta| delta.new_file().path() == Some(Path::new(path)))
+        .and_then(|delta| delta.old_file().path())
+        .map(|old| old.to_string_lossy().to_string())
+}
+
+/// Walks history from HEAD, keeping commits that changed `path` and switching
+/// to the old name whenever a rename is crossed. Stops at the commit that
+/// added the file.
+pub(crate) fn file_log(
+    repo: &Repository,
+    path: &str,
+    limit: usize,
+) -> Result<Vec<GitFileLogEntry>, String> {
+    let mut current_path = normalize_git_path(path).trim().to_string();
+    if current_path.is_empty() {
+        return Err("Path is required.".to_string());
+    }
+
+    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+    revwalk.push_head().map_err(|e| e.to_string())?;
+    revwalk
+        .set_sorting(Sort::TIME | Sort::TOPOLOGICAL)
+        .map_err(|e| e.to_string())?;
+
+    let mut entries = Vec::new();
+    for oid_result in revwalk {
+        if entries.len() >= limit {
+            break;
+        }
+        let oid = oid_result.map_err(|e| e.to_string())?;
+        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+        let tree = commit.tree().map_err(|e| e.to_string())?;
+        let Some(entry_id) = tree_entry_id(&tree, &current_path) else {
+            continue;
+        };
+        let parent_ids: Vec<Option<Oid>> = commit
+            .parents()
+            .map(|parent| {
+                parent
+                    .tree()
+                    .ok()
+                    .and_then(|tree| tree_entry_id(&tree, &current_path))
+            })
+            .collect();
+        if parent_ids.contains(&Some(entry_id)) {
+            continue;
+        }
+
+        let in_parent = parent_ids.iter().any(Option::is_some);
+        let previous_path = if in_parent {
+            None
+        } else {
+            renamed_from(repo, &commit, &current_path)
+        };
+        let status = match (&previous_path, in_parent) {
+            (Some(_), _) => "R",
+            (None, true) => "M",
+            (None, false) => "A",
+        };
+        entries.push(GitFileLogEntry {
+            sha: commit.id().to_string(),
+            summary: commit.summary().unwrap_or("").to_string(),
+            author: commit.author().name().unwrap_or("").to_string(),
+            timestamp: commit.time().seconds(),
+            path: current_path.clone(),
+            status: status.to_string(),
+            previous_path: previous_path.clone(),
+        });
+        match previous_path {
+            Some(previous_path) => current_path = previous_path,
+            // Older history holds nothing for this file.
+            None if !in_parent => break,
+            None => {}
+        }
+    }
+    Ok(entries)
+}
+
+pub(crate) fn blame_file(
+    repo: &Repository,
+    path: &str,
+    rev: Option<&str>,
+) -> Result<GitBlameResponse, String> {
+    let path = normalize_git_path(path).trim().to_string();
+    if path.is_empty() {
+        return Err("Path is required.".to_string());
+    }
+    let commit = resolve_commit(repo, rev.unwrap_or("HEAD"))?;
+    let blob = commit
+        .tree()
+        .map_err(|e| e.to_string())?
+        .get_path(Path::new(&path))
+        .map_err(|_| format!("{path} does not exist at {}.", commit.id()))
+        .and_then(|entry| repo.find_blob(entry.id()).map_err(|e| e.to_string()))?;
+    let lines = blob_to_lines(blob).ok_or_else(|| format!("{path} is a binary file."))?;
+
+    let mut options = BlameOptions::new();
+    options.newest_commit(commit.id());
+    let blame = repo
+        .blame_file(Path::new(&path), Some(&mut options))
+        .map_err(|e| e.to_string())?;
+
+    let mut summaries: HashMap<Oid, String> = HashMap::new();
+    let mut hunks = Vec::with_capacity(blame.len());
+    for hunk in blame.iter() {
+        let sha = hunk.final_commit_id();
+        let summary = summaries
+            .entry(sha)
+            .or_insert_with(|| {
+                repo.find_commit(sha)
+                    .ok()
+                    .and_then(|commit| commit.summary().map(str::to_string))
+                    .unwrap_or_default()
+            })
+            .clone();
+        let signature = hunk.final_signature();
+        let original_path = hunk
+            .path()
+            .map(|value| normalize_git_path(&value.to_string_lossy()))
+            .filter(|value| *value != path);
+        hunks.push(GitBlameHunk {
+            start_line: hunk.final_start_line(),
+            line_count: hunk.lines_in_hunk(),
+            sha: sha.to_string(),
+            author: signature.name().unwrap_or("").to_string(),
+            timestamp: signature.when().seconds(),
+            summary,
+            original_path,
+            original_start_line: hunk.orig_start_line(),
+        });
+    }
+
+    Ok(GitBlameResponse {
+        path,
+        sha: commit.id().to_string(),
+        lines,
+        hunks,
+    })
+}
@@ -0,0 +1,332 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Instant;
+
+use git2::Repository;
+use uuid::Uuid;
+
+use crate::shared::git_core;
+use crate::shared::process_core::tokio_command;
+use crate::types::{GitCommitResult, GitCommitSignature, GitHookResult};
+use crate::utils::git_env_path;
+
+const COMMIT_MESSAGE_FILE_PREFIX: &str = "CODEX_MONITOR_COMMIT_MSG";
+
+/// A fresh message file per call so concurrent commits in the same repository
+/// never read each other's message. Callers remove it when done.
+fn commit_message_path(git_dir: &Path) -> PathBuf {
+    git_dir.join(format!("{COMMIT_MESSAGE_FILE_PREFIX}_{}", Uuid::new_v4()))
+}
+
+struct CommitSettings {
+    hooks_dir: PathBuf,
+    git_dir: PathBuf,
+    signing_required: bool,
+    signing_format: String,
+}
+
+fn commit_settings(repo_root: &Path) -> Result<CommitSettings, String> {
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let config = repo.config().map_err(|e| e.to_string())?;
+    let hooks_dir = match config.get_path("core.hooksPath").ok() {
+        Some(path) if path.is_absolute() => path,
+        Some(path) => repo_root.join(path),
+        None => repo.commondir().join("hooks"),
+    };
+    Ok(CommitSettings {
+        hooks_dir,
+        git_dir: repo.path().to_path_buf(),
+        signing_required: config.get_bool("commit.gpgsign").unwrap_or(false),
+        signing_format: config
+            .get_string("gpg.format")
+            .unwrap_or_else(|_| "openpgp".to_string()),
+    })
+}
+
+fn find_hook(hooks_dir: &Path, name: &str) -> Option<PathBuf> {
+    let path = hooks_dir.join(name);
+    let metadata = fs::metadata(&path).ok()?;
+    if !metadata.is_file() {
+        return None;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        // Git silently ignores hooks that are not executable; so do we.
+        if metadata.permissions().mode() & 0o111 == 0 {
+            return None;
+        }
+    }
+    Some(path)
+}
+
+async fn run_hook(repo_root: &Path, hook: &Path, name: &str, args: &[&str]) -> GitHookResult {
+    let started = Instant::now();
+    // Hooks are usually shell scripts, which Windows cannot execute directly.
+    let mut command = if cfg!(windows) {
+        let mut command = tokio_command("sh");
+        command.arg(hook);
+        command
+    } else {
+        tokio_command(hook)
+    };
+    let output = command
+        .args(args)
+        .current_dir(repo_root)
+        .env("PATH", git_env_path())
+        .env("GIT_EDITOR", ":")
+        .stdin(Stdio::null())
+        .output()
+        .await;
+    let duration_ms = started.elapsed().as_millis() as u64;
+    match output {
+        Ok(output) => {
+            let mut text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if !stderr.trim().is_empty() {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(stderr.trim());
+            }
+            GitHookResult {
+                name: name.to_string(),
+                passed: output.status.success(),
+                output: text,
+                duration_ms,
+            }
+        }
+        Err(err) => GitHookResult {
+            name: name.to_string(),
+            passed: false,
+            output: format!("Failed to run {name} hook: {err}"),
+            duration_ms,
+        },
+    }
+}
+
+/// Runs pre-commit, then commit-msg against `message_path` when given,
+/// stopping at the first failure like `git commit` does.
+async fn run_commit_hooks(
+    repo_root: &Path,
+    hooks_dir: &Path,
+    message_path: Option<&Path>,
+) -> Vec<GitHookResult> {
+    let mut results = Vec::new();
+    if let Some(hook) = find_hook(hooks_dir, "pre-commit") {
+        results.push(run_hook(repo_root, &hook, "pre-commit", &[]).await);
+        if results.iter().any(|result| !result.passed) {
+            return results;
+        }
+    }
+    if let (Some(hook), Some(message_path)) = (find_hook(hooks_dir, "commit-msg"), message_path) {
+        let message_arg = message_path.to_string_lossy().to_string();
+        results.push(run_hook(repo_root, &hook, "commit-msg", &[message_arg.as_str()]).await);
+    }
+    results
+}
+
+pub(crate) fn signature_status_for_code(code: &str) -> &'static str {
+    match code.trim() {
+        "G" => "good",
+        "U" => "untrusted",
+        "B" => "bad",
+        "X" | "Y" => "expired",
+        "R" => "revoked",
+        "E" => "unverifiable",
+        _ => "unsigned",
+    }
+}
+
+fn looks_like_signing_failure(error: &str) -> bool {
+    let lower = error.to_lowercase();
+    lower.contains("failed to sign") || lower.contains("signing failed")
+}
+
+async fn head_signature(repo_root: &Path, required: bool, format: &str) -> GitCommitSignature {
+    let repo_path = repo_root.to_path_buf();
+    let output =
+        git_core::run_git_command(&repo_path, &["log", "-1", "--format=%G?%x00%GS", "HEAD"])
+            .await
+            .unwrap_or_default();
+    let (code, signer) = output.split_once('\0').unwrap_or((output.as_str(), ""));
+    let status = signature_status_for_code(code);
+    GitCommitSignature {
+        required,
+        format: (status != "unsigned" || required).then(|| format.to_string()),
+        status: status.to_string(),
+        signer: Some(signer.trim().to_string()).filter(|value| !value.is_empty()),
+    }
+}
+
+/// Commits with hooks run individually so their output can be reported.
+/// Git itself still runs prepare-commit-msg and post-commit.
+pub(crate) async fn commit_with_hooks(
+    repo_root: &Path,
+    message: &str,
+    verify: bool,
+) -> Result<GitCommitResult, String> {
+    let settings = commit_settings(repo_root)?;
+    let message_path = commit_message_path(&settings.git_dir);
+    fs::write(&message_path, message).map_err(|e| e.to_string())?;
+    let result = commit_from_message_file(repo_root, &settings, &message_path, verify).await;
+    let _ = fs::remove_file(&message_path);
+    result
+}
+
+async fn commit_from_message_file(
+    repo_root: &Path,
+    settings: &CommitSettings,
+    message_path: &Path,
+    verify: bool,
+) -> Result<GitCommitResult, String> {
+    let hooks = if verify {
+        run_commit_hooks(repo_root, &settings.hooks_dir, Some(message_path)).await
+    } else {
+        Vec::new()
+    };
+    if let Some(failed) = hooks.iter().find(|hook| !hook.passed) {
+        return Ok(GitCommitResult {
+            success: false,
+            sha: None,
+            message: Some(format!("{} hook failed.", failed.name)),
+            hooks,
+            signature: None,
+            output: String::new(),
+        });
+    }
+
+    let repo_path = repo_root.to_path_buf();
+    let message_arg = message_path.to_string_lossy().to_string();
+    match git_core::run_git_command(
+        &repo_path,
+        &["commit", "--no-verify", "-F", message_arg.as_str()],
+    )
+    .await
+    {
+        Ok(output) => {
+            let sha = Repository::open(repo_root)
+                .ok()
+                .and_then(|repo| repo.head().ok().and_then(|head| head.target()))
+                .map(|oid| oid.to_string());
+            let signature = head_signature(
+                repo_root,
+                settings.signing_required,
+                &settings.signing_format,
+            )
+            .await;
+            let message = (settings.signing_required && signature.status == "unsigned")
+                .then(|| "commit.gpgsign is set but the commit is unsigned.".to_string());
+            Ok(GitCommitResult {
+                success: true,
+                sha,
+                hooks,
+                signature: Some(signature),
+                output,
+                message,
+            })
+        }
+        Err(err) => {
+            let signature = looks_like_signing_failure(&err).then(|| GitCommitSignature {
+                required: settings.signing_required,
+                format: Some(settings.signing_format.clone()),
+                status: "failed".to_string(),
+                signer: None,
+            });
+            if signature.is_none() {
+                return Err(err);
+            }
+            Ok(GitCommitResult {
+                success: false,
+                sha: None,
+                hooks,
+                signature,
+                output: String::new(),
+                message: Some(err),
+            })
+        }
+    }
+}
+
+/// Dry-runs the commit hooks against the staged changes without committing.
+pub(crate) async fn check_commit_hooks(
+    repo_root: &Path,
+    message: Option<&str>,
+) -> Result<Vec<GitHookResult>, String> {
+    let settings = commit_settings(repo_root)?;
+    let Some(message) = message else {
+        return Ok(run_commit_hooks(repo_root, &settings.hooks_dir, None).await);
+    };
+    let message_path = commit_message_path(&settings.git_dir);
+    fs::write(&message_path, message).map_err(|e| e.to_string())?;
+    let results = run_commit_hooks(repo_root, &settings.hooks_dir, Some(&message_path)).await;
+    let _ = fs::remove_file(&message_path);
+    Ok(results)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn write_hook(hooks_dir: &Path, name: &str, body: &str) {
+        let path = hooks_dir.join(name);
+        fs::write(&path, format!("#!/bin/sh\n{body}\n")).expect("write hook");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).expect("chmod hook");
+    }
+
+    #[test]
+    fn commit_reports_hook_results_and_signature_status() {
+        let root =
+            std::env::temp_dir().join(format!("codex-monitor-hooks-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create temp repo root");
+        let repo = Repository::init(&root).expect("init repo");
+        let mut config = repo.config().expect("config");
+        config.set_str("user.name", "Test").expect("user.name");
+        config
+            .set_str("user.email", "test@example.com")
+            .expect("user.email");
+        let hooks_dir = root.join(".git/hooks");
+        fs::create_dir_all(&hooks_dir).expect("hooks dir");
+        write_hook(&hooks_dir, "pre-commit", "echo linting");
+        write_hook(
+            &hooks_dir,
+            "commit-msg",
+            "grep -q '^feat' \"$1\" || { echo 'use a conventional type' >&2; exit 1; }",
+        );
+        fs::write(root.join("a.txt"), "one\n").expect("write file");
+        let mut index = repo.index().expect("index");
+        index.add_path(Path::new("a.txt")).expect("add path");
+        index.write().expect("write index");
+
+        let runtime = tokio::runtime::Runtime::new().expect("runtime");
+        let checks = runtime
+            .block_on(check_commit_hooks(&root, Some("wip")))
+            .expect("dry run");
+        assert_eq!(checks.len(), 2);
+        assert!(checks[0].passed);
+        assert_eq!(checks[0].output, "linting");
+        assert!(!checks[1].passed);
+        assert!(checks[1].output.contains("conventional type"));
+
+        let rejected = runtime
+            .block_on(commit_with_hooks(&root, "wip", true))
+            .expect("rejected commit");
+        assert!(!rejected.success);
+        assert_eq!(rejected.message.as_deref(), Some("commit-msg hook failed."));
+        assert!(repo.head().is_err());
+
+        let committed = runtime
+            .block_on(commit_with_hooks(&root, "feat: one", true))
+            .expect("commit");
+        assert!(committed.success);
+        assert_eq!(committed.hooks.len(), 2);
+        let head = repo.head().expect("head").target().expect("target");
+        assert_eq!(committed.sha, Some(head.to_string()));
+        let signature = committed.signature.expect("signature");
+        assert_eq!(signature.status, "unsigned");
+        assert!(!signature.required);
+        assert!(!root.join(".git").join(COMMIT_MESSAGE_FILE).exists());
+    }
+}
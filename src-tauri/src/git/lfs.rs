@@ -0,0 +1,204 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use git2::{AttrCheckFlags, Repository, Tree};
+use serde_json::Value;
+
+use super::{encode_image_base64, MAX_IMAGE_BYTES};
+use crate::types::{GitLfsChange, GitLfsLock};
+
+const LFS_POINTER_PREFIX: &[u8] = b"version https://git-lfs.github.com/spec/";
+const MAX_POINTER_BYTES: usize = 1024;
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct LfsPointer {
+    pub(crate) oid: String,
+    pub(crate) size: u64,
+}
+
+pub(crate) fn parse_lfs_pointer(bytes: &[u8]) -> Option<LfsPointer> {
+    if bytes.len() > MAX_POINTER_BYTES || !bytes.starts_with(LFS_POINTER_PREFIX) {
+        return None;
+    }
+    let text = std::str::from_utf8(bytes).ok()?;
+    let mut oid = None;
+    let mut size = None;
+    for line in text.lines() {
+        if let Some(value) = line.strip_prefix("oid sha256:") {
+            let value = value.trim();
+            if value.len() == 64 && value.chars().all(|ch| ch.is_ascii_hexdigit()) {
+                oid = Some(value.to_ascii_lowercase());
+            }
+        } else if let Some(value) = line.strip_prefix("size ") {
+            size = value.trim().parse::<u64>().ok();
+        }
+    }
+    Some(LfsPointer {
+        oid: oid?,
+        size: size?,
+    })
+}
+
+/// True when `.gitattributes` routes `path` through the LFS filter.
+pub(crate) fn is_lfs_path(repo: &Repository, path: &Path) -> bool {
+    repo.get_attr(path, "filter", AttrCheckFlags::FILE_THEN_INDEX)
+        .ok()
+        .flatten()
+        == Some("lfs")
+}
+
+fn lfs_storage_dir(repo: &Repository) -> PathBuf {
+    let common_dir = repo.commondir().to_path_buf();
+    let configured = repo
+        .config()
+        .ok()
+        .and_then(|config| config.get_path("lfs.storage").ok());
+    match configured {
+        Some(path) if path.is_absolute() => path,
+        Some(path) => common_dir.join(path),
+        None => common_dir.join("lfs"),
+    }
+}
+
+pub(crate) fn lfs_object_path(repo: &Repository, oid: &str) -> PathBuf {
+    lfs_storage_dir(repo)
+        .join("objects")
+        .join(&oid[0..2])
+        .join(&oid[2..4])
+        .join(oid)
+}
+
+/// Reads a fetched LFS object, or None when it is missing or over `max_bytes`.
+pub(crate) fn read_lfs_object(
+    repo: &Repository,
+    pointer: &LfsPointer,
+    max_bytes: usize,
+) -> Option<Vec<u8>> {
+    if pointer.size > max_bytes as u64 {
+        return None;
+    }
+    fs::read(lfs_object_path(repo, &pointer.oid)).ok()
+}
+
+pub(crate) fn tree_lfs_pointer(
+    repo: &Repository,
+    tree: Option<&Tree>,
+    path: Option<&Path>,
+) -> Option<LfsPointer> {
+    let entry = tree?.get_path(path?).ok()?;
+    let blob = repo.find_blob(entry.id()).ok()?;
+    parse_lfs_pointer(blob.content())
+}
+
+/// Returns the pointer when the working copy was never smudged, otherwise the
+/// size of the real content if `.gitattributes` says the path is LFS-tracked.
+pub(crate) fn workdir_lfs_state(
+    repo: &Repository,
+    repo_root: &Path,
+    path: &Path,
+) -> (Option<LfsPointer>, Option<u64>) {
+    let Ok(metadata) = fs::metadata(repo_root.join(path)) else {
+        return (None, None);
+    };
+    if metadata.len() <= MAX_POINTER_BYTES as u64 {
+        let pointer = fs::read(repo_root.join(path))
+            .ok()
+            .and_then(|bytes| parse_lfs_pointer(&bytes));
+        if pointer.is_some() {
+            return (pointer, None);
+        }
+    }
+    if is_lfs_path(repo, path) {
+        return (None, Some(metadata.len()));
+    }
+    (None, None)
+}
+
+pub(crate) fn lfs_image_base64(repo: &Repository, pointer: Option<&LfsPointer>) -> Option<String> {
+    let data = read_lfs_object(repo, pointer?, MAX_IMAGE_BYTES)?;
+    encode_image_base64(&data)
+}
+
+/// Builds the LFS side-by-side summary from the pointer (or smudged file)
+/// found on each side of a change.
+pub(crate) fn lfs_change(
+    repo: &Repository,
+    old_pointer: Option<&LfsPointer>,
+    new_pointer: Option<&LfsPointer>,
+    new_smudged_size: Option<u64>,
+) -> GitLfsChange {
+    let available = |pointer: Option<&LfsPointer>| {
+        pointer.is_some_and(|p| lfs_object_path(repo, &p.oid).is_file())
+    };
+    GitLfsChange {
+        old_oid: old_pointer.map(|pointer| pointer.oid.clone()),
+        new_oid: new_pointer.map(|pointer| pointer.oid.clone()),
+        old_size: old_pointer.map(|pointer| pointer.size),
+        new_size: new_pointer.map(|pointer| pointer.size).or(new_smudged_size),
+        old_available: available(old_pointer),
+        new_available: available(new_pointer) || new_smudged_size.is_some(),
+    }
+}
+
+/// Parses `git lfs locks --json`, which is an array of lock objects.
+pub(crate) fn parse_lfs_locks(output: &str) -> Result<Vec<GitLfsLock>, String> {
+    let trimmed = output.trim();
+    if trimmed.is_empty() {
+        return Ok(Vec::new());
+    }
+    let value: Value = serde_json::from_str(trimmed).map_err(|e| e.to_string())?;
+    let Some(items) = value.as_array() else {
+        return Ok(Vec::new());
+    };
+    Ok(items
+        .iter()
+        .filter_map(|item| {
+            Some(GitLfsLock {
+                id: item.get("id")?.as_str()?.to_string(),
+                path: item.get("path")?.as_str()?.to_string(),
+                owner: item
+                    .get("owner")
+                    .and_then(|owner| owner.get("name"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                locked_at: item
+                    .get("locked_at")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pointers_and_lock_listing() {
+        let oid = "4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393";
+        let pointer =
+            format!("version https://git-lfs.github.com/spec/v1\noid sha256:{oid}\nsize 12345\n");
+        assert_eq!(
+            parse_lfs_pointer(pointer.as_bytes()),
+            Some(LfsPointer {
+                oid: oid.to_string(),
+                size: 12345,
+            })
+        );
+        assert_eq!(parse_lfs_pointer(b"plain text\n"), None);
+        assert_eq!(
+            parse_lfs_pointer(b"version https://git-lfs.github.com/spec/v1\nsize 3\n"),
+            None
+        );
+
+        let locks = parse_lfs_locks(
+            r#"[{"id":"7","path":"design/logo.psd","owner":{"name":"sam"},"locked_at":"2024-01-02T03:04:05Z"},{"path":"broken"}]"#,
+        )
+        .expect("locks");
+        assert_eq!(locks.len(), 1);
+        assert_eq!(locks[0].path, "design/logo.psd");
+        assert_eq!(locks[0].owner.as_deref(), Some("sam"));
+        assert!(parse_lfs_locks("").expect("empty").is_empty());
+    }
+}
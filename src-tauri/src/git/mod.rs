@@ -4,8 +4,11 @@ use std::path::{Path, PathBuf};
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use git2::{BranchType, DiffOptions, Repository, Sort, Status, StatusOptions};
 use serde_json::json;
-use tauri::State;
+use tauri::{AppHandle, State};
 
+use crate::backend::events::EventSink;
+use crate::event_sink::TauriEventSink;
+use crate::shared::git_core::{self, run_git_command_with_input, run_git_diff};
 use crate::shared::process_core::tokio_command;
 use crate::git_utils::{
     checkout_branch, commit_to_entry, diff_patch_to_string, diff_stats_for_path, image_mime_type,
@@ -13,15 +16,51 @@ use crate::git_utils::{
 };
 use crate::state::AppState;
 use crate::types::{
-    BranchInfo, GitCommitDiff, GitFileDiff, GitFileStatus, GitHubIssue, GitHubIssuesResponse,
-    GitHubPullRequest, GitHubPullRequestComment, GitHubPullRequestDiff,
-    GitHubPullRequestsResponse, GitLogResponse,
+    AppSettings, GitBlameResponse, GitBranchComparison, GitCommitDiff, GitCommitResult,
+    GitConflictsResponse, GitFileDiff, GitFileLogEntry, GitFileStatus, GitHookResult,
+    GitHubCheckLog, GitHubChecksResponse, GitHubIssueDetails, GitHubIssuesResponse,
+    GitHubPullRequest, GitHubPullRequestComment, GitHubPullRequestDiff, GitHubPullRequestReview,
+    GitHubPullRequestsResponse, GitHubRateLimit, GitHubReviewCommentDraft, GitHunkSelection,
+    GitLfsLock, GitLogEntry, GitLogQuery, GitLogResponse, GitPickResult, GitReleaseNotes,
+    GitStashEntry, GitSubmoduleInfo, GitTagInfo,
 };
 use crate::utils::{git_env_path, normalize_git_path, resolve_git_binary};
 
+mod branches;
+mod checks;
+mod conflicts;
+mod forge;
+mod github;
+mod history;
+mod hooks;
+mod lfs;
+mod patch;
+mod release;
+mod submodules;
+
+use branches::{find_remote_branch, list_branches, local_branch_for_checkout};
+use checks::{fetch_check_logs, fetch_checks, resolve_checks_target, watch_checks};
+use conflicts::{collect_conflicts, conflicted_paths, operation_for_state};
+use forge::{Forge, WorkspaceForge};
+use github::{
+    gh_create_issue_comment, gh_create_pull_request, gh_create_review_comment,
+    gh_mark_ready_for_review, gh_pull_request_refs, gh_pull_request_review_comments,
+    gh_reply_to_review_comment, gh_request_reviewers, gh_submit_review, gh_update_pull_request,
+    pull_request_fill, review_event, review_findings_to_comments, GitHubClient, PullRequestDraft,
+    PullRequestRefs, PullRequestUpdate,
+};
+use history::{blame_file, count_log, file_log, query_log};
+use hooks::{check_commit_hooks, commit_with_hooks};
+use lfs::{lfs_change, lfs_image_base64, parse_lfs_locks, tree_lfs_pointer, workdir_lfs_state};
+use patch::{build_partial_patch, is_binary_patch, parse_file_patch, DiffAnchor, PatchDirection};
+use release::{build_release_notes, list_tags};
+use submodules::{list_submodules, submodule_change};
+
 const INDEX_SKIP_WORKTREE_FLAG: u16 = 0x4000;
 const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
 const MAX_TEXT_DIFF_BYTES: usize = 2 * 1024 * 1024;
+const ISSUE_SLUG_MAX_LEN: usize = 40;
+const ISSUE_PROMPT_COMMENTS: usize = 20;
 
 fn encode_image_base64(data: &[u8]) -> Option<String> {
     if data.len() > MAX_IMAGE_BYTES {
@@ -167,6 +206,83 @@ fn action_paths_for_file(repo_root: &Path, path: &str) -> Vec<String> {
     vec![target]
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HunkAction {
+    Stage,
+    Unstage,
+    Revert,
+}
+
+fn is_untracked_path(repo_root: &Path, path: &str) -> bool {
+    Repository::open(repo_root)
+        .ok()
+        .and_then(|repo| repo.status_file(Path::new(path)).ok())
+        .map(|status| status.contains(Status::WT_NEW))
+        .unwrap_or(false)
+}
+
+async fn apply_git_hunks(
+    repo_root: &Path,
+    path: &str,
+    hunks: &[GitHunkSelection],
+    action: HunkAction,
+) -> Result<(), String> {
+    let path = normalize_git_path(path).trim().to_string();
+    if path.is_empty() {
+        return Err("Path is required.".to_string());
+    }
+    let repo_path = repo_root.to_path_buf();
+    let untracked = is_untracked_path(repo_root, &path);
+    let null_device = if cfg!(windows) { "NUL" } else { "/dev/null" };
+
+    let mut args = vec![
+        "diff",
+        "--no-color",
+        "--no-ext-diff",
+        "--src-prefix=a/",
+        "--dst-prefix=b/",
+    ];
+    match action {
+        HunkAction::Revert if untracked => {
+            return Err("Untracked files can only be reverted as a whole.".to_string());
+        }
+        HunkAction::Stage if untracked => args.extend(["--no-index", "--", null_device]),
+        HunkAction::Unstage => args.extend(["--cached", "--"]),
+        _ => args.push("--"),
+    }
+    args.push(&path);
+
+    let diff = run_git_diff(&repo_path, &args).await?;
+    let diff = String::from_utf8_lossy(&diff);
+    if is_binary_patch(&diff) {
+        return Err("Binary files can only be staged or reverted as a whole.".to_string());
+    }
+    let file_patch =
+        parse_file_patch(&diff).ok_or_else(|| "No changes found for this file.".to_string())?;
+
+    let (direction, anchor, apply_args): (_, _, &[&str]) = match action {
+        HunkAction::Stage => (
+            PatchDirection::Forward,
+            DiffAnchor::New,
+            &["apply", "--cached", "--whitespace=nowarn", "-"],
+        ),
+        HunkAction::Unstage => (
+            PatchDirection::Reverse,
+            DiffAnchor::Old,
+            &["apply", "--cached", "--reverse", "--whitespace=nowarn", "-"],
+        ),
+        HunkAction::Revert => (
+            PatchDirection::Reverse,
+            DiffAnchor::New,
+            &["apply", "--reverse", "--whitespace=nowarn", "-"],
+        ),
+    };
+    let patch = build_partial_patch(&file_patch, hunks, direction, anchor, &path)?;
+    run_git_command_with_input(&repo_path, apply_args, patch.as_bytes())
+        .await
+        .map(|_| ())
+}
+
 fn parse_upstream_ref(name: &str) -> Option<(String, String)> {
     let trimmed = name.strip_prefix("refs/remotes/").unwrap_or(name);
     let mut parts = trimmed.splitn(2, '/');
@@ -205,6 +321,32 @@ fn upstream_remote_and_branch(repo_root: &Path) -> Result<Option<(String, String
     Ok(upstream_name.and_then(parse_upstream_ref))
 }
 
+pub(crate) fn with_conflict_context(repo_root: &Path, err: String) -> String {
+    let Ok(repo) = Repository::open(repo_root) else {
+        return err;
+    };
+    let count = repo
+        .index()
+        .ok()
+        .filter(|index| index.has_conflicts())
+        .and_then(|index| index.conflicts().ok().map(|conflicts| conflicts.count()))
+        .unwrap_or(0);
+    if count == 0 {
+        return err;
+    }
+    match operation_for_state(repo.state()) {
+        Some(operation) => format!(
+            "{err}\n\n{count} file(s) have conflicts. \
+             Resolve them, then continue or abort the {operation}."
+        ),
+        // Checkouts and `git apply --3way` leave conflicts without an
+        // operation to continue.
+        None => format!(
+            "{err}\n\n{count} file(s) have conflicts. Resolve them and stage the results."
+        ),
+    }
+}
+
 async fn push_with_upstream(repo_root: &Path) -> Result<(), String> {
     let upstream = upstream_remote_and_branch(repo_root)?;
     if let Some((remote, branch)) = upstream {
@@ -390,6 +532,16 @@ fn collect_workspace_diff(repo_root: &Path) -> Result<String, String> {
 
 fn github_repo_from_path(path: &Path) -> Result<String, String> {
     let repo = Repository::open(path).map_err(|e| e.to_string())?;
+    let name = default_remote_name(&repo)?;
+    let remote = repo.find_remote(&name).map_err(|e| e.to_string())?;
+    let remote_url = remote
+        .url()
+        .ok_or("Remote has no URL configured.")?;
+    parse_github_repo(remote_url).ok_or("Remote is not a GitHub repository.".to_string())
+}
+
+/// `origin` when present, otherwise the first configured remote.
+fn default_remote_name(repo: &Repository) -> Result<String, String> {
     let remotes = repo.remotes().map_err(|e| e.to_string())?;
     let name = if remotes.iter().any(|remote| remote == Some("origin")) {
         "origin".to_string()
@@ -404,11 +556,7 @@ fn github_repo_from_path(path: &Path) -> Result<String, String> {
     if name.is_empty() {
         return Err("No git remote configured.".to_string());
     }
-    let remote = repo.find_remote(&name).map_err(|e| e.to_string())?;
-    let remote_url = remote
-        .url()
-        .ok_or("Remote has no URL configured.")?;
-    parse_github_repo(remote_url).ok_or("Remote is not a GitHub repository.".to_string())
+    Ok(name)
 }
 
 fn parse_pr_diff(diff: &str) -> Vec<GitHubPullRequestDiff> {
@@ -502,6 +650,499 @@ fn parse_pr_diff(diff: &str) -> Vec<GitHubPullRequestDiff> {
 
     entries
 }
+fn diff_trees_to_commit_diffs(
+    repo: &Repository,
+    old_tree: Option<&git2::Tree>,
+    new_tree: &git2::Tree,
+    ignore_whitespace_changes: bool,
+) -> Result<Vec<GitCommitDiff>, String> {
+    let mut options = DiffOptions::new();
+    options.ignore_whitespace_change(ignore_whitespace_changes);
+    let diff = repo
+        .diff_tree_to_tree(old_tree, Some(new_tree), Some(&mut options))
+        .map_err(|e| e.to_string())?;
+
+    let mut results = Vec::new();
+    for (index, delta) in diff.deltas().enumerate() {
+        let old_path = delta.old_file().path();
+        let new_path = delta.new_file().path();
+        let display_path = new_path.or(old_path);
+        let Some(display_path) = display_path else {
+            continue;
+        };
+        let old_path_str = old_path.map(|path| path.to_string_lossy());
+        let new_path_str = new_path.map(|path| path.to_string_lossy());
+        let display_path_str = display_path.to_string_lossy();
+        let normalized_path = normalize_git_path(&display_path_str);
+        let old_image_mime = old_path_str.as_deref().and_then(image_mime_type);
+        let new_image_mime = new_path_str.as_deref().and_then(image_mime_type);
+        let is_image = old_image_mime.is_some() || new_image_mime.is_some();
+        if let Some(change) = submodule_change(repo, &delta, false) {
+            results.push(GitCommitDiff {
+                path: normalized_path,
+                status: status_for_delta(delta.status()).to_string(),
+                diff: String::new(),
+                old_lines: None,
+                new_lines: None,
+                is_binary: false,
+                is_image: false,
+                old_image_data: None,
+                new_image_data: None,
+                old_image_mime: None,
+                new_image_mime: None,
+                submodule: Some(change),
+                lfs: None,
+            });
+            continue;
+        }
+        let is_deleted = delta.status() == git2::Delta::Deleted;
+        let is_added = delta.status() == git2::Delta::Added;
+
+        let old_pointer = if is_added {
+            None
+        } else {
+            tree_lfs_pointer(repo, old_tree, old_path)
+        };
+        let new_pointer = if is_deleted {
+            None
+        } else {
+            tree_lfs_pointer(repo, Some(new_tree), new_path)
+        };
+        if old_pointer.is_some() || new_pointer.is_some() {
+            let (old_image_data, new_image_data) = if is_image {
+                (
+                    lfs_image_base64(repo, old_pointer.as_ref()),
+                    lfs_image_base64(repo, new_pointer.as_ref()),
+                )
+            } else {
+                (None, None)
+            };
+            results.push(GitCommitDiff {
+                path: normalized_path,
+                status: status_for_delta(delta.status()).to_string(),
+                diff: String::new(),
+                old_lines: None,
+                new_lines: None,
+                is_binary: true,
+                is_image,
+                old_image_data,
+                new_image_data,
+                old_image_mime: old_image_mime.map(str::to_string),
+                new_image_mime: new_image_mime.map(str::to_string),
+                submodule: None,
+                lfs: Some(lfs_change(
+                    repo,
+                    old_pointer.as_ref(),
+                    new_pointer.as_ref(),
+                    None,
+                )),
+            });
+            continue;
+        }
+
+        let old_lines = if !is_added {
+            old_tree
+                .and_then(|tree| old_path.and_then(|path| tree.get_path(path).ok()))
+                .and_then(|entry| repo.find_blob(entry.id()).ok())
+                .and_then(blob_to_lines)
+        } else {
+            None
+        };
+
+        let new_lines = if !is_deleted {
+            new_path
+                .and_then(|path| new_tree.get_path(path).ok())
+                .and_then(|entry| repo.find_blob(entry.id()).ok())
+                .and_then(blob_to_lines)
+        } else {
+            None
+        };
+
+        if is_image {
+            let old_image_data = if !is_added && old_image_mime.is_some() {
+                old_tree
+                    .and_then(|tree| old_path.and_then(|path| tree.get_path(path).ok()))
+                    .and_then(|entry| repo.find_blob(entry.id()).ok())
+                    .and_then(blob_to_base64)
+            } else {
+                None
+            };
+
+            let new_image_data = if !is_deleted && new_image_mime.is_some() {
+                new_path
+                    .and_then(|path| new_tree.get_path(path).ok())
+                    .and_then(|entry| repo.find_blob(entry.id()).ok())
+                    .and_then(blob_to_base64)
+            } else {
+                None
+            };
+
+            results.push(GitCommitDiff {
+                path: normalized_path,
+                status: status_for_delta(delta.status()).to_string(),
+                diff: String::new(),
+                old_lines: None,
+                new_lines: None,
+                is_binary: true,
+                is_image: true,
+                old_image_data,
+                new_image_data,
+                old_image_mime: old_image_mime.map(str::to_string),
+                new_image_mime: new_image_mime.map(str::to_string),
+                submodule: None,
+                lfs: None,
+            });
+            continue;
+        }
+
+        let patch = match git2::Patch::from_diff(&diff, index) {
+            Ok(patch) => patch,
+            Err(_) => continue,
+        };
+        let Some(mut patch) = patch else {
+            continue;
+        };
+        let content = match diff_patch_to_string(&mut patch) {
+            Ok(content) => content,
+            Err(_) => continue,
+        };
+        if content.trim().is_empty() {
+            continue;
+        }
+        results.push(GitCommitDiff {
+            path: normalized_path,
+            status: status_for_delta(delta.status()).to_string(),
+            diff: content,
+            old_lines,
+            new_lines,
+            is_binary: false,
+            is_image: false,
+            old_image_data: None,
+            new_image_data: None,
+            old_image_mime: None,
+            new_image_mime: None,
+            submodule: None,
+            lfs: None,
+        });
+    }
+
+    Ok(results)
+}
+
+fn stash_ref(index: usize) -> String {
+    format!("stash@{{{index}}}")
+}
+
+fn stash_branch_from_message(message: &str) -> Option<String> {
+    let rest = message
+        .strip_prefix("WIP on ")
+        .or_else(|| message.strip_prefix("On "))?;
+    let (branch, _) = rest.split_once(':')?;
+    let branch = branch.trim();
+    if branch.is_empty() || branch == "(no branch)" {
+        return None;
+    }
+    Some(branch.to_string())
+}
+
+fn collect_git_stashes(repo: &mut Repository) -> Result<Vec<GitStashEntry>, String> {
+    let mut raw = Vec::new();
+    repo.stash_foreach(|index, message, oid| {
+        raw.push((index, message.to_string(), *oid));
+        true
+    })
+    .map_err(|e| e.to_string())?;
+
+    let mut stashes = Vec::with_capacity(raw.len());
+    for (index, message, oid) in raw {
+        let timestamp = repo
+            .find_commit(oid)
+            .map(|commit| commit.time().seconds())
+            .unwrap_or(0);
+        stashes.push(GitStashEntry {
+            index,
+            sha: oid.to_string(),
+            branch: stash_branch_from_message(&message),
+            message,
+            timestamp,
+        });
+    }
+    Ok(stashes)
+}
+
+fn stash_commit_diffs(
+    repo: &Repository,
+    index: usize,
+    ignore_whitespace_changes: bool,
+) -> Result<Vec<GitCommitDiff>, String> {
+    let stash = repo
+        .revparse_single(&stash_ref(index))
+        .and_then(|object| object.peel_to_commit())
+        .map_err(|_| format!("Stash {index} not found."))?;
+    let stash_tree = stash.tree().map_err(|e| e.to_string())?;
+    let base_tree = stash.parent(0).ok().and_then(|parent| parent.tree().ok());
+    let mut results = diff_trees_to_commit_diffs(
+        repo,
+        base_tree.as_ref(),
+        &stash_tree,
+        ignore_whitespace_changes,
+    )?;
+    // Stashes made with --include-untracked keep new files in a third parent.
+    if let Some(untracked_tree) = stash.parent(2).ok().and_then(|parent| parent.tree().ok()) {
+        results.extend(diff_trees_to_commit_diffs(
+            repo,
+            None,
+            &untracked_tree,
+            ignore_whitespace_changes,
+        )?);
+    }
+    Ok(results)
+}
+
+fn resolve_commit<'repo>(
+    repo: &'repo Repository,
+    rev: &str,
+) -> Result<git2::Commit<'repo>, String> {
+    let rev = rev.trim();
+    if rev.is_empty() {
+        return Err("Revision is required.".to_string());
+    }
+    repo.revparse_single(rev)
+        .and_then(|object| object.peel_to_commit())
+        .map_err(|_| format!("Unknown revision: {rev}"))
+}
+
+fn log_entries_between(
+    repo: &Repository,
+    include: git2::Oid,
+    exclude: git2::Oid,
+    limit: usize,
+) -> Result<Vec<GitLogEntry>, String> {
+    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+    revwalk.push(include).map_err(|e| e.to_string())?;
+    revwalk.hide(exclude).map_err(|e| e.to_string())?;
+    revwalk.set_sorting(Sort::TIME).map_err(|e| e.to_string())?;
+    let mut entries = Vec::new();
+    for oid_result in revwalk.take(limit) {
+        let oid = oid_result.map_err(|e| e.to_string())?;
+        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+        entries.push(commit_to_entry(commit));
+    }
+    Ok(entries)
+}
+
+fn range_file_diffs(
+    repo: &Repository,
+    base: &str,
+    head: &str,
+    three_dot: bool,
+    ignore_whitespace_changes: bool,
+) -> Result<Vec<GitFileDiff>, String> {
+    let head_commit = resolve_commit(repo, head)?;
+    let base_commit = resolve_commit(repo, base)?;
+    // `base...head` compares head against the point where it forked from base.
+    let base_tree = if three_dot {
+        let merge_base = repo
+            .merge_base(base_commit.id(), head_commit.id())
+            .map_err(|_| format!("{base} and {head} have no common ancestor."))?;
+        repo.find_commit(merge_base)
+            .and_then(|commit| commit.tree())
+            .map_err(|e| e.to_string())?
+    } else {
+        base_commit.tree().map_err(|e| e.to_string())?
+    };
+    let head_tree = head_commit.tree().map_err(|e| e.to_string())?;
+    let diffs = diff_trees_to_commit_diffs(
+        repo,
+        Some(&base_tree),
+        &head_tree,
+        ignore_whitespace_changes,
+    )?;
+    Ok(diffs
+        .into_iter()
+        .map(|diff| GitFileDiff {
+            path: diff.path,
+            diff: diff.diff,
+            old_lines: diff.old_lines,
+            new_lines: diff.new_lines,
+            is_binary: diff.is_binary,
+            is_image: diff.is_image,
+            old_image_data: diff.old_image_data,
+            new_image_data: diff.new_image_data,
+            old_image_mime: diff.old_image_mime,
+            new_image_mime: diff.new_image_mime,
+            submodule: diff.submodule,
+            lfs: diff.lfs,
+        })
+        .collect())
+}
+
+fn compare_branches(
+    repo: &Repository,
+    base: &str,
+    head: &str,
+    limit: usize,
+) -> Result<GitBranchComparison, String> {
+    let base_oid = resolve_commit(repo, base)?.id();
+    let head_oid = resolve_commit(repo, head)?.id();
+    let (ahead, behind) = repo
+        .graph_ahead_behind(head_oid, base_oid)
+        .map_err(|e| e.to_string())?;
+    Ok(GitBranchComparison {
+        base: base.trim().to_string(),
+        head: head.trim().to_string(),
+        merge_base: repo
+            .merge_base(base_oid, head_oid)
+            .ok()
+            .map(|oid| oid.to_string()),
+        ahead,
+        behind,
+        ahead_entries: log_entries_between(repo, head_oid, base_oid, limit)?,
+        behind_entries: log_entries_between(repo, base_oid, head_oid, limit)?,
+    })
+}
+
+fn build_squash_message(entries_oldest_first: &[GitLogEntry]) -> String {
+    let mut summaries = entries_oldest_first
+        .iter()
+        .map(|entry| entry.summary.trim())
+        .filter(|summary| !summary.is_empty());
+    let Some(title) = summaries.next() else {
+        return "Squashed commits".to_string();
+    };
+    let rest: Vec<String> = summaries.map(|summary| format!("- {summary}")).collect();
+    if rest.is_empty() {
+        return title.to_string();
+    }
+    format!("{title}\n\n{}", rest.join("\n"))
+}
+
+/// Folds every commit since the fork point with `base` into a single commit.
+/// Refuses to run with staged changes so they are not folded in, and
+/// restores the old HEAD if the commit fails.
+async fn squash_commits_since(
+    repo_root: &Path,
+    base: &str,
+    message: Option<String>,
+) -> Result<(), String> {
+    let (head_oid, fork_oid, default_message) = {
+        let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+        let head_oid = resolve_commit(&repo, "HEAD")?.id();
+        let base_oid = resolve_commit(&repo, base)?.id();
+        let fork_oid = repo
+            .merge_base(head_oid, base_oid)
+            .map_err(|_| format!("HEAD and {base} have no common ancestor."))?;
+        if fork_oid == head_oid {
+            return Err(format!("No commits to squash since {base}."));
+        }
+        let head_tree = repo
+            .find_commit(head_oid)
+            .and_then(|commit| commit.tree())
+            .map_err(|e| e.to_string())?;
+        let staged = repo
+            .diff_tree_to_index(Some(&head_tree), None, None)
+            .map_err(|e| e.to_string())?;
+        if staged.deltas().next().is_some() {
+            return Err("Commit or unstage your staged changes before squashing.".to_string());
+        }
+        let mut entries = log_entries_between(&repo, head_oid, fork_oid, usize::MAX)?;
+        entries.reverse();
+        (head_oid, fork_oid, build_squash_message(&entries))
+    };
+    let message = message
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or(default_message);
+
+    let fork = fork_oid.to_string();
+    run_git_command(repo_root, &["reset", "--soft", &fork]).await?;
+    if let Err(err) = run_git_command(repo_root, &["commit", "-m", &message]).await {
+        let head = head_oid.to_string();
+        let _ = run_git_command(repo_root, &["reset", "--soft", &head]).await;
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Runs a cherry-pick, revert or merge, turning a conflicted stop into a
+/// result the UI can act on instead of an error.
+async fn run_pick_command(repo_root: &Path, args: &[&str]) -> Result<GitPickResult, String> {
+    let outcome = run_git_command(repo_root, args).await;
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let head_sha = repo
+        .head()
+        .ok()
+        .and_then(|head| head.target())
+        .map(|oid| oid.to_string());
+    match outcome {
+        Ok(()) => Ok(GitPickResult {
+            success: true,
+            head_sha,
+            conflicted_files: Vec::new(),
+            operation: None,
+            message: None,
+        }),
+        Err(err) => {
+            let conflicted_files = conflicted_paths(&repo);
+            if conflicted_files.is_empty() {
+                return Err(err);
+            }
+            Ok(GitPickResult {
+                success: false,
+                head_sha,
+                conflicted_files,
+                operation: operation_for_state(repo.state()).map(str::to_string),
+                message: Some(err),
+            })
+        }
+    }
+}
+
+fn same_git_storage(left: &Path, right: &Path) -> bool {
+    let common_dir = |path: &Path| {
+        Repository::open(path)
+            .ok()
+            .and_then(|repo| repo.commondir().canonicalize().ok())
+    };
+    match (common_dir(left), common_dir(right)) {
+        (Some(left), Some(right)) => left == right,
+        _ => false,
+    }
+}
+
+/// Makes commits from another checkout of the same project available locally.
+/// Worktrees share an object store, so only separate clones need a fetch.
+async fn ensure_commits_available(
+    repo_root: &Path,
+    source_root: Option<&Path>,
+    shas: &[String],
+) -> Result<(), String> {
+    let missing: Vec<String> = {
+        let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+        shas.iter()
+            .filter(|sha| resolve_commit(&repo, sha).is_err())
+            .cloned()
+            .collect()
+    };
+    if missing.is_empty() {
+        return Ok(());
+    }
+    // Missing entries go to `git fetch`, so only plain object ids are allowed.
+    if let Some(invalid) = missing.iter().find(|sha| {
+        !(7..=40).contains(&sha.len()) || !sha.chars().all(|ch| ch.is_ascii_hexdigit())
+    }) {
+        return Err(format!("Unknown revision: {invalid}"));
+    }
+    let Some(source_root) = source_root.filter(|source| !same_git_storage(repo_root, source))
+    else {
+        return Err(format!("Unknown revision: {}", missing.join(", ")));
+    };
+    let source = source_root.to_string_lossy().to_string();
+    let mut args = vec!["fetch", "--no-tags", "--quiet", "--", source.as_str()];
+    args.extend(missing.iter().map(String::as_str));
+    run_git_command(repo_root, &args).await
+}
+
 #[tauri::command]
 pub(crate) async fn get_git_status(
     workspace_id: String,
@@ -541,6 +1182,7 @@ pub(crate) async fn get_git_status(
     let mut files = Vec::new();
     let mut staged_files = Vec::new();
     let mut unstaged_files = Vec::new();
+    let mut conflicted_files = Vec::new();
     let mut total_additions = 0i64;
     let mut total_deletions = 0i64;
     for entry in statuses.iter() {
@@ -557,6 +1199,13 @@ pub(crate) async fn get_git_status(
         }
         let status = entry.status();
         let normalized_path = normalize_git_path(path);
+        // Conflicted files also stay in the unstaged list, which is where the
+        // diff panel shows them.
+        let conflicted = status.contains(Status::CONFLICTED);
+        if conflicted {
+            conflicted_files.push(normalized_path.clone());
+        }
+        let workdir_status = status_for_workdir(status).or(conflicted.then_some("M"));
         let include_index = status.intersects(
             Status::INDEX_NEW
                 | Status::INDEX_MODIFIED
@@ -564,13 +1213,14 @@ pub(crate) async fn get_git_status(
                 | Status::INDEX_RENAMED
                 | Status::INDEX_TYPECHANGE,
         );
-        let include_workdir = status.intersects(
-            Status::WT_NEW
-                | Status::WT_MODIFIED
-                | Status::WT_DELETED
-                | Status::WT_RENAMED
-                | Status::WT_TYPECHANGE,
-        );
+        let include_workdir = conflicted
+            || status.intersects(
+                Status::WT_NEW
+                    | Status::WT_MODIFIED
+                    | Status::WT_DELETED
+                    | Status::WT_RENAMED
+                    | Status::WT_TYPECHANGE,
+            );
         let mut combined_additions = 0i64;
         let mut combined_deletions = 0i64;
 
@@ -596,7 +1246,7 @@ pub(crate) async fn get_git_status(
             let (additions, deletions) =
                 diff_stats_for_path(&repo, head_tree.as_ref(), path, false, true)
                     .unwrap_or((0, 0));
-            if let Some(status_str) = status_for_workdir(status) {
+            if let Some(status_str) = workdir_status {
                 unstaged_files.push(GitFileStatus {
                     path: normalized_path.clone(),
                     status: status_str.to_string(),
@@ -611,7 +1261,7 @@ pub(crate) async fn get_git_status(
         }
 
         if include_index || include_workdir {
-            let status_str = status_for_workdir(status)
+            let status_str = workdir_status
                 .or_else(|| status_for_index(status))
                 .unwrap_or("--");
             files.push(GitFileStatus {
@@ -628,6 +1278,11 @@ pub(crate) async fn get_git_status(
         "files": files,
         "stagedFiles": staged_files,
         "unstagedFiles": unstaged_files,
+        "conflictedFiles": conflicted_files,
+        "operation": operation_for_state(repo.state()),
+        // Submodule dirt is reported by `get_git_diffs`; scanning it here
+        // would multiply the cost of every status poll.
+        "submodules": list_submodules(&repo, false).unwrap_or_default(),
         "totalAdditions": total_additions,
         "totalDeletions": total_deletions,
     }))
@@ -738,20 +1393,96 @@ pub(crate) async fn revert_git_all(
     run_git_command(&repo_root, &["clean", "-f", "-d"]).await
 }
 
+#[tauri::command]
+pub(crate) async fn stage_git_hunks(
+    workspace_id: String,
+    path: String,
+    hunks: Vec<GitHunkSelection>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+
+    let repo_root = resolve_git_root(&entry)?;
+    apply_git_hunks(&repo_root, &path, &hunks, HunkAction::Stage).await
+}
+
+#[tauri::command]
+pub(crate) async fn unstage_git_hunks(
+    workspace_id: String,
+    path: String,
+    hunks: Vec<GitHunkSelection>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+
+    let repo_root = resolve_git_root(&entry)?;
+    apply_git_hunks(&repo_root, &path, &hunks, HunkAction::Unstage).await
+}
+
+#[tauri::command]
+pub(crate) async fn revert_git_hunks(
+    workspace_id: String,
+    path: String,
+    hunks: Vec<GitHunkSelection>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+
+    let repo_root = resolve_git_root(&entry)?;
+    apply_git_hunks(&repo_root, &path, &hunks, HunkAction::Revert).await
+}
+
 #[tauri::command]
 pub(crate) async fn commit_git(
     workspace_id: String,
     message: String,
+    no_verify: Option<bool>,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<GitCommitResult, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    commit_with_hooks(&repo_root, &message, !no_verify.unwrap_or(false)).await
+}
+
+#[tauri::command]
+pub(crate) async fn check_git_commit_hooks(
+    workspace_id: String,
+    message: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitHookResult>, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
 
     let repo_root = resolve_git_root(&entry)?;
-    run_git_command(&repo_root, &["commit", "-m", &message]).await
+    check_commit_hooks(&repo_root, message.as_deref()).await
 }
 
 #[tauri::command]
@@ -781,7 +1512,9 @@ pub(crate) async fn pull_git(
         .clone();
 
     let repo_root = resolve_git_root(&entry)?;
-    pull_with_default_strategy(&repo_root).await
+    pull_with_default_strategy(&repo_root)
+        .await
+        .map_err(|err| with_conflict_context(&repo_root, err))
 }
 
 #[tauri::command]
@@ -812,7 +1545,9 @@ pub(crate) async fn sync_git(
 
     let repo_root = resolve_git_root(&entry)?;
     // Pull first, then push (like VSCode sync)
-    pull_with_default_strategy(&repo_root).await?;
+    pull_with_default_strategy(&repo_root)
+        .await
+        .map_err(|err| with_conflict_context(&repo_root, err))?;
     push_with_upstream(&repo_root).await
 }
 
@@ -904,9 +1639,70 @@ pub(crate) async fn get_git_diffs(
             let old_image_mime = old_path_str.as_deref().and_then(image_mime_type);
             let new_image_mime = new_path_str.as_deref().and_then(image_mime_type);
             let is_image = old_image_mime.is_some() || new_image_mime.is_some();
+            if let Some(change) = submodule_change(&repo, &delta, true) {
+                results.push(GitFileDiff {
+                    path: normalized_path,
+                    diff: String::new(),
+                    old_lines: None,
+                    new_lines: None,
+                    is_binary: false,
+                    is_image: false,
+                    old_image_data: None,
+                    new_image_data: None,
+                    old_image_mime: None,
+                    new_image_mime: None,
+                    submodule: Some(change),
+                    lfs: None,
+                });
+                continue;
+            }
             let is_deleted = delta.status() == git2::Delta::Deleted;
             let is_added = delta.status() == git2::Delta::Added;
 
+            let old_pointer = if is_added {
+                None
+            } else {
+                tree_lfs_pointer(&repo, head_tree.as_ref(), old_path)
+            };
+            let (new_pointer, new_smudged_size) = match new_path {
+                Some(path) if !is_deleted => workdir_lfs_state(&repo, &repo_root, path),
+                _ => (None, None),
+            };
+            if old_pointer.is_some() || new_pointer.is_some() || new_smudged_size.is_some() {
+                let old_image_data = if is_image {
+                    lfs_image_base64(&repo, old_pointer.as_ref())
+                } else {
+                    None
+                };
+                let new_image_data = if !is_image {
+                    None
+                } else if new_pointer.is_some() {
+                    lfs_image_base64(&repo, new_pointer.as_ref())
+                } else {
+                    new_path.and_then(|path| read_image_base64(&repo_root.join(path)))
+                };
+                results.push(GitFileDiff {
+                    path: normalized_path,
+                    diff: String::new(),
+                    old_lines: None,
+                    new_lines: None,
+                    is_binary: true,
+                    is_image,
+                    old_image_data,
+                    new_image_data,
+                    old_image_mime: old_image_mime.map(str::to_string),
+                    new_image_mime: new_image_mime.map(str::to_string),
+                    submodule: None,
+                    lfs: Some(lfs_change(
+                        &repo,
+                        old_pointer.as_ref(),
+                        new_pointer.as_ref(),
+                        new_smudged_size,
+                    )),
+                });
+                continue;
+            }
+
             let old_lines = if !is_added {
                 head_tree
                     .as_ref()
@@ -963,6 +1759,8 @@ pub(crate) async fn get_git_diffs(
                     new_image_data,
                     old_image_mime: old_image_mime.map(str::to_string),
                     new_image_mime: new_image_mime.map(str::to_string),
+                    submodule: None,
+                    lfs: None,
                 });
                 continue;
             }
@@ -992,6 +1790,8 @@ pub(crate) async fn get_git_diffs(
                 new_image_data: None,
                 old_image_mime: None,
                 new_image_mime: None,
+                submodule: None,
+                lfs: None,
             });
         }
 
@@ -1005,6 +1805,7 @@ pub(crate) async fn get_git_diffs(
 pub(crate) async fn get_git_log(
     workspace_id: String,
     limit: Option<usize>,
+    query: Option<GitLogQuery>,
     state: State<'_, AppState>,
 ) -> Result<GitLogResponse, String> {
     let workspaces = state.workspaces.lock().await;
@@ -1017,30 +1818,14 @@ pub(crate) async fn get_git_log(
     let repo_root = resolve_git_root(&entry)?;
     let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
     let max_items = limit.unwrap_or(40);
-    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
-    revwalk.push_head().map_err(|e| e.to_string())?;
-    revwalk
-        .set_sorting(Sort::TIME)
-        .map_err(|e| e.to_string())?;
-
-    let mut total = 0usize;
-    for oid_result in revwalk {
-        oid_result.map_err(|e| e.to_string())?;
-        total += 1;
-    }
-
-    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
-    revwalk.push_head().map_err(|e| e.to_string())?;
-    revwalk
-        .set_sorting(Sort::TIME)
-        .map_err(|e| e.to_string())?;
-
-    let mut entries = Vec::new();
-    for oid_result in revwalk.take(max_items) {
-        let oid = oid_result.map_err(|e| e.to_string())?;
-        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
-        entries.push(commit_to_entry(commit));
-    }
+    let query = query.unwrap_or_default();
+    let (entries, next_cursor) = query_log(&repo, &query, max_items)?;
+    // Counting walks the full history, so it is opt-in.
+    let total = if query.include_total {
+        count_log(&repo, &query)?
+    } else {
+        0
+    };
 
     let mut ahead = 0usize;
     let mut behind = 0usize;
@@ -1107,6 +1892,7 @@ pub(crate) async fn get_git_log(
         ahead_entries,
         behind_entries,
         upstream,
+        next_cursor,
     })
 }
 
@@ -1138,115 +1924,12 @@ pub(crate) async fn get_git_commit_diff(
         .ok()
         .and_then(|parent| parent.tree().ok());
 
-    let mut options = DiffOptions::new();
-    options.ignore_whitespace_change(ignore_whitespace_changes);
-    let diff = repo
-        .diff_tree_to_tree(parent_tree.as_ref(), Some(&commit_tree), Some(&mut options))
-        .map_err(|e| e.to_string())?;
-
-    let mut results = Vec::new();
-    for (index, delta) in diff.deltas().enumerate() {
-        let old_path = delta.old_file().path();
-        let new_path = delta.new_file().path();
-        let display_path = new_path.or(old_path);
-        let Some(display_path) = display_path else {
-            continue;
-        };
-        let old_path_str = old_path.map(|path| path.to_string_lossy());
-        let new_path_str = new_path.map(|path| path.to_string_lossy());
-        let display_path_str = display_path.to_string_lossy();
-        let normalized_path = normalize_git_path(&display_path_str);
-        let old_image_mime = old_path_str.as_deref().and_then(image_mime_type);
-        let new_image_mime = new_path_str.as_deref().and_then(image_mime_type);
-        let is_image = old_image_mime.is_some() || new_image_mime.is_some();
-        let is_deleted = delta.status() == git2::Delta::Deleted;
-        let is_added = delta.status() == git2::Delta::Added;
-
-        let old_lines = if !is_added {
-            parent_tree
-                .as_ref()
-                .and_then(|tree| old_path.and_then(|path| tree.get_path(path).ok()))
-                .and_then(|entry| repo.find_blob(entry.id()).ok())
-                .and_then(blob_to_lines)
-        } else {
-            None
-        };
-
-        let new_lines = if !is_deleted {
-            new_path
-                .and_then(|path| commit_tree.get_path(path).ok())
-                .and_then(|entry| repo.find_blob(entry.id()).ok())
-                .and_then(blob_to_lines)
-        } else {
-            None
-        };
-
-        if is_image {
-            let old_image_data = if !is_added && old_image_mime.is_some() {
-                parent_tree
-                    .as_ref()
-                    .and_then(|tree| old_path.and_then(|path| tree.get_path(path).ok()))
-                    .and_then(|entry| repo.find_blob(entry.id()).ok())
-                    .and_then(blob_to_base64)
-            } else {
-                None
-            };
-
-            let new_image_data = if !is_deleted && new_image_mime.is_some() {
-                new_path
-                    .and_then(|path| commit_tree.get_path(path).ok())
-                    .and_then(|entry| repo.find_blob(entry.id()).ok())
-                    .and_then(blob_to_base64)
-            } else {
-                None
-            };
-
-            results.push(GitCommitDiff {
-                path: normalized_path,
-                status: status_for_delta(delta.status()).to_string(),
-                diff: String::new(),
-                old_lines: None,
-                new_lines: None,
-                is_binary: true,
-                is_image: true,
-                old_image_data,
-                new_image_data,
-                old_image_mime: old_image_mime.map(str::to_string),
-                new_image_mime: new_image_mime.map(str::to_string),
-            });
-            continue;
-        }
-
-        let patch = match git2::Patch::from_diff(&diff, index) {
-            Ok(patch) => patch,
-            Err(_) => continue,
-        };
-        let Some(mut patch) = patch else {
-            continue;
-        };
-        let content = match diff_patch_to_string(&mut patch) {
-            Ok(content) => content,
-            Err(_) => continue,
-        };
-        if content.trim().is_empty() {
-            continue;
-        }
-        results.push(GitCommitDiff {
-            path: normalized_path,
-            status: status_for_delta(delta.status()).to_string(),
-            diff: content,
-            old_lines,
-            new_lines,
-            is_binary: false,
-            is_image: false,
-            old_image_data: None,
-            new_image_data: None,
-            old_image_mime: None,
-            new_image_mime: None,
-        });
-    }
-
-    Ok(results)
+    diff_trees_to_commit_diffs(
+        &repo,
+        parent_tree.as_ref(),
+        &commit_tree,
+        ignore_whitespace_changes,
+    )
 }
 
 #[tauri::command]
@@ -1280,74 +1963,50 @@ pub(crate) async fn get_git_remote(
     Ok(remote.url().map(|url| url.to_string()))
 }
 
-#[tauri::command]
-pub(crate) async fn get_github_issues(
-    workspace_id: String,
-    state: State<'_, AppState>,
-) -> Result<GitHubIssuesResponse, String> {
+async fn github_client(state: &State<'_, AppState>) -> Result<GitHubClient, String> {
+    let settings = state.app_settings.lock().await;
+    GitHubClient::from_settings(&settings)
+}
+
+/// Issue and pull request views work with any forge; the remaining GitHub
+/// commands still require a GitHub remote.
+async fn workspace_forge(
+    state: &State<'_, AppState>,
+    workspace_id: &str,
+) -> Result<WorkspaceForge, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
-        .get(&workspace_id)
+        .get(workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
 
     let repo_root = resolve_git_root(&entry)?;
-    let repo_name = github_repo_from_path(&repo_root)?;
+    let settings = state.app_settings.lock().await;
+    WorkspaceForge::for_repo(&repo_root, &settings)
+}
 
-    let output = tokio_command("gh")
-        .args([
-            "issue",
-            "list",
-            "--repo",
-            &repo_name,
-            "--limit",
-            "50",
-            "--json",
-            "number,title,url,updatedAt",
-        ])
-        .current_dir(&repo_root)
-        .output()
+#[tauri::command]
+pub(crate) async fn get_github_issues(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<GitHubIssuesResponse, String> {
+    workspace_forge(&state, &workspace_id)
+        .await?
+        .list_issues()
         .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let detail = if stderr.trim().is_empty() {
-            stdout.trim()
-        } else {
-            stderr.trim()
-        };
-        if detail.is_empty() {
-            return Err("GitHub CLI command failed.".to_string());
-        }
-        return Err(detail.to_string());
-    }
-
-    let issues: Vec<GitHubIssue> =
-        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
-
-    let search_query = format!("repo:{repo_name} is:issue is:open");
-    let search_query = search_query.replace(' ', "+");
-    let total = match tokio_command("gh")
-        .args([
-            "api",
-            &format!("/search/issues?q={search_query}"),
-            "--jq",
-            ".total_count",
-        ])
-        .current_dir(&repo_root)
-        .output()
+#[tauri::command]
+pub(crate) async fn get_github_issue(
+    workspace_id: String,
+    issue_number: u64,
+    state: State<'_, AppState>,
+) -> Result<GitHubIssueDetails, String> {
+    workspace_forge(&state, &workspace_id)
+        .await?
+        .issue(issue_number)
         .await
-    {
-        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
-            .trim()
-            .parse::<usize>()
-            .unwrap_or(issues.len()),
-        _ => issues.len(),
-    };
-
-    Ok(GitHubIssuesResponse { total, issues })
 }
 
 #[tauri::command]
@@ -1355,243 +2014,1749 @@ pub(crate) async fn get_github_pull_requests(
     workspace_id: String,
     state: State<'_, AppState>,
 ) -> Result<GitHubPullRequestsResponse, String> {
-    let workspaces = state.workspaces.lock().await;
-    let entry = workspaces
-        .get(&workspace_id)
-        .ok_or("workspace not found")?
-        .clone();
-
+    workspace_forge(&state, &workspace_id)
+        .await?
+        .list_pull_requests()
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn get_github_pull_request_diff(
+    workspace_id: String,
+    pr_number: u64,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitHubPullRequestDiff>, String> {
+    let diff_text = workspace_forge(&state, &workspace_id)
+        .await?
+        .pull_request_diff(pr_number)
+        .await?;
+    Ok(parse_pr_diff(&diff_text))
+}
+
+#[tauri::command]
+pub(crate) async fn get_github_pull_request_comments(
+    workspace_id: String,
+    pr_number: u64,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitHubPullRequestComment>, String> {
+    workspace_forge(&state, &workspace_id)
+        .await?
+        .pull_request_comments(pr_number)
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn get_github_rate_limit(
+    state: State<'_, AppState>,
+) -> Result<GitHubRateLimit, String> {
+    github_client(&state).await?.rate_limit().await
+}
+
+async fn github_workspace(
+    state: &State<'_, AppState>,
+    workspace_id: &str,
+) -> Result<(PathBuf, String), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
     let repo_root = resolve_git_root(&entry)?;
     let repo_name = github_repo_from_path(&repo_root)?;
+    Ok((repo_root, repo_name))
+}
+
+#[tauri::command]
+pub(crate) async fn create_github_pull_request(
+    workspace_id: String,
+    title: Option<String>,
+    body: Option<String>,
+    base: Option<String>,
+    draft: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<GitHubPullRequest, String> {
+    let (repo_root, repo_name) = github_workspace(&state, &workspace_id).await?;
+    let client = github_client(&state).await?;
+
+    let (branch, remote, remote_default, needs_push) = {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        let head = repo.head().map_err(|e| e.to_string())?;
+        if !head.is_branch() {
+            return Err("Check out a branch before opening a pull request.".to_string());
+        }
+        let branch = head
+            .shorthand()
+            .ok_or("Branch name is not valid UTF-8.")?
+            .to_string();
+        let remote = default_remote_name(&repo)?;
+        // `origin/HEAD` names the default branch once it has been fetched.
+        let remote_prefix = format!("refs/remotes/{remote}/");
+        let remote_default = repo
+            .find_reference(&format!("{remote_prefix}HEAD"))
+            .ok()
+            .and_then(|reference| {
+                reference
+                    .symbolic_target()
+                    .and_then(|target| target.strip_prefix(&remote_prefix))
+                    .map(str::to_string)
+            });
+        // None means the branch has no usable upstream and must be published.
+        let needs_push = repo
+            .find_branch(&branch, BranchType::Local)
+            .ok()
+            .and_then(|local| {
+                let upstream = local.upstream().ok()?;
+                let (ahead, _) = repo
+                    .graph_ahead_behind(local.get().target()?, upstream.get().target()?)
+                    .ok()?;
+                Some(ahead > 0)
+            });
+        (branch, remote, remote_default, needs_push)
+    };
+
+    let base = match base
+        .map(|base| base.trim().to_string())
+        .filter(|base| !base.is_empty())
+        .or(remote_default)
+    {
+        Some(base) => base,
+        None => client.default_branch(&repo_name).await?,
+    };
+    if base == branch {
+        return Err(format!(
+            "{branch} is the base branch; open the pull request from another branch."
+        ));
+    }
+
+    match needs_push {
+        Some(false) => {}
+        Some(true) => push_with_upstream(&repo_root).await?,
+        None => {
+            let refspec = format!("HEAD:{branch}");
+            run_git_command(
+                &repo_root,
+                &["push", "--set-upstream", remote.as_str(), refspec.as_str()],
+            )
+            .await?;
+        }
+    }
+
+    let title = title
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty());
+    let (title, body) = match (title, body) {
+        (Some(title), Some(body)) => (title, body),
+        (title, body) => {
+            let (fill_title, fill_body) = {
+                let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+                let base_oid = [
+                    format!("refs/remotes/{remote}/{base}"),
+                    format!("refs/heads/{base}"),
+                ]
+                .iter()
+                .find_map(|name| repo.refname_to_id(name).ok());
+                pull_request_fill(&repo, base_oid, &branch)?
+            };
+            (title.unwrap_or(fill_title), body.unwrap_or(fill_body))
+        }
+    };
+    let issue_number = state
+        .workspaces
+        .lock()
+        .await
+        .get(&workspace_id)
+        .and_then(|entry| entry.settings.issue.as_ref().map(|issue| issue.number));
+    let body = with_issue_reference(body, issue_number);
+
+    let draft = PullRequestDraft {
+        title,
+        body,
+        head: branch,
+        base,
+        draft: draft.unwrap_or(false),
+    };
+    match client.create_pull_request(&repo_name, &draft).await {
+        Ok(pull_request) => Ok(pull_request),
+        Err(api_error) if !client.write_falls_back_to_gh(&api_error) => Err(api_error),
+        Err(api_error) => gh_create_pull_request(&repo_root, &repo_name, &draft)
+            .await
+            .map_err(|_| api_error),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn update_github_pull_request(
+    workspace_id: String,
+    pr_number: u64,
+    title: Option<String>,
+    body: Option<String>,
+    base: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<GitHubPullRequest, String> {
+    let (repo_root, repo_name) = github_workspace(&state, &workspace_id).await?;
+    let client = github_client(&state).await?;
+    let update = PullRequestUpdate {
+        title: title.filter(|title| !title.trim().is_empty()),
+        body,
+        base: base.filter(|base| !base.trim().is_empty()),
+    };
+    match client
+        .update_pull_request(&repo_name, pr_number, &update)
+        .await
+    {
+        Ok(pull_request) => Ok(pull_request),
+        Err(api_error) => gh_update_pull_request(&repo_root, &repo_name, pr_number, &update)
+            .await
+            .map_err(|_| api_error),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn mark_github_pull_request_ready(
+    workspace_id: String,
+    pr_number: u64,
+    state: State<'_, AppState>,
+) -> Result<GitHubPullRequest, String> {
+    let (repo_root, repo_name) = github_workspace(&state, &workspace_id).await?;
+    let client = github_client(&state).await?;
+    match client.mark_ready_for_review(&repo_name, pr_number).await {
+        Ok(pull_request) => Ok(pull_request),
+        Err(api_error) => gh_mark_ready_for_review(&repo_root, &repo_name, pr_number)
+            .await
+            .map_err(|_| api_error),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn request_github_pull_request_reviewers(
+    workspace_id: String,
+    pr_number: u64,
+    reviewers: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<GitHubPullRequest, String> {
+    let (repo_root, repo_name) = github_workspace(&state, &workspace_id).await?;
+    let client = github_client(&state).await?;
+    match client
+        .request_reviewers(&repo_name, pr_number, &reviewers)
+        .await
+    {
+        Ok(pull_request) => Ok(pull_request),
+        Err(api_error) => gh_request_reviewers(&repo_root, &repo_name, pr_number, &reviewers)
+            .await
+            .map_err(|_| api_error),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn get_github_pull_request_review_comments(
+    workspace_id: String,
+    pr_number: u64,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitHubPullRequestComment>, String> {
+    let (repo_root, repo_name) = github_workspace(&state, &workspace_id).await?;
+    let client = github_client(&state).await?;
+    match client
+        .pull_request_review_comments(&repo_name, pr_number)
+        .await
+    {
+        Ok(comments) => Ok(comments),
+        Err(api_error) => gh_pull_request_review_comments(&repo_root, &repo_name, pr_number)
+            .await
+            .map_err(|_| api_error),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn post_github_pull_request_comment(
+    workspace_id: String,
+    pr_number: u64,
+    body: String,
+    state: State<'_, AppState>,
+) -> Result<GitHubPullRequestComment, String> {
+    if body.trim().is_empty() {
+        return Err("Comment body is empty.".to_string());
+    }
+    let (repo_root, repo_name) = github_workspace(&state, &workspace_id).await?;
+    let client = github_client(&state).await?;
+    match client
+        .create_issue_comment(&repo_name, pr_number, &body)
+        .await
+    {
+        Ok(comment) => Ok(comment),
+        Err(api_error) if !client.write_falls_back_to_gh(&api_error) => Err(api_error),
+        Err(api_error) => gh_create_issue_comment(&repo_root, &repo_name, pr_number, &body)
+            .await
+            .map_err(|_| api_error),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn post_github_review_comment(
+    workspace_id: String,
+    pr_number: u64,
+    comment: GitHubReviewCommentDraft,
+    state: State<'_, AppState>,
+) -> Result<GitHubPullRequestComment, String> {
+    if comment.body.trim().is_empty() {
+        return Err("Comment body is empty.".to_string());
+    }
+    let (repo_root, repo_name) = github_workspace(&state, &workspace_id).await?;
+    let client = github_client(&state).await?;
+    match client
+        .create_review_comment(&repo_name, pr_number, &comment)
+        .await
+    {
+        Ok(comment) => Ok(comment),
+        Err(api_error) if !client.write_falls_back_to_gh(&api_error) => Err(api_error),
+        Err(api_error) => gh_create_review_comment(&repo_root, &repo_name, pr_number, &comment)
+            .await
+            .map_err(|_| api_error),
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn reply_github_review_comment(
+    workspace_id: String,
+    pr_number: u64,
+    comment_id: u64,
+    body: String,
+    state: State<'_, AppState>,
+) -> Result<GitHubPullRequestComment, String> {
+    if body.trim().is_empty() {
+        return Err("Reply body is empty.".to_string());
+    }
+    let (repo_root, repo_name) = github_workspace(&state, &workspace_id).await?;
+    let client = github_client(&state).await?;
+    match client
+        .reply_to_review_comment(&repo_name, pr_number, comment_id, &body)
+        .await
+    {
+        Ok(comment) => Ok(comment),
+        Err(api_error) if !client.write_falls_back_to_gh(&api_error) => Err(api_error),
+        Err(api_error) => {
+            gh_reply_to_review_comment(&repo_root, &repo_name, pr_number, comment_id, &body)
+                .await
+                .map_err(|_| api_error)
+        }
+    }
+}
+
+/// Submits a review in one request. `review_text` is the summary of a Codex
+/// review thread; its findings are posted as inline comments next to
+/// `comments`.
+#[tauri::command]
+pub(crate) async fn submit_github_pull_request_review(
+    workspace_id: String,
+    pr_number: u64,
+    event: String,
+    body: Option<String>,
+    comments: Option<Vec<GitHubReviewCommentDraft>>,
+    review_text: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<GitHubPullRequestReview, String> {
+    let event = review_event(&event)?;
+    let body = body.unwrap_or_default();
+    let (repo_root, repo_name) = github_workspace(&state, &workspace_id).await?;
+    let mut comments = comments.unwrap_or_default();
+    if let Some(review_text) = review_text.as_deref() {
+        comments.extend(review_findings_to_comments(review_text, &repo_root));
+    }
+    if event != "APPROVE" && body.trim().is_empty() && comments.is_empty() {
+        return Err("A review needs a body or at least one comment.".to_string());
+    }
+    let client = github_client(&state).await?;
+    match client
+        .submit_review(&repo_name, pr_number, event, &body, &comments)
+        .await
+    {
+        Ok(review) => Ok(review),
+        Err(api_error) if !client.write_falls_back_to_gh(&api_error) => Err(api_error),
+        Err(api_error) => {
+            gh_submit_review(&repo_root, &repo_name, pr_number, event, &body, &comments)
+                .await
+                .map_err(|_| api_error)
+        }
+    }
+}
+
+/// `git_ref` defaults to HEAD; `pr_number` selects a pull request head.
+#[tauri::command]
+pub(crate) async fn get_github_checks(
+    workspace_id: String,
+    git_ref: Option<String>,
+    pr_number: Option<u64>,
+    state: State<'_, AppState>,
+) -> Result<GitHubChecksResponse, String> {
+    let (repo_root, repo_name) = github_workspace(&state, &workspace_id).await?;
+    let client = github_client(&state).await?;
+    let target = resolve_checks_target(
+        &client,
+        &repo_root,
+        &repo_name,
+        git_ref.as_deref(),
+        pr_number,
+    )
+    .await?;
+    fetch_checks(&client, &repo_root, &repo_name, &target).await
+}
+
+/// Log excerpts for the failing checks, or for `check_id` alone.
+#[tauri::command]
+pub(crate) async fn get_github_check_logs(
+    workspace_id: String,
+    git_ref: Option<String>,
+    pr_number: Option<u64>,
+    check_id: Option<u64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitHubCheckLog>, String> {
+    let (repo_root, repo_name) = github_workspace(&state, &workspace_id).await?;
+    let client = github_client(&state).await?;
+    let target = resolve_checks_target(
+        &client,
+        &repo_root,
+        &repo_name,
+        git_ref.as_deref(),
+        pr_number,
+    )
+    .await?;
+    fetch_check_logs(&client, &repo_root, &repo_name, &target, check_id).await
+}
+
+/// Returns the current checks and keeps polling in the background until they
+/// finish, then emits `github-checks-finished`.
+#[tauri::command]
+pub(crate) async fn watch_github_checks(
+    workspace_id: String,
+    git_ref: Option<String>,
+    pr_number: Option<u64>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitHubChecksResponse, String> {
+    let event_sink = TauriEventSink::new(app);
+    watch_github_checks_with(workspace_id, git_ref, pr_number, state, move |event| {
+        event_sink.emit_github_checks_finished(event)
+    })
+    .await
+}
+
+/// The web server has no `AppHandle`, so it passes its own emitter.
+pub(crate) async fn watch_github_checks_with<F>(
+    workspace_id: String,
+    git_ref: Option<String>,
+    pr_number: Option<u64>,
+    state: State<'_, AppState>,
+    emit: F,
+) -> Result<GitHubChecksResponse, String>
+where
+    F: Fn(crate::backend::events::GitHubChecksFinished) + Send + 'static,
+{
+    let (repo_root, repo_name) = github_workspace(&state, &workspace_id).await?;
+    let client = github_client(&state).await?;
+    let target = resolve_checks_target(
+        &client,
+        &repo_root,
+        &repo_name,
+        git_ref.as_deref(),
+        pr_number,
+    )
+    .await?;
+    let response = fetch_checks(&client, &repo_root, &repo_name, &target).await?;
+    watch_checks(client, repo_root, repo_name, target, workspace_id, emit);
+    Ok(response)
+}
+
+pub(crate) struct PullRequestBranch {
+    pub(crate) branch: String,
+    /// Remote-tracking name of the base branch, e.g. `origin/main`.
+    pub(crate) base_ref: String,
+    pub(crate) title: String,
+}
+
+/// Fetches a pull request into a local branch a worktree can check out. Same-
+/// repo pull requests use their real head branch so pushes update the pull
+/// request; forks land on `pr/<number>` from `refs/pull/<number>/head`.
+pub(crate) async fn prepare_pull_request_branch(
+    repo_root: &Path,
+    settings: &AppSettings,
+    pr_number: u64,
+) -> Result<PullRequestBranch, String> {
+    let repo_name = github_repo_from_path(repo_root)?;
+    let client = GitHubClient::from_settings(settings)?;
+    let refs = match client.pull_request_refs(&repo_name, pr_number).await {
+        Ok(refs) => refs,
+        Err(api_error) => gh_pull_request_refs(repo_root, &repo_name, pr_number)
+            .await
+            .map_err(|_| api_error)?,
+    };
+    checkout_pull_request_refs(repo_root, refs, pr_number).await
+}
+
+/// Existing local branches are fast-forwarded to the fetched head so a re-
+/// checkout or force-push never leaves a worktree on stale commits; a local
+/// branch that has diverged from the pull request is an error.
+async fn checkout_pull_request_refs(
+    repo_root: &Path,
+    refs: PullRequestRefs,
+    pr_number: u64,
+) -> Result<PullRequestBranch, String> {
+    let (branch, source) = if refs.cross_repository {
+        (
+            format!("pr/{pr_number}"),
+            format!("refs/pull/{pr_number}/head"),
+        )
+    } else {
+        (
+            refs.head_ref.clone(),
+            format!("refs/heads/{}", refs.head_ref),
+        )
+    };
+    let (remote, branch_exists) = {
+        let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+        let remote = default_remote_name(&repo)?;
+        let exists = repo.find_branch(&branch, BranchType::Local).is_ok();
+        (remote, exists)
+    };
+    let tracking = format!("{remote}/{branch}");
+    let head_refspec = format!("+{source}:refs/remotes/{tracking}");
+    let base_refspec = format!("+refs/heads/{0}:refs/remotes/{remote}/{0}", refs.base_ref);
+    run_git_command(
+        repo_root,
+        &[
+            "fetch",
+            remote.as_str(),
+            head_refspec.as_str(),
+            base_refspec.as_str(),
+        ],
+    )
+    .await?;
+    if !branch_exists {
+        // `refs/remotes/<remote>/pr/<n>` has no branch behind it on the
+        // remote, so only same-repo branches get an upstream.
+        let mut args = vec!["branch"];
+        if !refs.cross_repository {
+            args.push("--track");
+        }
+        args.extend([branch.as_str(), tracking.as_str()]);
+        run_git_command(repo_root, &args).await?;
+    } else {
+        let fast_forward = {
+            let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+            let local = resolve_commit(&repo, &format!("refs/heads/{branch}"))?.id();
+            let fetched = resolve_commit(&repo, &format!("refs/remotes/{tracking}"))?.id();
+            if local == fetched
+                || repo
+                    .graph_descendant_of(local, fetched)
+                    .map_err(|e| e.to_string())?
+            {
+                false
+            } else if repo
+                .graph_descendant_of(fetched, local)
+                .map_err(|e| e.to_string())?
+            {
+                true
+            } else {
+                return Err(format!(
+                    "Local branch {branch} has diverged from pull request #{pr_number}. Rename or delete it and try again."
+                ));
+            }
+        };
+        if fast_forward {
+            // Refuses to move a branch that is checked out in another worktree.
+            run_git_command(repo_root, &["branch", "-f", branch.as_str(), tracking.as_str()])
+                .await?;
+        }
+    }
+
+    Ok(PullRequestBranch {
+        branch,
+        base_ref: format!("{remote}/{}", refs.base_ref),
+        title: refs.title,
+    })
+}
+
+pub(crate) async fn fetch_issue(
+    repo_root: &Path,
+    settings: &AppSettings,
+    issue_number: u64,
+) -> Result<GitHubIssueDetails, String> {
+    WorkspaceForge::for_repo(repo_root, settings)?
+        .issue(issue_number)
+        .await
+}
+
+/// `issue/<number>-<title slug>`, kept short enough to read in a branch list.
+pub(crate) fn issue_branch_name(issue: &GitHubIssueDetails) -> String {
+    let mut slug = String::new();
+    for ch in issue.issue.title.to_lowercase().chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if slug.len() >= ISSUE_SLUG_MAX_LEN {
+            break;
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        format!("issue/{}", issue.issue.number)
+    } else {
+        format!("issue/{}-{slug}", issue.issue.number)
+    }
+}
+
+/// The opening message for an agent working on `issue`. Only the most recent
+/// comments are included so long threads do not crowd out the task.
+pub(crate) fn issue_run_prompt(issue: &GitHubIssueDetails) -> String {
+    let mut prompt = format!(
+        "Resolve issue #{}: {}\n{}\n",
+        issue.issue.number, issue.issue.title, issue.issue.url
+    );
+    if !issue.labels.is_empty() {
+        prompt.push_str(&format!("Labels: {}\n", issue.labels.join(", ")));
+    }
+    let body = issue.body.trim();
+    if !body.is_empty() {
+        prompt.push_str(&format!("\n{body}\n"));
+    }
+    let skipped = issue.comments.len().saturating_sub(ISSUE_PROMPT_COMMENTS);
+    if !issue.comments.is_empty() {
+        prompt.push_str("\nComments");
+        if skipped > 0 {
+            prompt.push_str(&format!(
+                " (latest {ISSUE_PROMPT_COMMENTS} of {})",
+                issue.comments.len()
+            ));
+        }
+        prompt.push_str(":\n");
+        for comment in &issue.comments[skipped..] {
+            let author = comment
+                .author
+                .as_ref()
+                .map(|author| author.login.as_str())
+                .unwrap_or("unknown");
+            prompt.push_str(&format!("\n@{author}:\n{}\n", comment.body.trim()));
+        }
+    }
+    prompt.push_str(
+        "\nInvestigate the codebase, implement a fix, and add or update tests where it makes sense. \
+         Summarize the changes when you are done.",
+    );
+    prompt
+}
+
+/// Appends `Fixes #N` unless the body already mentions the issue.
+fn with_issue_reference(body: String, issue_number: Option<u64>) -> String {
+    let Some(number) = issue_number else {
+        return body;
+    };
+    let reference = format!("#{number}");
+    let mentioned = body.match_indices(&reference).any(|(index, _)| {
+        !body[index + reference.len()..].starts_with(|ch: char| ch.is_ascii_digit())
+    });
+    if mentioned {
+        return body;
+    }
+    let body = body.trim_end();
+    if body.is_empty() {
+        format!("Fixes {reference}")
+    } else {
+        format!("{body}\n\nFixes {reference}")
+    }
+}
+
+#[tauri::command]
+pub(crate) async fn list_git_branches(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let (branches, remote_branches) = list_branches(&repo)?;
+    Ok(json!({ "branches": branches, "remoteBranches": remote_branches }))
+}
+
+#[tauri::command]
+pub(crate) async fn checkout_git_branch(
+    workspace_id: String,
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let local_name = local_branch_for_checkout(&repo, &name)?;
+    checkout_branch(&repo, &local_name)
+        .map_err(|e| with_conflict_context(&repo_root, e.to_string()))
+}
+
+#[tauri::command]
+pub(crate) async fn create_git_branch(
+    workspace_id: String,
+    name: String,
+    start_point: Option<String>,
+    checkout: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let start_point = start_point
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let target = match start_point.as_deref() {
+        Some(start_point) => resolve_commit(&repo, start_point)?,
+        None => repo
+            .head()
+            .map_err(|e| e.to_string())?
+            .peel_to_commit()
+            .map_err(|e| e.to_string())?,
+    };
+    let mut branch = repo
+        .branch(&name, &target, false)
+        .map_err(|e| e.to_string())?;
+    // Match `git branch <name> <remote>/<branch>`, which tracks the start point.
+    if let Some(start_point) = start_point.as_deref() {
+        if repo.find_branch(start_point, BranchType::Remote).is_ok() {
+            branch
+                .set_upstream(Some(start_point))
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    if checkout.unwrap_or(true) {
+        checkout_branch(&repo, &name).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn delete_git_branch(
+    workspace_id: String,
+    name: String,
+    remote: Option<bool>,
+    force: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    if remote.unwrap_or(false) {
+        let remote_ref = {
+            let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+            let branch = find_remote_branch(&repo, &name)?
+                .ok_or_else(|| format!("Remote branch {name} does not exist."))?;
+            let full_name = branch
+                .name()
+                .map_err(|e| e.to_string())?
+                .unwrap_or("")
+                .to_string();
+            parse_upstream_ref(&full_name)
+                .ok_or_else(|| format!("Remote branch {name} does not exist."))?
+        };
+        let (remote_name, branch_name) = remote_ref;
+        return run_git_command(
+            &repo_root,
+            &[
+                "push",
+                remote_name.as_str(),
+                "--delete",
+                branch_name.as_str(),
+            ],
+        )
+        .await;
+    }
+    let local_name = {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        let branch = repo
+            .find_branch(name.trim(), BranchType::Local)
+            .map_err(|_| format!("Branch {name} does not exist."))?;
+        branch
+            .name()
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Branch {name} does not exist."))?
+            .to_string()
+    };
+    // The CLI refuses to drop unmerged work unless forced, which git2 does not.
+    let flag = if force.unwrap_or(false) { "-D" } else { "-d" };
+    run_git_command(&repo_root, &["branch", flag, "--", local_name.as_str()]).await
+}
+
+#[tauri::command]
+pub(crate) async fn rename_git_branch(
+    workspace_id: String,
+    name: String,
+    new_name: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        return Err("Branch name is required.".to_string());
+    }
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let mut branch = repo
+        .find_branch(&name, BranchType::Local)
+        .map_err(|e| e.to_string())?;
+    branch
+        .rename(new_name, false)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub(crate) async fn set_git_branch_upstream(
+    workspace_id: String,
+    name: Option<String>,
+    upstream: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let name = match name.filter(|value| !value.trim().is_empty()) {
+        Some(name) => name,
+        None => {
+            let head = repo.head().map_err(|e| e.to_string())?;
+            if !head.is_branch() {
+                return Err("HEAD is detached; pick a branch.".to_string());
+            }
+            head.shorthand().unwrap_or("").to_string()
+        }
+    };
+    let mut branch = repo
+        .find_branch(&name, BranchType::Local)
+        .map_err(|e| e.to_string())?;
+    let upstream = upstream.filter(|value| !value.trim().is_empty());
+    branch
+        .set_upstream(upstream.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub(crate) async fn merge_git_branch(
+    workspace_id: String,
+    name: String,
+    no_ff: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<GitPickResult, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let name = name.trim();
+    // Keep the name for the merge message, but never let it be read as an option.
+    if name.starts_with('-') {
+        return Err(format!("Unknown revision: {name}"));
+    }
+    {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        resolve_commit(&repo, name)?;
+    }
+    let mut args = vec!["merge", "--no-edit"];
+    if no_ff.unwrap_or(false) {
+        args.push("--no-ff");
+    }
+    args.push(name);
+    run_pick_command(&repo_root, &args).await
+}
+
+#[tauri::command]
+pub(crate) async fn list_git_stashes(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitStashEntry>, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let mut repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    collect_git_stashes(&mut repo)
+}
+
+#[tauri::command]
+pub(crate) async fn stash_git_push(
+    workspace_id: String,
+    message: Option<String>,
+    include_untracked: Option<bool>,
+    paths: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<Option<GitStashEntry>, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let mut repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let previous_top = collect_git_stashes(&mut repo)?
+        .into_iter()
+        .next()
+        .map(|stash| stash.sha);
+
+    let mut args = vec!["stash".to_string(), "push".to_string()];
+    if include_untracked.unwrap_or(false) {
+        args.push("--include-untracked".to_string());
+    }
+    if let Some(message) = message.as_deref().map(str::trim).filter(|m| !m.is_empty()) {
+        args.push("-m".to_string());
+        args.push(message.to_string());
+    }
+    let paths: Vec<String> = paths
+        .unwrap_or_default()
+        .iter()
+        .map(|path| normalize_git_path(path).trim().to_string())
+        .filter(|path| !path.is_empty())
+        .collect();
+    if !paths.is_empty() {
+        args.push("--".to_string());
+        args.extend(paths);
+    }
+    let args_ref: Vec<&str> = args.iter().map(String::as_str).collect();
+    run_git_command(&repo_root, &args_ref).await?;
+
+    // `git stash push` succeeds without creating a stash when nothing changed.
+    let top = collect_git_stashes(&mut repo)?.into_iter().next();
+    Ok(top.filter(|stash| Some(&stash.sha) != previous_top.as_ref()))
+}
+
+#[tauri::command]
+pub(crate) async fn stash_git_apply(
+    workspace_id: String,
+    index: usize,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    run_git_command(&repo_root, &["stash", "apply", &stash_ref(index)]).await
+}
+
+#[tauri::command]
+pub(crate) async fn stash_git_pop(
+    workspace_id: String,
+    index: usize,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    run_git_command(&repo_root, &["stash", "pop", &stash_ref(index)]).await
+}
+
+#[tauri::command]
+pub(crate) async fn stash_git_drop(
+    workspace_id: String,
+    index: usize,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    run_git_command(&repo_root, &["stash", "drop", &stash_ref(index)]).await
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_stash_diff(
+    workspace_id: String,
+    index: usize,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitCommitDiff>, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let ignore_whitespace_changes = {
+        let settings = state.app_settings.lock().await;
+        settings.git_diff_ignore_whitespace_changes
+    };
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    stash_commit_diffs(&repo, index, ignore_whitespace_changes)
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_conflicts(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<GitConflictsResponse, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    Ok(GitConflictsResponse {
+        operation: operation_for_state(repo.state()).map(str::to_string),
+        files: collect_conflicts(&repo, &repo_root)?,
+    })
+}
+
+/// `resolution` is "ours", "theirs" or "content". During a rebase git swaps the
+/// sides, so "ours" is the branch being rebased onto.
+#[tauri::command]
+pub(crate) async fn resolve_git_conflict(
+    workspace_id: String,
+    path: String,
+    resolution: String,
+    content: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let target = normalize_git_path(&path);
+    let conflict = collect_conflicts(&repo, &repo_root)?
+        .into_iter()
+        .find(|file| file.path == target)
+        .ok_or_else(|| format!("{target} is not in conflict."))?;
+
+    match resolution.as_str() {
+        "ours" | "theirs" => {
+            let deleted = if resolution == "ours" {
+                conflict.ours_deleted
+            } else {
+                conflict.theirs_deleted
+            };
+            if deleted {
+                return run_git_command(&repo_root, &["rm", "--quiet", "--", &target]).await;
+            }
+            let side = format!("--{resolution}");
+            run_git_command(&repo_root, &["checkout", &side, "--", &target]).await?;
+        }
+        "content" => {
+            let content = content.ok_or("Resolved content is required.")?;
+            fs::write(repo_root.join(&target), content)
+                .map_err(|e| format!("Failed to write {target}: {e}"))?;
+        }
+        other => return Err(format!("Unknown conflict resolution: {other}")),
+    }
+    run_git_command(&repo_root, &["add", "--", &target]).await
+}
+
+async fn run_conflict_operation(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    args: &[&str],
+) -> Result<(), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    // Keep the prepared commit message instead of waiting on an editor.
+    let mut full_args = vec!["-c", "core.editor=true"];
+    full_args.extend_from_slice(args);
+    run_git_command(&repo_root, &full_args)
+        .await
+        .map_err(|err| with_conflict_context(&repo_root, err))
+}
+
+async fn current_git_operation(
+    workspace_id: &str,
+    state: &State<'_, AppState>,
+) -> Result<&'static str, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    operation_for_state(repo.state())
+        .ok_or_else(|| "No merge, rebase, cherry-pick or revert is in progress.".to_string())
+}
+
+#[tauri::command]
+pub(crate) async fn abort_git_merge(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    run_conflict_operation(workspace_id, state, &["merge", "--abort"]).await
+}
+
+#[tauri::command]
+pub(crate) async fn continue_git_merge(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    run_conflict_operation(workspace_id, state, &["merge", "--continue"]).await
+}
+
+#[tauri::command]
+pub(crate) async fn abort_git_rebase(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    run_conflict_operation(workspace_id, state, &["rebase", "--abort"]).await
+}
+
+#[tauri::command]
+pub(crate) async fn continue_git_rebase(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    run_conflict_operation(workspace_id, state, &["rebase", "--continue"]).await
+}
+
+#[tauri::command]
+pub(crate) async fn amend_git_commit(
+    workspace_id: String,
+    message: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let message = message.as_deref().map(str::trim).unwrap_or("");
+    if message.is_empty() {
+        return run_git_command(&repo_root, &["commit", "--amend", "--no-edit"]).await;
+    }
+    run_git_command(&repo_root, &["commit", "--amend", "-m", message]).await
+}
+
+#[tauri::command]
+pub(crate) async fn fixup_git_commit(
+    workspace_id: String,
+    sha: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let target = {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        resolve_commit(&repo, &sha)?.id().to_string()
+    };
+    let fixup = format!("--fixup={target}");
+    run_git_command(&repo_root, &["commit", &fixup]).await
+}
+
+#[tauri::command]
+pub(crate) async fn autosquash_git_rebase(
+    workspace_id: String,
+    base: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let base = base.trim().to_string();
+    if base.is_empty() {
+        return Err("Base branch is required.".to_string());
+    }
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    // Pass a resolved sha so a base like `--exec=...` is never read as an option.
+    let base = {
+        let repo_root = resolve_git_root(&entry)?;
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        resolve_commit(&repo, &base)?.id().to_string()
+    };
+    // An editor of `true` accepts the autosquashed todo list as generated.
+    run_conflict_operation(
+        workspace_id,
+        state,
+        &[
+            "-c",
+            "sequence.editor=true",
+            "rebase",
+            "--interactive",
+            "--autosquash",
+            &base,
+        ],
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn squash_worktree_commits(
+    workspace_id: String,
+    message: Option<String>,
+    base: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let (entry, parent) = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?;
+        let parent = entry
+            .parent_id
+            .as_ref()
+            .and_then(|parent_id| workspaces.get(parent_id))
+            .cloned();
+        (entry, parent)
+    };
+
+    let repo_root = resolve_git_root(&entry)?;
+    let base = base
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let base = match base {
+        Some(base) => base,
+        None => {
+            let parent = parent.ok_or("A base branch is required outside worktrees.")?;
+            let parent_root = resolve_git_root(&parent)?;
+            let parent_repo = Repository::open(&parent_root).map_err(|e| e.to_string())?;
+            let head = parent_repo.head().map_err(|e| e.to_string())?;
+            head.shorthand()
+                .filter(|_| head.is_branch())
+                .map(str::to_string)
+                .ok_or("The parent workspace is not on a branch.")?
+        }
+    };
+    squash_commits_since(&repo_root, &base, message).await
+}
+
+#[tauri::command]
+pub(crate) async fn cherry_pick_git_commits(
+    workspace_id: String,
+    shas: Vec<String>,
+    source_workspace_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<GitPickResult, String> {
+    let (entry, source) = {
+        let workspaces = state.workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?;
+        let source = match source_workspace_id.as_ref() {
+            Some(source_id) => Some(
+                workspaces
+                    .get(source_id)
+                    .cloned()
+                    .ok_or("source workspace not found")?,
+            ),
+            None => None,
+        };
+        (entry, source)
+    };
+
+    let shas: Vec<String> = shas
+        .iter()
+        .map(|sha| sha.trim().to_string())
+        .filter(|sha| !sha.is_empty())
+        .collect();
+    if shas.is_empty() {
+        return Err("Select at least one commit to cherry-pick.".to_string());
+    }
+    let repo_root = resolve_git_root(&entry)?;
+    let source_root = source.as_ref().map(resolve_git_root).transpose()?;
+    ensure_commits_available(&repo_root, source_root.as_deref(), &shas).await?;
+
+    // Apply ancestors first regardless of the order the commits were selected
+    // in. Timestamps are not reliable for this: agents commit several times a
+    // second and rebased commits keep their original dates.
+    let ordered = {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        let mut oids = Vec::with_capacity(shas.len());
+        for sha in &shas {
+            let commit = resolve_commit(&repo, sha)?;
+            if commit.parent_count() > 1 {
+                return Err(format!(
+                    "{sha} is a merge commit and cannot be cherry-picked."
+                ));
+            }
+            if !oids.contains(&commit.id()) {
+                oids.push(commit.id());
+            }
+        }
+        // A commit has more selected ancestors than any of those ancestors,
+        // so sorting by that count is topological and keeps the selection
+        // order for unrelated commits.
+        let mut ranked = Vec::with_capacity(oids.len());
+        for &oid in &oids {
+            let mut ancestors = 0usize;
+            for &other in &oids {
+                if other != oid
+                    && repo
+                        .graph_descendant_of(oid, other)
+                        .map_err(|e| e.to_string())?
+                {
+                    ancestors += 1;
+                }
+            }
+            ranked.push((ancestors, oid.to_string()));
+        }
+        ranked.sort_by_key(|(ancestors, _)| *ancestors);
+        ranked.into_iter().map(|(_, sha)| sha).collect::<Vec<_>>()
+    };
+    let mut args = vec!["cherry-pick", "-x"];
+    args.extend(ordered.iter().map(String::as_str));
+    run_pick_command(&repo_root, &args).await
+}
+
+#[tauri::command]
+pub(crate) async fn revert_git_commit(
+    workspace_id: String,
+    sha: String,
+    state: State<'_, AppState>,
+) -> Result<GitPickResult, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let (target, is_merge) = {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        let commit = resolve_commit(&repo, &sha)?;
+        (commit.id().to_string(), commit.parent_count() > 1)
+    };
+    if is_merge {
+        // Revert a merge relative to the branch it was merged into.
+        return run_pick_command(&repo_root, &["revert", "--no-edit", "-m", "1", &target]).await;
+    }
+    run_pick_command(&repo_root, &["revert", "--no-edit", &target]).await
+}
+
+#[tauri::command]
+pub(crate) async fn continue_git_operation(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let operation = current_git_operation(&workspace_id, &state).await?;
+    run_conflict_operation(workspace_id, state, &[operation, "--continue"]).await
+}
+
+#[tauri::command]
+pub(crate) async fn abort_git_operation(
+    workspace_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let operation = current_git_operation(&workspace_id, &state).await?;
+    run_conflict_operation(workspace_id, state, &[operation, "--abort"]).await
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_range_diff(
+    workspace_id: String,
+    base: String,
+    head: String,
+    three_dot: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitFileDiff>, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let ignore_whitespace_changes = {
+        let settings = state.app_settings.lock().await;
+        settings.git_diff_ignore_whitespace_changes
+    };
+    tokio::task::spawn_blocking(move || {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        range_file_diffs(
+            &repo,
+            &base,
+            &head,
+            three_dot.unwrap_or(true),
+            ignore_whitespace_changes,
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_branch_comparison(
+    workspace_id: String,
+    base: String,
+    head: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<GitBranchComparison, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    compare_branches(&repo, &base, &head, limit.unwrap_or(40))
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_file_log(
+    workspace_id: String,
+    path: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GitFileLogEntry>, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
 
-    let output = tokio_command("gh")
-        .args([
-            "pr",
-            "list",
-            "--repo",
-            &repo_name,
-            "--state",
-            "open",
-            "--limit",
-            "50",
-            "--json",
-            "number,title,url,updatedAt,createdAt,body,headRefName,baseRefName,isDraft,author",
-        ])
-        .current_dir(&repo_root)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        file_log(&repo, &path, limit.unwrap_or(40))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let detail = if stderr.trim().is_empty() {
-            stdout.trim()
-        } else {
-            stderr.trim()
-        };
-        if detail.is_empty() {
-            return Err("GitHub CLI command failed.".to_string());
-        }
-        return Err(detail.to_string());
-    }
-
-    let pull_requests: Vec<GitHubPullRequest> =
-        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
-
-    let search_query = format!("repo:{repo_name} is:pr is:open");
-    let search_query = search_query.replace(' ', "+");
-    let total = match tokio_command("gh")
-        .args([
-            "api",
-            &format!("/search/issues?q={search_query}"),
-            "--jq",
-            ".total_count",
-        ])
-        .current_dir(&repo_root)
-        .output()
-        .await
-    {
-        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
-            .trim()
-            .parse::<usize>()
-            .unwrap_or(pull_requests.len()),
-        _ => pull_requests.len(),
-    };
+#[tauri::command]
+pub(crate) async fn get_git_blame(
+    workspace_id: String,
+    path: String,
+    rev: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<GitBlameResponse, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
 
-    Ok(GitHubPullRequestsResponse {
-        total,
-        pull_requests,
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        blame_file(&repo, &path, rev.as_deref())
     })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
 #[tauri::command]
-pub(crate) async fn get_github_pull_request_diff(
+pub(crate) async fn list_git_tags(
     workspace_id: String,
-    pr_number: u64,
     state: State<'_, AppState>,
-) -> Result<Vec<GitHubPullRequestDiff>, String> {
+) -> Result<Vec<GitTagInfo>, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
 
     let repo_root = resolve_git_root(&entry)?;
-    let repo_name = github_repo_from_path(&repo_root)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    list_tags(&repo)
+}
 
-    let output = tokio_command("gh")
-        .args([
-            "pr",
-            "diff",
-            &pr_number.to_string(),
-            "--repo",
-            &repo_name,
-            "--color",
-            "never",
-        ])
-        .current_dir(&repo_root)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
+#[tauri::command]
+pub(crate) async fn create_git_tag(
+    workspace_id: String,
+    name: String,
+    target: Option<String>,
+    message: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<GitTagInfo, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let detail = if stderr.trim().is_empty() {
-            stdout.trim()
-        } else {
-            stderr.trim()
-        };
-        if detail.is_empty() {
-            return Err("GitHub CLI command failed.".to_string());
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Tag name is required.".to_string());
+    }
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let commit = resolve_commit(&repo, target.as_deref().unwrap_or("HEAD"))?;
+    let message = message
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    match message.as_deref() {
+        Some(message) => {
+            let tagger = repo.signature().map_err(|e| e.to_string())?;
+            repo.tag(&name, commit.as_object(), &tagger, message, false)
+                .map_err(|e| e.to_string())?;
+        }
+        None => {
+            repo.tag_lightweight(&name, commit.as_object(), false)
+                .map_err(|e| e.to_string())?;
         }
-        return Err(detail.to_string());
     }
-
-    let diff_text = String::from_utf8_lossy(&output.stdout);
-    Ok(parse_pr_diff(&diff_text))
+    list_tags(&repo)?
+        .into_iter()
+        .find(|tag| tag.name == name)
+        .ok_or_else(|| format!("Tag {name} was not created."))
 }
 
 #[tauri::command]
-pub(crate) async fn get_github_pull_request_comments(
+pub(crate) async fn delete_git_tag(
     workspace_id: String,
-    pr_number: u64,
+    name: String,
     state: State<'_, AppState>,
-) -> Result<Vec<GitHubPullRequestComment>, String> {
+) -> Result<(), String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
 
     let repo_root = resolve_git_root(&entry)?;
-    let repo_name = github_repo_from_path(&repo_root)?;
-
-    let comments_endpoint =
-        format!("/repos/{repo_name}/issues/{pr_number}/comments?per_page=30");
-    let jq_filter = r#"[.[] | {id, body, createdAt: .created_at, url: .html_url, author: (if .user then {login: .user.login} else null end)}]"#;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    repo.tag_delete(name.trim()).map_err(|e| e.to_string())
+}
 
-    let output = tokio_command("gh")
-        .args(["api", &comments_endpoint, "--jq", jq_filter])
-        .current_dir(&repo_root)
-        .output()
-        .await
-        .map_err(|e| format!("Failed to run gh: {e}"))?;
+#[tauri::command]
+pub(crate) async fn push_git_tag(
+    workspace_id: String,
+    name: String,
+    remote: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let detail = if stderr.trim().is_empty() {
-            stdout.trim()
-        } else {
-            stderr.trim()
-        };
-        if detail.is_empty() {
-            return Err("GitHub CLI command failed.".to_string());
-        }
-        return Err(detail.to_string());
+    let repo_root = resolve_git_root(&entry)?;
+    let remote = match remote.map(|value| value.trim().to_string()) {
+        Some(remote) if !remote.is_empty() => remote,
+        _ => upstream_remote_and_branch(&repo_root)?
+            .map(|(remote, _)| remote)
+            .unwrap_or_else(|| "origin".to_string()),
+    };
+    let refspec = format!("refs/tags/{}", name.trim());
+    {
+        // Only configured remotes, so the value is never an option or a URL.
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        repo.find_remote(&remote)
+            .map_err(|_| format!("Remote {remote} does not exist."))?;
+        repo.find_reference(&refspec)
+            .map_err(|_| format!("Tag {} does not exist.", name.trim()))?;
     }
+    run_git_command(&repo_root, &["push", remote.as_str(), refspec.as_str()]).await
+}
 
-    let comments: Vec<GitHubPullRequestComment> =
-        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())?;
+#[tauri::command]
+pub(crate) async fn get_git_release_notes(
+    workspace_id: String,
+    from_tag: String,
+    to_ref: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<GitReleaseNotes, String> {
+    let workspaces = state.workspaces.lock().await;
+    let entry = workspaces
+        .get(&workspace_id)
+        .ok_or("workspace not found")?
+        .clone();
+    drop(workspaces);
 
-    Ok(comments)
+    let repo_root = resolve_git_root(&entry)?;
+    let to_ref = to_ref.unwrap_or_else(|| "HEAD".to_string());
+    tokio::task::spawn_blocking(move || {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        let from = resolve_commit(&repo, &from_tag)?.id();
+        let to = resolve_commit(&repo, &to_ref)?.id();
+        build_release_notes(&repo, from, to, &from_tag, &to_ref)
+    })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
 #[tauri::command]
-pub(crate) async fn list_git_branches(
+pub(crate) async fn list_git_submodules(
     workspace_id: String,
     state: State<'_, AppState>,
-) -> Result<serde_json::Value, String> {
+) -> Result<Vec<GitSubmoduleInfo>, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
+
     let repo_root = resolve_git_root(&entry)?;
     let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
-    let mut branches = Vec::new();
-    let refs = repo
-        .branches(Some(BranchType::Local))
-        .map_err(|e| e.to_string())?;
-    for branch_result in refs {
-        let (branch, _) = branch_result.map_err(|e| e.to_string())?;
-        let name = branch.name().ok().flatten().unwrap_or("").to_string();
-        if name.is_empty() {
-            continue;
-        }
-        let last_commit = branch
-            .get()
-            .target()
-            .and_then(|oid| repo.find_commit(oid).ok())
-            .map(|commit| commit.time().seconds())
-            .unwrap_or(0);
-        branches.push(BranchInfo { name, last_commit });
-    }
-    branches.sort_by(|a, b| b.last_commit.cmp(&a.last_commit));
-    Ok(json!({ "branches": branches }))
+    list_submodules(&repo, true)
 }
 
 #[tauri::command]
-pub(crate) async fn checkout_git_branch(
+pub(crate) async fn update_git_submodules(
     workspace_id: String,
-    name: String,
+    paths: Option<Vec<String>>,
+    init: Option<bool>,
+    sync: Option<bool>,
+    recursive: Option<bool>,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<Vec<GitSubmoduleInfo>, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
+
     let repo_root = resolve_git_root(&entry)?;
+    let recursive = recursive.unwrap_or(true);
+    let paths: Vec<String> = paths
+        .unwrap_or_default()
+        .iter()
+        .map(|path| normalize_git_path(path.trim()))
+        .filter(|path| !path.is_empty())
+        .collect();
+
+    // Sync first so a changed `.gitmodules` URL is used by the update.
+    if sync.unwrap_or(false) {
+        let mut args = vec!["submodule", "sync"];
+        if recursive {
+            args.push("--recursive");
+        }
+        args.push("--");
+        args.extend(paths.iter().map(String::as_str));
+        run_git_command(&repo_root, &args).await?;
+    }
+    let mut args = vec!["submodule", "update"];
+    if init.unwrap_or(true) {
+        args.push("--init");
+    }
+    if recursive {
+        args.push("--recursive");
+    }
+    args.push("--");
+    args.extend(paths.iter().map(String::as_str));
+    run_git_command(&repo_root, &args).await?;
+
     let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
-    checkout_branch(&repo, &name).map_err(|e| e.to_string())
+    list_submodules(&repo, true)
 }
 
 #[tauri::command]
-pub(crate) async fn create_git_branch(
+pub(crate) async fn get_git_lfs_locks(
     workspace_id: String,
-    name: String,
+    local: Option<bool>,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<Vec<GitLfsLock>, String> {
     let workspaces = state.workspaces.lock().await;
     let entry = workspaces
         .get(&workspace_id)
         .ok_or("workspace not found")?
         .clone();
+    drop(workspaces);
+
     let repo_root = resolve_git_root(&entry)?;
-    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
-    let head = repo.head().map_err(|e| e.to_string())?;
-    let target = head.peel_to_commit().map_err(|e| e.to_string())?;
-    repo.branch(&name, &target, false)
-        .map_err(|e| e.to_string())?;
-    checkout_branch(&repo, &name).map_err(|e| e.to_string())
+    let mut args = vec!["lfs", "locks", "--json"];
+    // `--local` reads the cached locks this clone holds without a server round trip.
+    if local.unwrap_or(false) {
+        args.push("--local");
+    }
+    let output = git_core::run_git_command(&repo_root, &args)
+        .await
+        .map_err(|err| {
+            if err.contains("'lfs' is not a git command") {
+                "Git LFS is not installed.".to_string()
+            } else {
+                err
+            }
+        })?;
+    parse_lfs_locks(&output)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{GitHubIssue, GitHubPullRequestAuthor};
     use std::fs;
     use std::path::Path;
 
@@ -1605,6 +3770,33 @@ mod tests {
         (root, repo)
     }
 
+    fn commit_file(repo: &Repository, name: &str, content: &str, message: &str) -> git2::Oid {
+        let root = repo.workdir().expect("workdir").to_path_buf();
+        fs::write(root.join(name), content).expect("write file");
+        let mut index = repo.index().expect("repo index");
+        index.add_path(Path::new(name)).expect("add path");
+        index.write().expect("write index");
+        let tree_id = index.write_tree().expect("write tree");
+        let tree = repo.find_tree(tree_id).expect("find tree");
+        let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+        let parents = repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok())
+            .into_iter()
+            .collect::<Vec<_>>();
+        let parent_refs = parents.iter().collect::<Vec<_>>();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+            .expect("commit")
+    }
+
+    fn index_content(repo: &Repository, name: &str) -> String {
+        let index = repo.index().expect("repo index");
+        let entry = index.get_path(Path::new(name), 0).expect("index entry");
+        let blob = repo.find_blob(entry.id).expect("index blob");
+        String::from_utf8_lossy(blob.content()).to_string()
+    }
+
     #[test]
     fn collect_workspace_diff_prefers_staged_changes() {
         let (root, repo) = create_temp_repo();
@@ -1661,4 +3853,695 @@ mod tests {
         let paths = action_paths_for_file(&root, "b.txt");
         assert_eq!(paths, vec!["a.txt".to_string(), "b.txt".to_string()]);
     }
+
+    #[test]
+    fn apply_git_hunks_stages_and_reverts_selected_hunks() {
+        let (root, repo) = create_temp_repo();
+        let original = (1..=20)
+            .map(|line| format!("line {line}\n"))
+            .collect::<String>();
+        commit_file(&repo, "notes.txt", &original, "init");
+        let modified = original
+            .replace("line 2\n", "line two\n")
+            .replace("line 18\n", "line eighteen\n");
+        fs::write(root.join("notes.txt"), &modified).expect("modify file");
+
+        let runtime = tokio::runtime::Runtime::new().expect("create runtime");
+        let second_hunk = GitHunkSelection {
+            header: "@@ -15,6 +15,6 @@".to_string(),
+            lines: None,
+        };
+        runtime
+            .block_on(apply_git_hunks(
+                &root,
+                "notes.txt",
+                &[second_hunk],
+                HunkAction::Stage,
+            ))
+            .expect("stage hunk");
+        let staged = index_content(&repo, "notes.txt");
+        assert!(staged.contains("line eighteen\n"));
+        assert!(staged.contains("line 2\n"));
+
+        let first_hunk = GitHunkSelection {
+            header: "@@ -1,5 +1,5 @@".to_string(),
+            lines: None,
+        };
+        runtime
+            .block_on(apply_git_hunks(
+                &root,
+                "notes.txt",
+                &[first_hunk],
+                HunkAction::Revert,
+            ))
+            .expect("revert hunk");
+        let workdir = fs::read_to_string(root.join("notes.txt")).expect("read file");
+        assert!(workdir.contains("line 2\n"));
+        assert!(workdir.contains("line eighteen\n"));
+    }
+
+    #[test]
+    fn stash_diff_includes_tracked_and_untracked_changes() {
+        let (root, mut repo) = create_temp_repo();
+        commit_file(&repo, "tracked.txt", "one\n", "initial");
+        fs::write(root.join("tracked.txt"), "two\n").expect("modify tracked");
+        fs::write(root.join("new.txt"), "fresh\n").expect("write untracked");
+
+        let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+        repo.stash_save(&sig, "wip", Some(git2::StashFlags::INCLUDE_UNTRACKED))
+            .expect("stash save");
+
+        let stashes = collect_git_stashes(&mut repo).expect("list stashes");
+        assert_eq!(stashes.len(), 1);
+        assert_eq!(stashes[0].index, 0);
+        assert!(stashes[0].message.ends_with("wip"));
+        assert!(stashes[0].branch.is_some());
+
+        let diffs = stash_commit_diffs(&repo, 0, false).expect("stash diff");
+        let tracked = diffs
+            .iter()
+            .find(|diff| diff.path == "tracked.txt")
+            .expect("tracked diff");
+        assert_eq!(tracked.status, "M");
+        assert!(tracked.diff.contains("+two"));
+        let untracked = diffs
+            .iter()
+            .find(|diff| diff.path == "new.txt")
+            .expect("untracked diff");
+        assert_eq!(untracked.status, "A");
+        assert!(stash_commit_diffs(&repo, 1, false).is_err());
+    }
+
+    #[test]
+    fn stash_branch_from_message_reads_branch_name() {
+        assert_eq!(
+            stash_branch_from_message("WIP on main: abc123 subject").as_deref(),
+            Some("main")
+        );
+        assert_eq!(
+            stash_branch_from_message("On feature/x: saved work").as_deref(),
+            Some("feature/x")
+        );
+        assert_eq!(stash_branch_from_message("custom"), None);
+    }
+
+    #[test]
+    fn collect_conflicts_reports_sides_and_markers() {
+        let (root, repo) = create_temp_repo();
+        let base = commit_file(&repo, "shared.txt", "base\n", "base");
+        let base_commit = repo.find_commit(base).expect("base commit");
+        repo.branch("other", &base_commit, false)
+            .expect("create branch");
+        commit_file(&repo, "shared.txt", "ours\n", "ours");
+
+        repo.set_head("refs/heads/other").expect("switch head");
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .expect("checkout other");
+        let theirs = commit_file(&repo, "shared.txt", "theirs\n", "theirs");
+        let default_branch = repo
+            .branches(Some(BranchType::Local))
+            .expect("branches")
+            .filter_map(|branch| branch.ok())
+            .filter_map(|(branch, _)| branch.name().ok().flatten().map(str::to_string))
+            .find(|name| name != "other")
+            .expect("default branch");
+        repo.set_head(&format!("refs/heads/{default_branch}"))
+            .expect("switch back");
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .expect("checkout default");
+
+        let annotated = repo.find_annotated_commit(theirs).expect("annotated");
+        repo.merge(&[&annotated], None, None).expect("merge");
+
+        assert_eq!(operation_for_state(repo.state()), Some("merge"));
+        let conflicts = collect_conflicts(&repo, &root).expect("conflicts");
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert_eq!(conflict.path, "shared.txt");
+        assert_eq!(conflict.base.as_deref(), Some("base\n"));
+        assert_eq!(conflict.ours.as_deref(), Some("ours\n"));
+        assert_eq!(conflict.theirs.as_deref(), Some("theirs\n"));
+        assert_eq!(conflict.markers.len(), 1);
+        assert!(!conflict.ours_deleted && !conflict.theirs_deleted);
+    }
+
+    #[test]
+    fn range_diff_and_comparison_follow_fork_point() {
+        let (_root, repo) = create_temp_repo();
+        let base = commit_file(&repo, "a.txt", "one\n", "base");
+        let base_commit = repo.find_commit(base).expect("base commit");
+        repo.branch("feature", &base_commit, false)
+            .expect("create branch");
+        let main_tip = commit_file(&repo, "main.txt", "main\n", "main work");
+
+        repo.set_head("refs/heads/feature").expect("switch head");
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .expect("checkout feature");
+        let feature_tip = commit_file(&repo, "a.txt", "two\n", "feature work");
+
+        let main = main_tip.to_string();
+        let feature = feature_tip.to_string();
+        let three_dot = range_file_diffs(&repo, &main, &feature, true, false).expect("diff");
+        assert_eq!(three_dot.len(), 1);
+        assert_eq!(three_dot[0].path, "a.txt");
+
+        let two_dot = range_file_diffs(&repo, &main, &feature, false, false).expect("diff");
+        let paths: Vec<_> = two_dot.iter().map(|diff| diff.path.as_str()).collect();
+        assert!(paths.contains(&"a.txt"));
+        assert!(paths.contains(&"main.txt"));
+
+        let comparison = compare_branches(&repo, &main, "feature", 10).expect("compare");
+        assert_eq!(comparison.ahead, 1);
+        assert_eq!(comparison.behind, 1);
+        assert_eq!(comparison.ahead_entries[0].sha, feature);
+        assert_eq!(comparison.behind_entries[0].sha, main);
+        assert_eq!(comparison.merge_base, Some(base.to_string()));
+        assert!(range_file_diffs(&repo, "missing", "feature", true, false).is_err());
+    }
+
+    #[test]
+    fn file_log_follows_renames_and_blame_attributes_lines() {
+        let (root, repo) = create_temp_repo();
+        let first = commit_file(&repo, "old.txt", "alpha\nbeta\n", "add old");
+        commit_file(&repo, "other.txt", "noise\n", "unrelated");
+
+        let mut index = repo.index().expect("repo index");
+        fs::rename(root.join("old.txt"), root.join("new.txt")).expect("rename file");
+        index
+            .remove_path(Path::new("old.txt"))
+            .expect("remove old path");
+        index.add_path(Path::new("new.txt")).expect("add new path");
+        index.write().expect("write index");
+        let tree = repo
+            .find_tree(index.write_tree().expect("write tree"))
+            .expect("find tree");
+        let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "rename", &tree, &[&head])
+            .expect("commit rename");
+        let last = commit_file(&repo, "new.txt", "alpha\ngamma\n", "edit new");
+
+        let log = file_log(&repo, "new.txt", 10).expect("file log");
+        let summary: Vec<_> = log
+            .iter()
+            .map(|entry| (entry.status.as_str(), entry.path.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("M", "new.txt"), ("R", "new.txt"), ("A", "old.txt")]
+        );
+        assert_eq!(log[1].previous_path.as_deref(), Some("old.txt"));
+        assert_eq!(file_log(&repo, "new.txt", 1).expect("limited").len(), 1);
+
+        let blame = blame_file(&repo, "new.txt", None).expect("blame");
+        assert_eq!(blame.lines, vec!["alpha\n", "gamma\n"]);
+        let line_owner = |line: usize| {
+            blame
+                .hunks
+                .iter()
+                .find(|hunk| line >= hunk.start_line && line < hunk.start_line + hunk.line_count)
+                .map(|hunk| hunk.sha.clone())
+        };
+        assert_eq!(line_owner(1), Some(first.to_string()));
+        assert_eq!(line_owner(2), Some(last.to_string()));
+        assert!(blame_file(&repo, "missing.txt", None).is_err());
+    }
+
+    #[test]
+    fn query_log_pages_filters_and_selects_branches() {
+        let (root, repo) = create_temp_repo();
+        // Explicit timestamps keep the time-sorted walk deterministic.
+        let commit_at = |name: &str, author: &str, time: i64, message: &str| {
+            fs::write(root.join(name), message).expect("write file");
+            let mut index = repo.index().expect("repo index");
+            index.add_path(Path::new(name)).expect("add path");
+            index.write().expect("write index");
+            let tree = repo
+                .find_tree(index.write_tree().expect("write tree"))
+                .expect("find tree");
+            let email = format!("{}@example.com", author.to_lowercase());
+            let sig =
+                git2::Signature::new(author, &email, &git2::Time::new(time, 0)).expect("signature");
+            let parents = repo
+                .head()
+                .ok()
+                .and_then(|head| head.peel_to_commit().ok())
+                .into_iter()
+                .collect::<Vec<_>>();
+            let parent_refs = parents.iter().collect::<Vec<_>>();
+            repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+                .expect("commit")
+        };
+        let first = commit_at("a.txt", "Test", 1_000, "initial");
+        let second = commit_at("b.txt", "Test", 2_000, "add b");
+        let third = commit_at("a.txt", "Alice", 3_000, "fix: alice change");
+
+        let page = |query: GitLogQuery, limit: usize| {
+            let (entries, cursor) = query_log(&repo, &query, limit).expect("query log");
+            let shas: Vec<String> = entries.iter().map(|entry| entry.sha.clone()).collect();
+            (shas, cursor)
+        };
+        let (shas, cursor) = page(GitLogQuery::default(), 2);
+        assert_eq!(shas, vec![third.to_string(), second.to_string()]);
+        assert_eq!(cursor, Some(second.to_string()));
+        let (shas, cursor) = page(
+            GitLogQuery {
+                cursor,
+                ..Default::default()
+            },
+            2,
+        );
+        assert_eq!(shas, vec![first.to_string()]);
+        assert_eq!(cursor, None);
+
+        let (shas, _) = page(
+            GitLogQuery {
+                path: Some("a.txt".to_string()),
+                ..Default::default()
+            },
+            10,
+        );
+        assert_eq!(shas, vec![third.to_string(), first.to_string()]);
+        let alice_query = GitLogQuery {
+            author: Some("ALICE".to_string()),
+            message: Some("fix:".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(page(alice_query.clone(), 10).0, vec![third.to_string()]);
+        assert_eq!(count_log(&repo, &alice_query).expect("count"), 1);
+        assert_eq!(count_log(&repo, &GitLogQuery::default()).expect("count"), 3);
+        let future = GitLogQuery {
+            since: Some(i64::MAX),
+            ..Default::default()
+        };
+        assert!(page(future, 10).0.is_empty());
+
+        let (entries, _) = query_log(&repo, &GitLogQuery::default(), 1).expect("query log");
+        assert_eq!(entries[0].parents, vec![second.to_string()]);
+
+        let first_commit = repo.find_commit(first).expect("first commit");
+        repo.branch("side", &first_commit, false)
+            .expect("create branch");
+        let side = GitLogQuery {
+            branch: Some("side".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(page(side, 10).0, vec![first.to_string()]);
+        let all = GitLogQuery {
+            all_branches: true,
+            ..Default::default()
+        };
+        assert_eq!(page(all, 10).0.len(), 3);
+        let stale = GitLogQuery {
+            cursor: Some(git2::Oid::zero().to_string()),
+            ..Default::default()
+        };
+        assert!(query_log(&repo, &stale, 10).is_err());
+    }
+
+    #[test]
+    fn squash_commits_since_folds_branch_commits() {
+        let (root, repo) = create_temp_repo();
+        {
+            let mut config = repo.config().expect("repo config");
+            config.set_str("user.name", "Test").expect("set name");
+            config
+                .set_str("user.email", "test@example.com")
+                .expect("set email");
+        }
+        let base = commit_file(&repo, "a.txt", "one\n", "base");
+        let base_commit = repo.find_commit(base).expect("base commit");
+        repo.branch("main-line", &base_commit, false)
+            .expect("create branch");
+        commit_file(&repo, "a.txt", "two\n", "first tweak");
+        commit_file(&repo, "b.txt", "new\n", "second tweak");
+
+        let runtime = tokio::runtime::Runtime::new().expect("create runtime");
+        runtime
+            .block_on(squash_commits_since(&root, "main-line", None))
+            .expect("squash commits");
+
+        let head = repo
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .expect("head commit");
+        assert_eq!(head.parent_id(0).expect("head parent"), base);
+        assert_eq!(
+            head.message().expect("head message").trim(),
+            "first tweak\n\n- second tweak"
+        );
+        let tree = head.tree().expect("head tree");
+        assert!(tree.get_path(Path::new("b.txt")).is_ok());
+
+        commit_file(&repo, "c.txt", "more\n", "third tweak");
+        fs::write(root.join("staged.txt"), "staged\n").expect("write staged file");
+        let mut index = repo.index().expect("repo index");
+        index.add_path(Path::new("staged.txt")).expect("stage file");
+        index.write().expect("write index");
+        let err = runtime
+            .block_on(squash_commits_since(&root, "main-line", None))
+            .expect_err("staged changes block squash");
+        assert!(err.contains("staged changes"));
+
+        let err = runtime
+            .block_on(squash_commits_since(&root, "HEAD", None))
+            .expect_err("nothing to squash");
+        assert!(err.contains("No commits to squash"));
+    }
+
+    #[test]
+    fn cherry_pick_fetches_from_other_clone_and_reports_conflicts() {
+        let configure = |repo: &Repository| {
+            let mut config = repo.config().expect("repo config");
+            config.set_str("user.name", "Test").expect("set name");
+            config
+                .set_str("user.email", "test@example.com")
+                .expect("set email");
+        };
+        let (source_root, source) = create_temp_repo();
+        configure(&source);
+        commit_file(&source, "a.txt", "base\n", "base");
+
+        let (target_root, _) = create_temp_repo();
+        fs::remove_dir_all(&target_root).expect("clear clone target");
+        let target =
+            Repository::clone(source_root.to_str().unwrap(), &target_root).expect("clone source");
+        configure(&target);
+        commit_file(&target, "a.txt", "target\n", "target edit");
+
+        let feature = commit_file(&source, "feature.txt", "feature\n", "feature").to_string();
+        let clash = commit_file(&source, "a.txt", "source\n", "source edit").to_string();
+
+        let runtime = tokio::runtime::Runtime::new().expect("create runtime");
+        assert!(runtime
+            .block_on(ensure_commits_available(
+                &target_root,
+                None,
+                std::slice::from_ref(&feature)
+            ))
+            .is_err());
+        let injected = runtime
+            .block_on(ensure_commits_available(
+                &target_root,
+                Some(&source_root),
+                &["--upload-pack=touch injected".to_string()],
+            ))
+            .expect_err("option-like sha");
+        assert!(injected.contains("Unknown revision"));
+        runtime
+            .block_on(ensure_commits_available(
+                &target_root,
+                Some(&source_root),
+                &[feature.clone(), clash.clone()],
+            ))
+            .expect("fetch commits");
+
+        let picked = runtime
+            .block_on(run_pick_command(&target_root, &["cherry-pick", &feature]))
+            .expect("cherry-pick");
+        assert!(picked.success);
+        assert!(target_root.join("feature.txt").exists());
+
+        let conflicted = runtime
+            .block_on(run_pick_command(&target_root, &["cherry-pick", &clash]))
+            .expect("conflict result");
+        assert!(!conflicted.success);
+        assert_eq!(conflicted.conflicted_files, vec!["a.txt".to_string()]);
+        assert_eq!(conflicted.operation.as_deref(), Some("cherry-pick"));
+        assert!(same_git_storage(&target_root, &target_root));
+        assert!(!same_git_storage(&target_root, &source_root));
+    }
+
+    #[test]
+    fn release_notes_group_commits_since_tag() {
+        let (_root, repo) = create_temp_repo();
+        let base = commit_file(&repo, "a.txt", "one\n", "chore: initial");
+        let base_commit = repo.find_commit(base).expect("base commit");
+        repo.tag_lightweight("v0.1.0", base_commit.as_object(), false)
+            .expect("lightweight tag");
+        commit_file(&repo, "a.txt", "two\n", "feat(git): add tags");
+        commit_file(&repo, "a.txt", "three\n", "fix: handle empty tag list");
+        let head = commit_file(
+            &repo,
+            "a.txt",
+            "four\n",
+            "refactor!: drop old api\n\nBREAKING CHANGE: removed",
+        );
+        commit_file(&repo, "b.txt", "b\n", "Update readme");
+        let signature = git2::Signature::now("Tester", "tester@example.com").expect("signature");
+        let head_commit = repo.find_commit(head).expect("head commit");
+        repo.tag(
+            "v0.2.0",
+            head_commit.as_object(),
+            &signature,
+            "Second release\n",
+            false,
+        )
+        .expect("annotated tag");
+
+        let tags = list_tags(&repo).expect("tags");
+        let annotated = tags
+            .iter()
+            .find(|tag| tag.name == "v0.2.0")
+            .expect("v0.2.0");
+        assert!(annotated.annotated);
+        assert_eq!(annotated.sha, head.to_string());
+        assert_eq!(annotated.message.as_deref(), Some("Second release"));
+        let lightweight = tags
+            .iter()
+            .find(|tag| tag.name == "v0.1.0")
+            .expect("v0.1.0");
+        assert!(!lightweight.annotated);
+        assert_eq!(lightweight.sha, base.to_string());
+
+        let to = repo.head().expect("head").target().expect("target");
+        let notes = build_release_notes(&repo, base, to, "v0.1.0", "HEAD").expect("notes");
+        let kinds: Vec<&str> = notes
+            .sections
+            .iter()
+            .map(|section| section.kind.as_str())
+            .collect();
+        assert_eq!(kinds, vec!["feat", "fix", "refactor", "other"]);
+        assert_eq!(notes.breaking.len(), 1);
+        assert_eq!(notes.breaking[0].sha, head.to_string());
+        assert!(notes
+            .markdown
+            .starts_with("## Breaking Changes\n- drop old api"));
+        assert!(notes.markdown.contains("## Features\n- **git:** add tags"));
+        assert!(notes.markdown.contains("## Other Changes\n- Update readme"));
+    }
+
+    #[test]
+    fn commit_diffs_resolve_lfs_images_from_local_store() {
+        let (root, repo) = create_temp_repo();
+        commit_file(
+            &repo,
+            ".gitattributes",
+            "*.png filter=lfs diff=lfs merge=lfs -text\n",
+            "lfs",
+        );
+        let base = repo.head().expect("head").peel_to_tree().expect("tree");
+        let oid = "a".repeat(64);
+        let pointer =
+            format!("version https://git-lfs.github.com/spec/v1\noid sha256:{oid}\nsize 4\n");
+        commit_file(&repo, "logo.png", &pointer, "add logo");
+        let head = repo.head().expect("head").peel_to_tree().expect("tree");
+
+        let diffs = diff_trees_to_commit_diffs(&repo, Some(&base), &head, false).expect("diffs");
+        assert_eq!(diffs.len(), 1);
+        let lfs = diffs[0].lfs.as_ref().expect("lfs entry");
+        assert_eq!(lfs.new_oid.as_deref(), Some(oid.as_str()));
+        assert_eq!(lfs.new_size, Some(4));
+        assert!(!lfs.new_available);
+        assert!(diffs[0].diff.is_empty());
+        assert!(diffs[0].new_image_data.is_none());
+
+        let object = root.join(".git/lfs/objects/aa/aa").join(&oid);
+        fs::create_dir_all(object.parent().expect("object dir")).expect("create lfs dir");
+        fs::write(&object, b"\x89PNG").expect("write lfs object");
+        let diffs = diff_trees_to_commit_diffs(&repo, Some(&base), &head, false).expect("diffs");
+        assert!(diffs[0].lfs.as_ref().expect("lfs entry").new_available);
+        assert_eq!(diffs[0].new_image_data.as_deref(), Some("iVBORw=="));
+    }
+
+    #[test]
+    fn issue_runs_get_branch_prompt_and_pull_request_reference() {
+        let comment = |id: u64, body: &str| GitHubPullRequestComment {
+            id,
+            body: body.to_string(),
+            created_at: String::new(),
+            url: String::new(),
+            author: Some(GitHubPullRequestAuthor {
+                login: "octocat".to_string(),
+            }),
+            path: None,
+            line: None,
+            start_line: None,
+            original_line: None,
+            side: None,
+            diff_hunk: None,
+            in_reply_to_id: None,
+        };
+        let issue = GitHubIssueDetails {
+            issue: GitHubIssue {
+                number: 42,
+                title: "Crash when the config file is empty!".to_string(),
+                url: "https://github.com/acme/app/issues/42".to_string(),
+                updated_at: String::new(),
+            },
+            body: "Steps to reproduce...\n".to_string(),
+            state: "open".to_string(),
+            labels: vec!["bug".to_string()],
+            author: None,
+            comments: (0..25)
+                .map(|index| comment(index, &format!("note {index}")))
+                .collect(),
+        };
+
+        assert_eq!(
+            issue_branch_name(&issue),
+            "issue/42-crash-when-the-config-file-is-empty"
+        );
+        let prompt = issue_run_prompt(&issue);
+        assert!(prompt.starts_with("Resolve issue #42: Crash when the config file is empty!\n"));
+        assert!(prompt.contains("Labels: bug\n\nSteps to reproduce...\n"));
+        assert!(prompt.contains("Comments (latest 20 of 25):"));
+        assert!(!prompt.contains("note 4\n"));
+        assert!(prompt.contains("@octocat:\nnote 24\n"));
+
+        assert_eq!(
+            with_issue_reference("- Fix parsing\n".to_string(), Some(42)),
+            "- Fix parsing\n\nFixes #42"
+        );
+        assert_eq!(
+            with_issue_reference("Closes #42".to_string(), Some(42)),
+            "Closes #42"
+        );
+        assert_eq!(
+            with_issue_reference("See #420".to_string(), Some(42)),
+            "See #420\n\nFixes #42"
+        );
+        assert_eq!(with_issue_reference(String::new(), None), "");
+    }
+
+    #[test]
+    fn pull_request_checkout_fast_forwards_and_rejects_diverged_branches() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        use tokio::net::TcpListener;
+
+        let (source_root, source) = create_temp_repo();
+        let base = commit_file(&source, "a.txt", "base\n", "base");
+        let main_branch = source
+            .head()
+            .expect("source head")
+            .shorthand()
+            .expect("branch name")
+            .to_string();
+        let base_commit = source.find_commit(base).expect("base commit");
+        source
+            .branch("feature", &base_commit, false)
+            .expect("create feature");
+        source.set_head("refs/heads/feature").expect("switch to feature");
+        let publish = |head: git2::Oid| {
+            source
+                .reference("refs/pull/7/head", head, true, "pull request head")
+                .expect("set pull ref");
+        };
+        let bare_root = source_root.with_extension("remote.git");
+        Repository::init_bare(&bare_root).expect("init bare remote");
+        let push = || {
+            let mut remote = source
+                .remote_anonymous(bare_root.to_str().expect("bare path"))
+                .expect("bare remote");
+            let main_refspec = format!("+refs/heads/{main_branch}:refs/heads/{main_branch}");
+            remote
+                .push(
+                    &[main_refspec.as_str(), "+refs/pull/7/head:refs/pull/7/head"],
+                    None,
+                )
+                .expect("push to bare remote");
+        };
+        let first = commit_file(&source, "pr.txt", "one\n", "pr one");
+        publish(first);
+        push();
+
+        let (workspace_root, _) = create_temp_repo();
+        fs::remove_dir_all(&workspace_root).expect("clear clone target");
+        let workspace = Repository::clone(bare_root.to_str().expect("bare path"), &workspace_root)
+            .expect("clone remote");
+        let local_head = |workspace: &Repository| {
+            workspace
+                .find_branch("pr/7", BranchType::Local)
+                .expect("pr branch")
+                .get()
+                .target()
+                .expect("pr branch target")
+        };
+
+        let runtime = tokio::runtime::Runtime::new().expect("create runtime");
+        runtime.block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+            let api_base = format!("http://{}", listener.local_addr().expect("addr"));
+            let body = format!(
+                r#"{{"title":"Fix it","head":{{"ref":"feature","repo":{{"full_name":"fork/demo"}}}},"base":{{"ref":"{main_branch}","repo":{{"full_name":"octo/demo"}}}}}}"#
+            );
+            tokio::spawn(async move {
+                while let Ok((mut socket, _)) = listener.accept().await {
+                    let mut buffer = vec![0u8; 8192];
+                    let read = socket.read(&mut buffer).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+                    let (status, body) = if request.starts_with("GET /repos/octo/demo/pulls/7 ") {
+                        ("200 OK", body.clone())
+                    } else {
+                        ("404 Not Found", r#"{"message":"Not Found"}"#.to_string())
+                    };
+                    let response = format!(
+                        "HTTP/1.1 {status}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                    let _ = socket.shutdown().await;
+                }
+            });
+            let client = GitHubClient::new(Some(&api_base), None).expect("client");
+            async fn checkout_with(
+                client: &GitHubClient,
+                repo_root: &Path,
+            ) -> Result<PullRequestBranch, String> {
+                let refs = client
+                    .pull_request_refs("octo/demo", 7)
+                    .await
+                    .expect("pull request refs");
+                checkout_pull_request_refs(repo_root, refs, 7).await
+            }
+            let checkout = || checkout_with(&client, &workspace_root);
+
+            let prepared = checkout().await.expect("first checkout");
+            assert_eq!(prepared.branch, "pr/7");
+            assert_eq!(prepared.base_ref, format!("origin/{main_branch}"));
+            assert_eq!(prepared.title, "Fix it");
+            assert_eq!(local_head(&workspace), first);
+
+            let second = commit_file(&source, "pr.txt", "two\n", "pr two");
+            publish(second);
+            push();
+            checkout().await.expect("fast-forward checkout");
+            assert_eq!(local_head(&workspace), second);
+
+            let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+            let tree = base_commit.tree().expect("base tree");
+            let rewritten = source
+                .commit(None, &sig, &sig, "pr rewritten", &tree, &[&base_commit])
+                .expect("rewritten commit");
+            publish(rewritten);
+            push();
+            let err = checkout().await.expect_err("diverged branch");
+            assert!(err.contains("diverged"));
+            assert_eq!(local_head(&workspace), second);
+        });
+
+        let _ = fs::remove_dir_all(source_root);
+        let _ = fs::remove_dir_all(bare_root);
+        let _ = fs::remove_dir_all(workspace_root);
+    }
 }
@@ -0,0 +1,432 @@
+use crate::types::GitHunkSelection;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HunkRange {
+    pub(crate) start: u32,
+    pub(crate) count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PatchHunk {
+    pub(crate) old: HunkRange,
+    pub(crate) new: HunkRange,
+    pub(crate) section: String,
+    pub(crate) lines: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct FilePatch {
+    pub(crate) header: Vec<String>,
+    pub(crate) hunks: Vec<PatchHunk>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PatchDirection {
+    /// The patch is applied as-is (staging).
+    Forward,
+    /// The patch is applied with `-R` (unstaging or reverting).
+    Reverse,
+}
+
+/// The side of the diff that matches the HEAD-to-worktree diff shown in the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DiffAnchor {
+    Old,
+    New,
+}
+
+fn parse_range(value: &str) -> Option<HunkRange> {
+    let (start, count) = match value.split_once(',') {
+        Some((start, count)) => (start, count.parse().ok()?),
+        None => (value, 1),
+    };
+    Some(HunkRange {
+        start: start.parse().ok()?,
+        count,
+    })
+}
+
+pub(crate) fn parse_hunk_header(header: &str) -> Option<(HunkRange, HunkRange, String)> {
+    let rest = header.trim_end_matches(['\r', '\n']).strip_prefix("@@ -")?;
+    let (ranges, section) = rest.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+    Some((parse_range(old)?, parse_range(new)?, section.to_string()))
+}
+
+pub(crate) fn parse_file_patch(diff: &str) -> Option<FilePatch> {
+    let mut header = Vec::new();
+    let mut hunks: Vec<PatchHunk> = Vec::new();
+    for line in diff.split_inclusive('\n') {
+        if line.starts_with("@@ ") {
+            let (old, new, section) = parse_hunk_header(line)?;
+            hunks.push(PatchHunk {
+                old,
+                new,
+                section,
+                lines: Vec::new(),
+            });
+            continue;
+        }
+        match hunks.last_mut() {
+            Some(hunk) => hunk.lines.push(line.to_string()),
+            None => {
+                if line.starts_with("diff --git ") && !header.is_empty() {
+                    // Only the first file of a multi-file diff is used.
+                    break;
+                }
+                header.push(line.to_string());
+            }
+        }
+    }
+    if hunks.is_empty() {
+        return None;
+    }
+    Some(FilePatch { header, hunks })
+}
+
+pub(crate) fn is_binary_patch(diff: &str) -> bool {
+    diff.lines()
+        .any(|line| line.starts_with("Binary files ") || line == "GIT binary patch")
+}
+
+fn filter_hunk_lines(
+    hunk: &PatchHunk,
+    selected: Option<&[usize]>,
+    direction: PatchDirection,
+) -> Option<PatchHunk> {
+    let Some(selected) = selected else {
+        return Some(hunk.clone());
+    };
+    let (keep_prefix, drop_prefix) = match direction {
+        PatchDirection::Forward => ('-', '+'),
+        PatchDirection::Reverse => ('+', '-'),
+    };
+    let mut lines = Vec::with_capacity(hunk.lines.len());
+    let mut previous_kept = true;
+    for (index, line) in hunk.lines.iter().enumerate() {
+        let prefix = line.chars().next().unwrap_or(' ');
+        if prefix == '\\' {
+            if previous_kept {
+                lines.push(line.clone());
+            }
+            continue;
+        }
+        if (prefix != '+' && prefix != '-') || selected.contains(&index) {
+            lines.push(line.clone());
+            previous_kept = true;
+        } else if prefix == keep_prefix {
+            lines.push(format!(" {}", &line[1..]));
+            previous_kept = true;
+        } else {
+            debug_assert_eq!(prefix, drop_prefix);
+            previous_kept = false;
+        }
+    }
+    if !lines
+        .iter()
+        .any(|line| line.starts_with('+') || line.starts_with('-'))
+    {
+        return None;
+    }
+    let old_count = lines
+        .iter()
+        .filter(|line| line.starts_with(' ') || line.starts_with('-'))
+        .count() as u32;
+    let new_count = lines
+        .iter()
+        .filter(|line| line.starts_with(' ') || line.starts_with('+'))
+        .count() as u32;
+    Some(PatchHunk {
+        old: HunkRange {
+            start: hunk.old.start,
+            count: old_count,
+        },
+        new: HunkRange {
+            start: hunk.new.start,
+            count: new_count,
+        },
+        section: hunk.section.clone(),
+        lines,
+    })
+}
+
+fn first_line(range: HunkRange) -> i64 {
+    if range.count == 0 {
+        i64::from(range.start) + 1
+    } else {
+        i64::from(range.start)
+    }
+}
+
+fn range_start(first_line: i64, count: u32) -> u32 {
+    let start = if count == 0 {
+        first_line - 1
+    } else {
+        first_line
+    };
+    start.max(0) as u32
+}
+
+/// Recomputes the side of each hunk that the target does not already contain,
+/// so dropped hunks and lines do not leave stale offsets behind.
+fn renumber_hunks(hunks: &mut [PatchHunk], direction: PatchDirection) {
+    let mut offset: i64 = 0;
+    for hunk in hunks.iter_mut() {
+        match direction {
+            PatchDirection::Forward => {
+                let first = first_line(hunk.old) + offset;
+                hunk.new.start = range_start(first, hunk.new.count);
+                offset += i64::from(hunk.new.count) - i64::from(hunk.old.count);
+            }
+            PatchDirection::Reverse => {
+                let first = first_line(hunk.new) + offset;
+                hunk.old.start = range_start(first, hunk.old.count);
+                offset += i64::from(hunk.old.count) - i64::from(hunk.new.count);
+            }
+        }
+    }
+}
+
+fn ranges_overlap(left: HunkRange, right: HunkRange) -> bool {
+    let left_start = first_line(left);
+    let right_start = first_line(right);
+    let left_end = left_start + i64::from(left.count.max(1));
+    let right_end = right_start + i64::from(right.count.max(1));
+    left_start < right_end && right_start < left_end
+}
+
+fn find_hunk(
+    patch: &FilePatch,
+    selection: &GitHunkSelection,
+    anchor: DiffAnchor,
+) -> Result<usize, String> {
+    let (old, new, _) = parse_hunk_header(&selection.header)
+        .ok_or_else(|| format!("Invalid hunk header: {}", selection.header))?;
+    if let Some(index) = patch
+        .hunks
+        .iter()
+        .position(|hunk| hunk.old == old && hunk.new == new)
+    {
+        return Ok(index);
+    }
+    // Headers from get_git_diffs compare HEAD to the working tree. When part of the
+    // file is already staged they drift from the index-based diff, so fall back to
+    // the side both diffs share.
+    if selection.lines.is_none() {
+        let mut matches = patch
+            .hunks
+            .iter()
+            .enumerate()
+            .filter(|(_, hunk)| match anchor {
+                DiffAnchor::Old => ranges_overlap(hunk.old, old),
+                DiffAnchor::New => ranges_overlap(hunk.new, new),
+            });
+        if let (Some((index, _)), None) = (matches.next(), matches.next()) {
+            return Ok(index);
+        }
+    }
+    Err("Selected hunk no longer matches the current diff. Refresh and try again.".to_string())
+}
+
+fn rewrite_header_for_partial(
+    header: &[String],
+    path: &str,
+    direction: PatchDirection,
+) -> Vec<String> {
+    let removes_file = header.iter().any(|line| match direction {
+        PatchDirection::Forward => line.starts_with("deleted file mode"),
+        PatchDirection::Reverse => line.starts_with("new file mode"),
+    });
+    if !removes_file {
+        return header.to_vec();
+    }
+    header
+        .iter()
+        .filter(|line| {
+            !line.starts_with("deleted file mode")
+                && !line.starts_with("new file mode")
+                && !line.starts_with("index ")
+        })
+        .map(|line| {
+            if line.trim_end() == "--- /dev/null" {
+                format!("--- a/{path}\n")
+            } else if line.trim_end() == "+++ /dev/null" {
+                format!("+++ b/{path}\n")
+            } else {
+                line.clone()
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn build_partial_patch(
+    patch: &FilePatch,
+    selections: &[GitHunkSelection],
+    direction: PatchDirection,
+    anchor: DiffAnchor,
+    path: &str,
+) -> Result<String, String> {
+    if selections.is_empty() {
+        return Err("No hunks selected.".to_string());
+    }
+    let mut picked: Vec<(usize, PatchHunk)> = Vec::new();
+    for selection in selections {
+        let index = find_hunk(patch, selection, anchor)?;
+        if picked.iter().any(|(existing, _)| *existing == index) {
+            continue;
+        }
+        let hunk = &patch.hunks[index];
+        if let Some(filtered) = filter_hunk_lines(hunk, selection.lines.as_deref(), direction) {
+            picked.push((index, filtered));
+        }
+    }
+    if picked.is_empty() {
+        return Err("Selected lines contain no changes.".to_string());
+    }
+    picked.sort_by_key(|(index, _)| *index);
+    let mut hunks = picked.into_iter().map(|(_, hunk)| hunk).collect::<Vec<_>>();
+    let is_whole_file = hunks == patch.hunks;
+    if !is_whole_file {
+        renumber_hunks(&mut hunks, direction);
+    }
+
+    let mut output = String::new();
+    let header = if is_whole_file {
+        patch.header.clone()
+    } else {
+        rewrite_header_for_partial(&patch.header, path, direction)
+    };
+    for line in header {
+        output.push_str(&line);
+    }
+    for hunk in hunks {
+        output.push_str(&format!(
+            "@@ -{},{} +{},{} @@{}\n",
+            hunk.old.start, hunk.old.count, hunk.new.start, hunk.new.count, hunk.section
+        ));
+        for line in hunk.lines {
+            output.push_str(&line);
+            if !line.ends_with('\n') {
+                output.push('\n');
+            }
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = concat!(
+        "diff --git a/file.txt b/file.txt\n",
+        "index 1111111..2222222 100644\n",
+        "--- a/file.txt\n",
+        "+++ b/file.txt\n",
+        "@@ -1,3 +1,4 @@\n",
+        " one\n",
+        "-two\n",
+        "+TWO\n",
+        "+extra\n",
+        " three\n",
+        "@@ -10,2 +11,3 @@ fn tail\n",
+        " ten\n",
+        "+ten-and-a-half\n",
+        " eleven\n",
+    );
+
+    fn selection(header: &str, lines: Option<Vec<usize>>) -> GitHunkSelection {
+        GitHunkSelection {
+            header: header.to_string(),
+            lines,
+        }
+    }
+
+    #[test]
+    fn parses_hunk_headers() {
+        let (old, new, section) = parse_hunk_header("@@ -3 +3,0 @@ fn main").expect("header");
+        assert_eq!(old, HunkRange { start: 3, count: 1 });
+        assert_eq!(new, HunkRange { start: 3, count: 0 });
+        assert_eq!(section, " fn main");
+    }
+
+    #[test]
+    fn builds_patch_for_single_hunk_with_renumbering() {
+        let patch = parse_file_patch(DIFF).expect("patch");
+        assert_eq!(patch.hunks.len(), 2);
+        let output = build_partial_patch(
+            &patch,
+            &[selection("@@ -10,2 +11,3 @@", None)],
+            PatchDirection::Forward,
+            DiffAnchor::New,
+            "file.txt",
+        )
+        .expect("partial patch");
+        assert!(output.starts_with("diff --git a/file.txt b/file.txt\n"));
+        assert!(output.contains("@@ -10,2 +10,3 @@ fn tail\n"));
+        assert!(!output.contains("TWO"));
+    }
+
+    #[test]
+    fn filters_lines_for_forward_and_reverse() {
+        let patch = parse_file_patch(DIFF).expect("patch");
+        let forward = build_partial_patch(
+            &patch,
+            &[selection("@@ -1,3 +1,4 @@", Some(vec![2]))],
+            PatchDirection::Forward,
+            DiffAnchor::New,
+            "file.txt",
+        )
+        .expect("forward patch");
+        assert!(forward.contains("@@ -1,3 +1,4 @@\n one\n two\n+TWO\n three\n"));
+
+        let reverse = build_partial_patch(
+            &patch,
+            &[selection("@@ -1,3 +1,4 @@", Some(vec![1]))],
+            PatchDirection::Reverse,
+            DiffAnchor::New,
+            "file.txt",
+        )
+        .expect("reverse patch");
+        assert!(reverse.contains("@@ -1,5 +1,4 @@\n one\n-two\n TWO\n extra\n three\n"));
+    }
+
+    #[test]
+    fn rejects_stale_headers_with_line_selection() {
+        let patch = parse_file_patch(DIFF).expect("patch");
+        let result = build_partial_patch(
+            &patch,
+            &[selection("@@ -40,2 +40,3 @@", Some(vec![0]))],
+            PatchDirection::Forward,
+            DiffAnchor::New,
+            "file.txt",
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn rewrites_deleted_file_header_for_partial_stage() {
+        let diff = concat!(
+            "diff --git a/gone.txt b/gone.txt\n",
+            "deleted file mode 100644\n",
+            "index 1111111..0000000\n",
+            "--- a/gone.txt\n",
+            "+++ /dev/null\n",
+            "@@ -1,2 +0,0 @@\n",
+            "-first\n",
+            "-second\n",
+        );
+        let patch = parse_file_patch(diff).expect("patch");
+        let output = build_partial_patch(
+            &patch,
+            &[selection("@@ -1,2 +0,0 @@", Some(vec![0]))],
+            PatchDirection::Forward,
+            DiffAnchor::New,
+            "gone.txt",
+        )
+        .expect("partial patch");
+        assert!(!output.contains("deleted file mode"));
+        assert!(output.contains("+++ b/gone.txt\n"));
+        assert!(output.contains("@@ -1,2 +1,1 @@\n-first\n second\n"));
+    }
+}
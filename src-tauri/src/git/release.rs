@@ -0,0 +1,233 @@
+use git2::{ObjectType, Oid, Repository, Sort};
+
+use crate::git_utils::commit_to_entry;
+use crate::types::{GitLogEntry, GitReleaseNotes, GitReleaseSection, GitTagInfo};
+
+const SECTIONS: &[(&str, &str)] = &[
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance"),
+    ("refactor", "Refactoring"),
+    ("docs", "Documentation"),
+    ("test", "Tests"),
+    ("build", "Build"),
+    ("ci", "CI"),
+    ("chore", "Chores"),
+    ("style", "Style"),
+    ("revert", "Reverts"),
+];
+const OTHER_KIND: &str = "other";
+const OTHER_TITLE: &str = "Other Changes";
+
+/// Lists tags that point at commits, newest first.
+pub(crate) fn list_tags(repo: &Repository) -> Result<Vec<GitTagInfo>, String> {
+    let names = repo.tag_names(None).map_err(|e| e.to_string())?;
+    let mut tags = Vec::new();
+    for name in names.iter().flatten() {
+        let reference = match repo.find_reference(&format!("refs/tags/{name}")) {
+            Ok(reference) => reference,
+            Err(_) => continue,
+        };
+        let Ok(commit) = reference.peel_to_commit() else {
+            continue;
+        };
+        let tag = reference
+            .peel(ObjectType::Tag)
+            .ok()
+            .and_then(|object| object.into_tag().ok());
+        let tagger = tag.as_ref().and_then(|tag| tag.tagger());
+        tags.push(GitTagInfo {
+            name: name.to_string(),
+            sha: commit.id().to_string(),
+            annotated: tag.is_some(),
+            message: tag
+                .as_ref()
+                .and_then(|tag| tag.message())
+                .map(|message| message.trim().to_string())
+                .filter(|message| !message.is_empty()),
+            tagger: tagger
+                .as_ref()
+                .and_then(|tagger| tagger.name())
+                .map(str::to_string),
+            timestamp: tagger
+                .map(|tagger| tagger.when().seconds())
+                .unwrap_or_else(|| commit.time().seconds()),
+        });
+    }
+    tags.sort_by(|a, b| {
+        b.timestamp
+            .cmp(&a.timestamp)
+            .then_with(|| b.name.cmp(&a.name))
+    });
+    Ok(tags)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ConventionalSummary {
+    pub(crate) kind: String,
+    pub(crate) scope: Option<String>,
+    pub(crate) breaking: bool,
+    pub(crate) description: String,
+}
+
+/// Parses `type(scope)!: description`; returns None for free-form summaries.
+pub(crate) fn parse_conventional_summary(summary: &str) -> Option<ConventionalSummary> {
+    let (head, description) = summary.split_once(':')?;
+    let description = description.trim();
+    if description.is_empty() {
+        return None;
+    }
+    let (head, breaking) = match head.strip_suffix('!') {
+        Some(head) => (head, true),
+        None => (head, false),
+    };
+    let (kind, scope) = match head.split_once('(') {
+        Some((kind, rest)) => {
+            let scope = rest.strip_suffix(')')?.trim();
+            (kind, (!scope.is_empty()).then(|| scope.to_string()))
+        }
+        None => (head, None),
+    };
+    if kind.is_empty() || !kind.chars().all(|ch| ch.is_ascii_alphabetic()) {
+        return None;
+    }
+    Some(ConventionalSummary {
+        kind: kind.to_ascii_lowercase(),
+        scope,
+        breaking,
+        description: description.to_string(),
+    })
+}
+
+fn message_has_breaking_footer(message: &str) -> bool {
+    message
+        .lines()
+        .any(|line| line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:"))
+}
+
+fn markdown_line(entry: &GitLogEntry) -> String {
+    let short_sha: String = entry.sha.chars().take(7).collect();
+    match parse_conventional_summary(&entry.summary) {
+        Some(parsed) => match parsed.scope {
+            Some(scope) => format!("- **{scope}:** {} ({short_sha})", parsed.description),
+            None => format!("- {} ({short_sha})", parsed.description),
+        },
+        None => format!("- {} ({short_sha})", entry.summary),
+    }
+}
+
+pub(crate) fn render_release_markdown(notes: &GitReleaseNotes) -> String {
+    let mut out = Vec::new();
+    if !notes.breaking.is_empty() {
+        out.push("## Breaking Changes".to_string());
+        out.extend(notes.breaking.iter().map(markdown_line));
+        out.push(String::new());
+    }
+    for section in &notes.sections {
+        out.push(format!("## {}", section.title));
+        out.extend(section.entries.iter().map(markdown_line));
+        out.push(String::new());
+    }
+    out.join("\n").trim_end().to_string()
+}
+
+/// Groups the commits reachable from `to` but not from `from` by
+/// conventional-commit type, in the order of `SECTIONS`.
+pub(crate) fn build_release_notes(
+    repo: &Repository,
+    from: Oid,
+    to: Oid,
+    from_label: &str,
+    to_label: &str,
+) -> Result<GitReleaseNotes, String> {
+    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
+    revwalk.push(to).map_err(|e| e.to_string())?;
+    revwalk.hide(from).map_err(|e| e.to_string())?;
+    revwalk
+        .set_sorting(Sort::TOPOLOGICAL | Sort::TIME)
+        .map_err(|e| e.to_string())?;
+
+    let mut grouped: Vec<(String, Vec<GitLogEntry>)> = Vec::new();
+    let mut breaking = Vec::new();
+    for oid_result in revwalk {
+        let oid = oid_result.map_err(|e| e.to_string())?;
+        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+        // Merge commits only repeat what the merged commits already say.
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        let has_footer =
+            message_has_breaking_footer(&String::from_utf8_lossy(commit.message_bytes()));
+        let entry = commit_to_entry(commit);
+        let parsed = parse_conventional_summary(&entry.summary);
+        if has_footer || parsed.as_ref().is_some_and(|parsed| parsed.breaking) {
+            breaking.push(entry.clone());
+        }
+        let kind = parsed
+            .map(|parsed| parsed.kind)
+            .filter(|kind| SECTIONS.iter().any(|(known, _)| known == kind))
+            .unwrap_or_else(|| OTHER_KIND.to_string());
+        match grouped.iter_mut().find(|(existing, _)| *existing == kind) {
+            Some((_, entries)) => entries.push(entry),
+            None => grouped.push((kind, vec![entry])),
+        }
+    }
+
+    let title_for = |kind: &str| {
+        SECTIONS
+            .iter()
+            .find(|(known, _)| *known == kind)
+            .map(|(_, title)| *title)
+            .unwrap_or(OTHER_TITLE)
+    };
+    let rank = |kind: &str| {
+        SECTIONS
+            .iter()
+            .position(|(known, _)| *known == kind)
+            .unwrap_or(SECTIONS.len())
+    };
+    grouped.sort_by_key(|(kind, _)| rank(kind));
+    let sections = grouped
+        .into_iter()
+        .map(|(kind, entries)| GitReleaseSection {
+            title: title_for(&kind).to_string(),
+            kind,
+            entries,
+        })
+        .collect();
+
+    let mut notes = GitReleaseNotes {
+        from: from_label.to_string(),
+        to: to_label.to_string(),
+        sections,
+        breaking,
+        markdown: String::new(),
+    };
+    notes.markdown = render_release_markdown(&notes);
+    Ok(notes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_conventional_summaries() {
+        assert_eq!(
+            parse_conventional_summary("feat(git)!: add tags"),
+            Some(ConventionalSummary {
+                kind: "feat".to_string(),
+                scope: Some("git".to_string()),
+                breaking: true,
+                description: "add tags".to_string(),
+            })
+        );
+        let fix = parse_conventional_summary("Fix: handle empty repo").expect("fix");
+        assert_eq!(fix.kind, "fix");
+        assert_eq!(fix.scope, None);
+        assert!(!fix.breaking);
+        assert_eq!(parse_conventional_summary("Merge branch 'main'"), None);
+        assert_eq!(parse_conventional_summary("docs: "), None);
+        assert_eq!(parse_conventional_summary("see http://x: y"), None);
+    }
+}
@@ -0,0 +1,164 @@
+use git2::{DiffDelta, FileMode, Oid, Repository, SubmoduleIgnore, SubmoduleStatus};
+
+use crate::types::{GitSubmoduleChange, GitSubmoduleInfo};
+use crate::utils::normalize_git_path;
+
+fn non_zero(oid: Option<Oid>) -> Option<String> {
+    oid.filter(|oid| !oid.is_zero()).map(|oid| oid.to_string())
+}
+
+fn is_dirty(status: SubmoduleStatus) -> bool {
+    status.intersects(
+        SubmoduleStatus::WD_INDEX_MODIFIED
+            | SubmoduleStatus::WD_WD_MODIFIED
+            | SubmoduleStatus::WD_UNTRACKED,
+    )
+}
+
+/// `scan_workdir` walks every submodule checkout to report `dirty`. That is
+/// too slow for status polls, which pass false and leave `dirty` unset.
+pub(crate) fn list_submodules(
+    repo: &Repository,
+    scan_workdir: bool,
+) -> Result<Vec<GitSubmoduleInfo>, String> {
+    let ignore = if scan_workdir {
+        SubmoduleIgnore::None
+    } else {
+        SubmoduleIgnore::Dirty
+    };
+    let submodules = repo.submodules().map_err(|e| e.to_string())?;
+    let mut results = Vec::with_capacity(submodules.len());
+    for submodule in submodules {
+        let path = normalize_git_path(&submodule.path().to_string_lossy());
+        let status = repo
+            .submodule_status(&path, ignore)
+            .unwrap_or(SubmoduleStatus::empty());
+        // The index wins over HEAD so a staged pointer bump counts as recorded.
+        let recorded_sha = non_zero(submodule.index_id().or(submodule.head_id()));
+        let checked_out_sha = non_zero(submodule.workdir_id());
+        results.push(GitSubmoduleInfo {
+            name: submodule.name().unwrap_or(&path).to_string(),
+            url: submodule.url().map(str::to_string),
+            branch: submodule.branch().map(str::to_string),
+            initialized: !status.contains(SubmoduleStatus::WD_UNINITIALIZED)
+                && checked_out_sha.is_some(),
+            out_of_sync: checked_out_sha.is_some() && recorded_sha != checked_out_sha,
+            dirty: scan_workdir.then(|| is_dirty(status)),
+            recorded_sha,
+            checked_out_sha,
+            path,
+        });
+    }
+    results.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(results)
+}
+
+/// Describes a gitlink delta; returns None for ordinary files. Working-tree
+/// dirt only applies when the new side of the delta is the working tree.
+pub(crate) fn submodule_change(
+    repo: &Repository,
+    delta: &DiffDelta,
+    check_workdir: bool,
+) -> Option<GitSubmoduleChange> {
+    let old_file = delta.old_file();
+    let new_file = delta.new_file();
+    if old_file.mode() != FileMode::Commit && new_file.mode() != FileMode::Commit {
+        return None;
+    }
+    let dirty = check_workdir
+        && new_file
+            .path()
+            .and_then(|path| path.to_str())
+            .and_then(|path| repo.submodule_status(path, SubmoduleIgnore::None).ok())
+            .is_some_and(is_dirty);
+    Some(GitSubmoduleChange {
+        old_sha: non_zero(Some(old_file.id())).filter(|_| old_file.mode() == FileMode::Commit),
+        new_sha: non_zero(Some(new_file.id())).filter(|_| new_file.mode() == FileMode::Commit),
+        dirty,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(["-c", "protocol.file.allow=always"])
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("run git");
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fn temp_dir(label: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "codex-monitor-submodule-{label}-{}",
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::create_dir_all(&root).expect("create temp dir");
+        root
+    }
+
+    #[test]
+    fn reports_recorded_and_checked_out_submodule_shas() {
+        let library = temp_dir("library");
+        git(&library, &["init", "-q"]);
+        std::fs::write(library.join("lib.txt"), "v1\n").expect("write lib");
+        git(&library, &["add", "."]);
+        git(&library, &["commit", "-q", "-m", "v1"]);
+
+        let app = temp_dir("app");
+        git(&app, &["init", "-q"]);
+        let library_url = library.to_string_lossy().to_string();
+        git(
+            &app,
+            &["submodule", "add", "-q", &library_url, "vendor/lib"],
+        );
+        git(&app, &["commit", "-q", "-m", "add submodule"]);
+
+        let repo = Repository::open(&app).expect("open app");
+        let listed = list_submodules(&repo, true).expect("submodules");
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].path, "vendor/lib");
+        assert!(listed[0].initialized);
+        assert!(!listed[0].out_of_sync);
+        assert_eq!(listed[0].dirty, Some(false));
+        let recorded = listed[0].recorded_sha.clone().expect("recorded sha");
+
+        let checkout = app.join("vendor/lib");
+        std::fs::write(checkout.join("lib.txt"), "v2\n").expect("write lib");
+        git(&checkout, &["commit", "-q", "-am", "v2"]);
+        std::fs::write(checkout.join("scratch.txt"), "wip\n").expect("write scratch");
+
+        let listed = list_submodules(&repo, true).expect("submodules");
+        assert!(listed[0].out_of_sync);
+        assert_eq!(listed[0].dirty, Some(true));
+        assert_eq!(listed[0].recorded_sha.as_deref(), Some(recorded.as_str()));
+        let quick = list_submodules(&repo, false).expect("submodules");
+        assert!(quick[0].out_of_sync);
+        assert_eq!(quick[0].dirty, None);
+        assert_eq!(quick[0].checked_out_sha, listed[0].checked_out_sha);
+
+        let head_tree = repo
+            .head()
+            .and_then(|head| head.peel_to_tree())
+            .expect("tree");
+        let diff = repo
+            .diff_tree_to_workdir_with_index(Some(&head_tree), None)
+            .expect("diff");
+        let delta = diff.deltas().next().expect("submodule delta");
+        let change = submodule_change(&repo, &delta, true).expect("gitlink change");
+        assert_eq!(change.old_sha.as_deref(), Some(recorded.as_str()));
+        assert_eq!(change.new_sha, listed[0].checked_out_sha);
+        assert!(change.dirty);
+    }
+}
@@ -28,11 +28,13 @@ pub(crate) fn commit_to_entry(commit: git2::Commit) -> GitLogEntry {
     let summary = commit.summary().unwrap_or("").to_string();
     let author = commit.author().name().unwrap_or("").to_string();
     let timestamp = commit.time().seconds();
+    let parents = commit.parent_ids().map(|oid| oid.to_string()).collect();
     GitLogEntry {
         sha: commit.id().to_string(),
         summary,
         author,
         timestamp,
+        parents,
     }
 }
 
@@ -131,26 +133,39 @@ mod tests {
     }
 }
 
-pub(crate) fn parse_github_repo(remote_url: &str) -> Option<String> {
+/// Splits a remote URL into its lowercased host and repository path. Handles
+/// `https://host/owner/repo.git`, `ssh://git@host:22/owner/repo` and the
+/// scp-like `git@host:owner/repo` form.
+pub(crate) fn parse_remote_url(remote_url: &str) -> Option<(String, String)> {
     let trimmed = remote_url.trim();
-    if trimmed.is_empty() {
-        return None;
-    }
-    let mut path = if trimmed.starts_with("git@github.com:") {
-        trimmed.trim_start_matches("git@github.com:").to_string()
-    } else if trimmed.starts_with("ssh://git@github.com/") {
-        trimmed.trim_start_matches("ssh://git@github.com/").to_string()
-    } else if let Some(index) = trimmed.find("github.com/") {
-        trimmed[index + "github.com/".len()..].to_string()
-    } else {
-        return None;
+    let (authority, path) = match trimmed.split_once("://") {
+        Some((_, rest)) => {
+            let (authority, path) = rest.split_once('/')?;
+            // Only URL authorities can carry a port.
+            let authority = authority.rsplit('@').next().unwrap_or(authority);
+            (authority.split(':').next().unwrap_or(authority), path)
+        }
+        None => {
+            let (authority, path) = trimmed.split_once(':')?;
+            if authority.contains('/') {
+                return None;
+            }
+            (authority.rsplit('@').next().unwrap_or(authority), path)
+        }
     };
-    path = path.trim_end_matches(".git").trim_end_matches('/').to_string();
-    if path.is_empty() {
-        None
-    } else {
-        Some(path)
+    let path = path
+        .trim_matches('/')
+        .trim_end_matches(".git")
+        .trim_end_matches('/');
+    if authority.is_empty() || !path.contains('/') {
+        return None;
     }
+    Some((authority.to_ascii_lowercase(), path.to_string()))
+}
+
+pub(crate) fn parse_github_repo(remote_url: &str) -> Option<String> {
+    let (host, path) = parse_remote_url(remote_url)?;
+    (host == "github.com" || host == "www.github.com").then_some(path)
 }
 
 pub(crate) fn resolve_git_root(entry: &WorkspaceEntry) -> Result<PathBuf, String> {
@@ -176,6 +191,11 @@ pub(crate) fn resolve_git_root(entry: &WorkspaceEntry) -> Result<PathBuf, String
     }
 }
 
+fn has_git_marker(path: &Path) -> bool {
+    let git_marker = path.join(".git");
+    git_marker.is_dir() || git_marker.is_file()
+}
+
 fn should_skip_dir(name: &str) -> bool {
     matches!(
         name,
@@ -208,6 +228,16 @@ pub(crate) fn list_git_roots(
                 if should_skip_dir(&name) {
                     return false;
                 }
+                // Nested repositories are listed but not descended into; their
+                // submodules belong to them, not to the scanned root.
+                if entry.depth() > 1
+                    && entry
+                        .path()
+                        .parent()
+                        .is_some_and(|parent| has_git_marker(parent))
+                {
+                    return false;
+                }
             }
             true
         })
@@ -225,8 +255,7 @@ pub(crate) fn list_git_roots(
             continue;
         }
         let candidate = entry.path();
-        let git_marker = candidate.join(".git");
-        if !git_marker.is_dir() && !git_marker.is_file() {
+        if !has_git_marker(candidate) {
             continue;
         }
         let rel = match candidate.strip_prefix(root) {
@@ -243,6 +272,23 @@ pub(crate) fn list_git_roots(
         }
     }
 
+    // The root's own submodules are always reported, however deep they sit.
+    if results.len() < max_results {
+        if let Ok(repo) = Repository::open(root) {
+            if let Ok(submodules) = repo.submodules() {
+                for submodule in submodules {
+                    let normalized = normalize_git_path(&submodule.path().to_string_lossy());
+                    if results.len() >= max_results {
+                        break;
+                    }
+                    if has_git_marker(&root.join(&normalized)) && seen.insert(normalized.clone()) {
+                        results.push(normalized);
+                    }
+                }
+            }
+        }
+    }
+
     results.sort();
     results
 }
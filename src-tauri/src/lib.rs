@@ -53,6 +53,7 @@ pub fn run() {
         .setup(|app| {
             let state = state::AppState::load(&app.handle());
             app.manage(state);
+            terminal::spawn_terminal_reaper(app.handle().clone());
             #[cfg(desktop)]
             {
                 app.handle()
@@ -76,6 +77,12 @@ pub fn run() {
             settings::get_codex_config_path,
             files::file_read,
             files::file_write,
+            files::list_workspace_directory,
+            files::read_workspace_text_file,
+            files::write_workspace_text_file,
+            files::create_workspace_entry,
+            files::rename_workspace_entry,
+            files::delete_workspace_entry,
             codex::get_config_model,
             menu::menu_set_accelerators,
             codex::codex_doctor,
@@ -84,8 +91,11 @@ pub fn run() {
             workspaces::add_workspace,
             workspaces::add_clone,
             workspaces::add_worktree,
+            workspaces::add_pr_worktree,
+            workspaces::start_issue_run,
             workspaces::worktree_setup_status,
             workspaces::worktree_setup_mark_ran,
+            workspaces::worktree_setup_run,
             workspaces::remove_workspace,
             workspaces::remove_worktree,
             workspaces::rename_worktree,
@@ -122,22 +132,79 @@ pub fn run() {
             git::unstage_git_file,
             git::revert_git_file,
             git::revert_git_all,
+            git::stage_git_hunks,
+            git::unstage_git_hunks,
+            git::revert_git_hunks,
             git::commit_git,
             git::push_git,
             git::pull_git,
             git::fetch_git,
             git::sync_git,
             git::get_github_issues,
+            git::get_github_issue,
             git::get_github_pull_requests,
             git::get_github_pull_request_diff,
             git::get_github_pull_request_comments,
+            git::get_github_rate_limit,
+            git::create_github_pull_request,
+            git::update_github_pull_request,
+            git::mark_github_pull_request_ready,
+            git::request_github_pull_request_reviewers,
+            git::get_github_pull_request_review_comments,
+            git::post_github_pull_request_comment,
+            git::post_github_review_comment,
+            git::reply_github_review_comment,
+            git::submit_github_pull_request_review,
+            git::get_github_checks,
+            git::get_github_check_logs,
+            git::watch_github_checks,
             workspaces::list_workspace_files,
             workspaces::read_workspace_file,
+            workspaces::search_workspace_files,
+            workspaces::cancel_workspace_search,
+            workspaces::find_workspace_paths,
             workspaces::open_workspace_in,
             workspaces::get_open_app_icon,
             git::list_git_branches,
             git::checkout_git_branch,
             git::create_git_branch,
+            git::list_git_stashes,
+            git::stash_git_push,
+            git::stash_git_apply,
+            git::stash_git_pop,
+            git::stash_git_drop,
+            git::get_git_stash_diff,
+            git::get_git_conflicts,
+            git::resolve_git_conflict,
+            git::abort_git_merge,
+            git::continue_git_merge,
+            git::abort_git_rebase,
+            git::continue_git_rebase,
+            git::get_git_range_diff,
+            git::get_git_branch_comparison,
+            git::get_git_file_log,
+            git::get_git_blame,
+            git::amend_git_commit,
+            git::fixup_git_commit,
+            git::autosquash_git_rebase,
+            git::squash_worktree_commits,
+            git::cherry_pick_git_commits,
+            git::revert_git_commit,
+            git::continue_git_operation,
+            git::abort_git_operation,
+            git::list_git_tags,
+            git::create_git_tag,
+            git::delete_git_tag,
+            git::push_git_tag,
+            git::get_git_release_notes,
+            git::delete_git_branch,
+            git::rename_git_branch,
+            git::set_git_branch_upstream,
+            git::merge_git_branch,
+            git::list_git_submodules,
+            git::update_git_submodules,
+            git::get_git_lfs_locks,
+            git::check_git_commit_hooks,
             codex::model_list,
             codex::account_rate_limits,
             codex::account_read,
@@ -254,6 +254,15 @@ async fn read_loop(
             "terminal-exit" => {
                 let _ = app.emit("terminal-exit", params);
             }
+            "worktree-setup-output" => {
+                let _ = app.emit("worktree-setup-output", params);
+            }
+            "github-checks-finished" => {
+                let _ = app.emit("github-checks-finished", params);
+            }
+            "workspace-search-matches" => {
+                let _ = app.emit("workspace-search-matches", params);
+            }
             _ => {}
         }
     }
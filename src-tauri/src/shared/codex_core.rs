@@ -260,6 +260,81 @@ pub(crate) async fn start_review_core(
         .await
 }
 
+fn response_error(response: &Value) -> Option<String> {
+    let error = response.get("error")?;
+    Some(
+        error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("Unknown error")
+            .to_string(),
+    )
+}
+
+async fn start_thread_id(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+) -> Result<String, String> {
+    let thread = start_thread_core(sessions, workspace_id).await?;
+    if let Some(error) = response_error(&thread) {
+        return Err(error);
+    }
+    thread
+        .get("result")
+        .and_then(|result| result.get("thread"))
+        .or_else(|| thread.get("thread"))
+        .and_then(|thread| thread.get("id"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| "Failed to get thread id from thread/start response.".to_string())
+}
+
+/// Starts a new thread in the workspace and sends `text` as its first
+/// message, returning the thread id.
+#[allow(dead_code)]
+pub(crate) async fn start_thread_with_message_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    text: String,
+) -> Result<String, String> {
+    let thread_id = start_thread_id(sessions, workspace_id.clone()).await?;
+    let turn = send_user_message_core(
+        sessions,
+        workspace_id,
+        thread_id.clone(),
+        text,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await?;
+    if let Some(error) = response_error(&turn) {
+        return Err(error);
+    }
+    Ok(thread_id)
+}
+
+/// Starts a new thread in the workspace and reviews its changes against
+/// `base_branch`, returning the thread id.
+#[allow(dead_code)]
+pub(crate) async fn start_base_branch_review_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    base_branch: String,
+    delivery: Option<String>,
+) -> Result<String, String> {
+    let thread_id = start_thread_id(sessions, workspace_id.clone()).await?;
+    let target = json!({ "type": "baseBranch", "branch": base_branch });
+    let review =
+        start_review_core(sessions, workspace_id, thread_id.clone(), target, delivery).await?;
+    if let Some(error) = response_error(&review) {
+        return Err(error);
+    }
+    Ok(thread_id)
+}
+
 pub(crate) async fn model_list_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     workspace_id: String,
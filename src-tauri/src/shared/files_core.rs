@@ -4,10 +4,14 @@ use std::path::PathBuf;
 use tokio::sync::Mutex;
 
 use crate::codex::home as codex_home;
+use crate::files::browser::{
+    create_workspace_entry, delete_workspace_entry, list_workspace_directory,
+    read_workspace_text_file, rename_workspace_entry, write_workspace_text_file,
+};
 use crate::files::io::TextFileResponse;
 use crate::files::ops::{read_with_policy, write_with_policy};
 use crate::files::policy::{policy_for, FileKind, FileScope};
-use crate::types::WorkspaceEntry;
+use crate::types::{WorkspaceDirEntry, WorkspaceEntry, WorkspaceFileStat, WorkspaceTextFile};
 
 fn resolve_default_codex_home() -> Result<PathBuf, String> {
     codex_home::resolve_default_codex_home()
@@ -62,3 +66,71 @@ pub(crate) async fn file_write_core(
     let root = resolve_root_core(workspaces, scope, workspace_id.as_deref()).await?;
     write_with_policy(&root, policy, &content)
 }
+
+pub(crate) async fn list_workspace_directory_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+    path: &str,
+) -> Result<Vec<WorkspaceDirEntry>, String> {
+    let root = resolve_workspace_root(workspaces, workspace_id).await?;
+    list_workspace_directory(&root, path)
+}
+
+pub(crate) async fn read_workspace_text_file_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+    path: &str,
+) -> Result<WorkspaceTextFile, String> {
+    let root = resolve_workspace_root(workspaces, workspace_id).await?;
+    read_workspace_text_file(&root, path)
+}
+
+pub(crate) async fn write_workspace_text_file_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+    path: &str,
+    content: &str,
+    encoding: Option<&str>,
+    expected_hash: Option<&str>,
+    expected_modified_ms: Option<i64>,
+) -> Result<WorkspaceFileStat, String> {
+    let root = resolve_workspace_root(workspaces, workspace_id).await?;
+    write_workspace_text_file(
+        &root,
+        path,
+        content,
+        encoding,
+        expected_hash,
+        expected_modified_ms,
+    )
+}
+
+pub(crate) async fn create_workspace_entry_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+    path: &str,
+    directory: bool,
+    content: Option<&str>,
+) -> Result<WorkspaceDirEntry, String> {
+    let root = resolve_workspace_root(workspaces, workspace_id).await?;
+    create_workspace_entry(&root, path, directory, content)
+}
+
+pub(crate) async fn rename_workspace_entry_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+    from: &str,
+    to: &str,
+) -> Result<WorkspaceDirEntry, String> {
+    let root = resolve_workspace_root(workspaces, workspace_id).await?;
+    rename_workspace_entry(&root, from, to)
+}
+
+pub(crate) async fn delete_workspace_entry_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+    path: &str,
+) -> Result<(), String> {
+    let root = resolve_workspace_root(workspaces, workspace_id).await?;
+    delete_workspace_entry(&root, path)
+}
@@ -1,6 +1,9 @@
 #![allow(dead_code)]
 
 use std::path::PathBuf;
+use std::process::Stdio;
+
+use tokio::io::AsyncWriteExt;
 
 use crate::shared::process_core::tokio_command;
 use crate::utils::{git_env_path, resolve_git_binary};
@@ -64,6 +67,37 @@ pub(crate) async fn run_git_command_bytes(
     Err(format_git_error(&output.stdout, &output.stderr))
 }
 
+pub(crate) async fn run_git_command_with_input(
+    repo_path: &PathBuf,
+    args: &[&str],
+    input: &[u8],
+) -> Result<String, String> {
+    let git_bin = resolve_git_binary().map_err(|err| format!("Failed to run git: {err}"))?;
+    let mut child = tokio_command(git_bin)
+        .args(args)
+        .current_dir(repo_path)
+        .env("PATH", git_env_path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Failed to run git: {err}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input)
+            .await
+            .map_err(|err| format!("Failed to write git input: {err}"))?;
+    }
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|err| format!("Failed to run git: {err}"))?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).trim().to_string());
+    }
+    Err(format_git_error(&output.stdout, &output.stderr))
+}
+
 pub(crate) async fn run_git_diff(repo_path: &PathBuf, args: &[&str]) -> Result<Vec<u8>, String> {
     let git_bin = resolve_git_binary().map_err(|err| format!("Failed to run git: {err}"))?;
     let output = tokio_command(git_bin)
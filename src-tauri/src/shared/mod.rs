@@ -3,6 +3,9 @@ pub(crate) mod codex_core;
 pub(crate) mod files_core;
 pub(crate) mod git_core;
 pub(crate) mod process_core;
+pub(crate) mod search_core;
 pub(crate) mod settings_core;
+pub(crate) mod terminal_core;
+pub(crate) mod watcher_core;
 pub(crate) mod worktree_core;
 pub(crate) mod workspaces_core;
@@ -0,0 +1,616 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+
+use ignore::overrides::{Override, OverrideBuilder};
+use ignore::WalkBuilder;
+use regex::{Regex, RegexBuilder};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::backend::events::WorkspaceSearchMatches;
+use crate::types::{
+    WorkspaceEntry, WorkspacePathMatch, WorkspaceSearchMatch, WorkspaceSearchQuery,
+    WorkspaceSearchSummary,
+};
+use crate::utils::normalize_git_path;
+
+const DEFAULT_MAX_RESULTS: usize = 2_000;
+const MAX_CONTEXT_LINES: usize = 10;
+/// Longest slice of a line a match carries; minified files would otherwise
+/// send whole megabyte lines per match.
+const MAX_MATCH_TEXT_CHARS: usize = 300;
+/// Characters kept before the match when its line is clipped.
+const MATCH_LEAD_CHARS: usize = 80;
+const MAX_SEARCH_FILE_BYTES: u64 = 2_000_000;
+const BINARY_SNIFF_BYTES: usize = 8_000;
+const DEFAULT_PATH_MATCH_LIMIT: usize = 50;
+const MAX_PATH_CANDIDATES: usize = 100_000;
+
+fn should_skip_dir(name: &str) -> bool {
+    matches!(
+        name,
+        ".git" | "node_modules" | "dist" | "target" | "release-artifacts"
+    )
+}
+
+fn active_searches() -> &'static std::sync::Mutex<HashMap<String, Arc<AtomicBool>>> {
+    static ACTIVE: OnceLock<std::sync::Mutex<HashMap<String, Arc<AtomicBool>>>> = OnceLock::new();
+    ACTIVE.get_or_init(|| std::sync::Mutex::new(HashMap::new()))
+}
+
+fn build_matcher(query: &WorkspaceSearchQuery) -> Result<Regex, String> {
+    if query.query.is_empty() {
+        return Err("Search query is required.".to_string());
+    }
+    let pattern = if query.regex {
+        query.query.clone()
+    } else {
+        regex::escape(&query.query)
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!query.case_sensitive)
+        .build()
+        .map_err(|err| format!("Invalid search pattern: {err}"))
+}
+
+fn build_overrides(
+    root: &Path,
+    include: &[String],
+    exclude: &[String],
+) -> Result<Override, String> {
+    let mut builder = OverrideBuilder::new(root);
+    for glob in include
+        .iter()
+        .map(|glob| glob.trim())
+        .filter(|glob| !glob.is_empty())
+    {
+        builder
+            .add(glob)
+            .map_err(|err| format!("Invalid include glob `{glob}`: {err}"))?;
+    }
+    for glob in exclude
+        .iter()
+        .map(|glob| glob.trim())
+        .filter(|glob| !glob.is_empty())
+    {
+        let glob = glob.trim_start_matches('!');
+        builder
+            .add(&format!("!{glob}"))
+            .map_err(|err| format!("Invalid exclude glob `{glob}`: {err}"))?;
+    }
+    builder
+        .build()
+        .map_err(|err| format!("Invalid search globs: {err}"))
+}
+
+fn workspace_walker(root: &Path, overrides: Override) -> ignore::Walk {
+    WalkBuilder::new(root)
+        .hidden(false)
+        .follow_links(false)
+        .require_git(false)
+        .overrides(overrides)
+        .filter_entry(|entry| {
+            if entry.depth() == 0 {
+                return true;
+            }
+            if entry.file_type().is_some_and(|ft| ft.is_dir()) {
+                let name = entry.file_name().to_string_lossy();
+                return !should_skip_dir(&name);
+            }
+            true
+        })
+        .build()
+}
+
+fn relative_path(root: &Path, path: &Path) -> Option<String> {
+    let rel_path = path.strip_prefix(root).ok()?;
+    let normalized = normalize_git_path(&rel_path.to_string_lossy());
+    (!normalized.is_empty()).then_some(normalized)
+}
+
+/// Reads a file for searching, skipping anything too large, binary or not
+/// valid UTF-8.
+fn read_searchable_file(path: &Path) -> Option<String> {
+    let metadata = std::fs::metadata(path).ok()?;
+    if metadata.len() > MAX_SEARCH_FILE_BYTES {
+        return None;
+    }
+    let mut buffer = Vec::with_capacity(metadata.len() as usize);
+    File::open(path).ok()?.read_to_end(&mut buffer).ok()?;
+    let sniff = &buffer[..buffer.len().min(BINARY_SNIFF_BYTES)];
+    if sniff.contains(&0) {
+        return None;
+    }
+    String::from_utf8(buffer).ok()
+}
+
+/// The `MAX_MATCH_TEXT_CHARS` characters of `line` starting at character
+/// `offset`.
+fn char_window(line: &str, offset: usize) -> String {
+    line.chars()
+        .skip(offset)
+        .take(MAX_MATCH_TEXT_CHARS)
+        .collect()
+}
+
+/// Where the window for a match starting at character `start` begins, so the
+/// match and some text before it stay visible.
+fn window_offset(line: &str, start: usize) -> usize {
+    let total = line.chars().count();
+    if total <= MAX_MATCH_TEXT_CHARS {
+        return 0;
+    }
+    start
+        .saturating_sub(MATCH_LEAD_CHARS)
+        .min(total - MAX_MATCH_TEXT_CHARS)
+}
+
+fn context_lines(lines: &[&str], range: std::ops::Range<usize>, offset: usize) -> Vec<String> {
+    lines[range]
+        .iter()
+        .map(|line| char_window(line, offset))
+        .collect()
+}
+
+fn search_file_content(
+    path: &str,
+    content: &str,
+    matcher: &Regex,
+    context: usize,
+    limit: usize,
+) -> Vec<WorkspaceSearchMatch> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut matches = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        for found in matcher.find_iter(line) {
+            if found.is_empty() {
+                continue;
+            }
+            if matches.len() >= limit {
+                return matches;
+            }
+            let before_start = index.saturating_sub(context);
+            let after_end = (index + 1 + context).min(lines.len());
+            let start = line[..found.start()].chars().count();
+            let offset = window_offset(line, start);
+            matches.push(WorkspaceSearchMatch {
+                path: path.to_string(),
+                line: index + 1,
+                column: start + 1,
+                length: found.as_str().chars().count(),
+                text: char_window(line, offset),
+                text_offset: offset,
+                context_before: context_lines(&lines, before_start..index, offset),
+                context_after: context_lines(&lines, index + 1..after_end, offset),
+            });
+        }
+    }
+    matches
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct SearchTotals {
+    pub(crate) match_count: usize,
+    pub(crate) file_count: usize,
+    pub(crate) files_searched: usize,
+    pub(crate) truncated: bool,
+    pub(crate) cancelled: bool,
+}
+
+/// Walks the workspace honouring `.gitignore` and the query globs, handing
+/// each file's matches to `emit` as soon as they are found.
+pub(crate) fn search_workspace_files_inner<F>(
+    root: &Path,
+    query: &WorkspaceSearchQuery,
+    cancel: &AtomicBool,
+    mut emit: F,
+) -> Result<SearchTotals, String>
+where
+    F: FnMut(Vec<WorkspaceSearchMatch>),
+{
+    let matcher = build_matcher(query)?;
+    let overrides = build_overrides(root, &query.include, &query.exclude)?;
+    let max_results = query.max_results.unwrap_or(DEFAULT_MAX_RESULTS).max(1);
+    let context = query.context_lines.unwrap_or(0).min(MAX_CONTEXT_LINES);
+
+    let mut totals = SearchTotals::default();
+    for entry in workspace_walker(root, overrides) {
+        if cancel.load(Ordering::Relaxed) {
+            totals.cancelled = true;
+            break;
+        }
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
+        if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+            continue;
+        }
+        let Some(path) = relative_path(root, entry.path()) else {
+            continue;
+        };
+        let Some(content) = read_searchable_file(entry.path()) else {
+            continue;
+        };
+        totals.files_searched += 1;
+        let remaining = max_results - totals.match_count;
+        // Ask for one extra match so hitting the limit exactly is not
+        // reported as truncated.
+        let mut matches = search_file_content(&path, &content, &matcher, context, remaining + 1);
+        if matches.len() > remaining {
+            matches.truncate(remaining);
+            totals.truncated = true;
+        }
+        if !matches.is_empty() {
+            totals.match_count += matches.len();
+            totals.file_count += 1;
+            emit(matches);
+        }
+        if totals.truncated {
+            break;
+        }
+    }
+    Ok(totals)
+}
+
+/// Runs a content search off the async runtime. The search can be stopped
+/// with [`cancel_workspace_search_core`] using the returned `searchId`.
+pub(crate) async fn search_workspace_files_core<F>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    search_id: Option<String>,
+    query: WorkspaceSearchQuery,
+    emit: F,
+) -> Result<WorkspaceSearchSummary, String>
+where
+    F: Fn(WorkspaceSearchMatches) + Send + 'static,
+{
+    let root = {
+        let workspaces = workspaces.lock().await;
+        let entry = workspaces
+            .get(&workspace_id)
+            .ok_or_else(|| "workspace not found".to_string())?;
+        PathBuf::from(&entry.path)
+    };
+    let search_id = search_id
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+    let cancel = Arc::new(AtomicBool::new(false));
+    if let Ok(mut active) = active_searches().lock() {
+        if let Some(previous) = active.insert(search_id.clone(), cancel.clone()) {
+            previous.store(true, Ordering::Relaxed);
+        }
+    }
+
+    let task_search_id = search_id.clone();
+    let task_cancel = cancel.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        search_workspace_files_inner(&root, &query, &task_cancel, |matches| {
+            emit(WorkspaceSearchMatches {
+                workspace_id: workspace_id.clone(),
+                search_id: task_search_id.clone(),
+                matches,
+            })
+        })
+    })
+    .await
+    .map_err(|err| format!("Search task failed: {err}"));
+
+    if let Ok(mut active) = active_searches().lock() {
+        if active
+            .get(&search_id)
+            .is_some_and(|current| Arc::ptr_eq(current, &cancel))
+        {
+            active.remove(&search_id);
+        }
+    }
+
+    let totals = result??;
+    Ok(WorkspaceSearchSummary {
+        search_id,
+        match_count: totals.match_count,
+        file_count: totals.file_count,
+        files_searched: totals.files_searched,
+        truncated: totals.truncated,
+        cancelled: totals.cancelled,
+    })
+}
+
+/// Returns whether a running search with this id was found and flagged.
+pub(crate) fn cancel_workspace_search_core(search_id: &str) -> bool {
+    active_searches()
+        .lock()
+        .ok()
+        .and_then(|active| active.get(search_id).cloned())
+        .map(|cancel| cancel.store(true, Ordering::Relaxed))
+        .is_some()
+}
+
+fn is_segment_start(previous: Option<char>) -> bool {
+    match previous {
+        None => true,
+        Some(ch) => matches!(ch, '/' | '_' | '-' | '.' | ' '),
+    }
+}
+
+/// Scores `query` as an in-order subsequence of `candidate`, rewarding
+/// consecutive runs and matches at the start of path segments. Returns the
+/// score and matched character positions, offset by `offset`.
+fn score_subsequence(
+    query: &[char],
+    candidate: &[char],
+    offset: usize,
+) -> Option<(i64, Vec<usize>)> {
+    let lower: Vec<char> = candidate
+        .iter()
+        .map(|ch| ch.to_lowercase().next().unwrap_or(*ch))
+        .collect();
+
+    // Find the end of the first full match, then walk back from it to get
+    // the tightest window, then match forward again inside that window.
+    let mut query_index = 0;
+    let mut end = None;
+    for (index, ch) in lower.iter().enumerate() {
+        if *ch == query[query_index] {
+            query_index += 1;
+            if query_index == query.len() {
+                end = Some(index);
+                break;
+            }
+        }
+    }
+    let end = end?;
+    let mut query_index = query.len();
+    let mut start = end;
+    for index in (0..=end).rev() {
+        if lower[index] == query[query_index - 1] {
+            query_index -= 1;
+            if query_index == 0 {
+                start = index;
+                break;
+            }
+        }
+    }
+
+    let mut positions = Vec::with_capacity(query.len());
+    let mut score: i64 = 0;
+    let mut query_index = 0;
+    let mut previous_match: Option<usize> = None;
+    for index in start..=end {
+        if query_index == query.len() {
+            break;
+        }
+        if lower[index] != query[query_index] {
+            continue;
+        }
+        score += 16;
+        if previous_match.is_some_and(|previous| previous + 1 == index) {
+            score += 12;
+        }
+        let previous_char = index.checked_sub(1).map(|prev| candidate[prev]);
+        if is_segment_start(previous_char) {
+            score += 10;
+        } else if candidate[index].is_uppercase()
+            && previous_char.is_some_and(|prev| prev.is_lowercase())
+        {
+            score += 8;
+        }
+        if let Some(previous) = previous_match {
+            score -= (index - previous - 1).min(8) as i64;
+        }
+        positions.push(index + offset);
+        previous_match = Some(index);
+        query_index += 1;
+    }
+    Some((score, positions))
+}
+
+/// Fuzzy-matches `query` against a workspace-relative path. Whitespace in the
+/// query is ignored and matching is case-insensitive.
+pub(crate) fn fuzzy_path_score(query: &str, path: &str) -> Option<(i64, Vec<usize>)> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .flat_map(|ch| ch.to_lowercase())
+        .collect();
+    if query.is_empty() {
+        return None;
+    }
+    let chars: Vec<char> = path.chars().collect();
+    let name_start = chars
+        .iter()
+        .rposition(|ch| *ch == '/')
+        .map(|index| index + 1)
+        .unwrap_or(0);
+
+    let full = score_subsequence(&query, &chars, 0);
+    let name = score_subsequence(&query, &chars[name_start..], name_start)
+        .map(|(score, positions)| (score + 24, positions));
+    let (score, positions) = match (full, name) {
+        (Some(full), Some(name)) if name.0 >= full.0 => name,
+        (Some(full), _) => full,
+        (None, _) => return None,
+    };
+    // Prefer shorter paths when everything else ties.
+    Some((score - (chars.len() as i64 / 8), positions))
+}
+
+pub(crate) fn find_workspace_paths_inner(
+    root: &Path,
+    query: &str,
+    limit: usize,
+) -> Vec<WorkspacePathMatch> {
+    let overrides = Override::empty();
+    let mut results: Vec<WorkspacePathMatch> = Vec::new();
+    for entry in workspace_walker(root, overrides).take(MAX_PATH_CANDIDATES) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
+        if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+            continue;
+        }
+        let Some(path) = relative_path(root, entry.path()) else {
+            continue;
+        };
+        if let Some((score, positions)) = fuzzy_path_score(query, &path) {
+            results.push(WorkspacePathMatch {
+                path,
+                score,
+                positions,
+            });
+        }
+    }
+    results.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.path.cmp(&b.path)));
+    results.truncate(limit);
+    results
+}
+
+pub(crate) async fn find_workspace_paths_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<WorkspacePathMatch>, String> {
+    let root = {
+        let workspaces = workspaces.lock().await;
+        let entry = workspaces
+            .get(workspace_id)
+            .ok_or_else(|| "workspace not found".to_string())?;
+        PathBuf::from(&entry.path)
+    };
+    let limit = limit.unwrap_or(DEFAULT_PATH_MATCH_LIMIT).max(1);
+    tokio::task::spawn_blocking(move || find_workspace_paths_inner(&root, &query, limit))
+        .await
+        .map_err(|err| format!("Path search failed: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_matcher, fuzzy_path_score, search_file_content, search_workspace_files_inner,
+        MATCH_LEAD_CHARS, MAX_MATCH_TEXT_CHARS,
+    };
+    use crate::types::WorkspaceSearchQuery;
+    use std::sync::atomic::AtomicBool;
+    use uuid::Uuid;
+
+    fn make_temp_dir() -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("codex-monitor-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("failed to create temp dir");
+        dir
+    }
+
+    fn query(text: &str) -> WorkspaceSearchQuery {
+        WorkspaceSearchQuery {
+            query: text.to_string(),
+            ..WorkspaceSearchQuery::default()
+        }
+    }
+
+    #[test]
+    fn search_reports_columns_and_context() {
+        let root = make_temp_dir();
+        std::fs::create_dir_all(root.join("src")).expect("create src");
+        std::fs::write(
+            root.join("src/lib.rs"),
+            "fn one() {}\nlet héllo = Needle;\nfn two() {}\n",
+        )
+        .expect("write lib.rs");
+        std::fs::write(root.join("notes.md"), "needle in notes\n").expect("write notes");
+
+        let mut search = query("needle");
+        search.include = vec!["*.rs".to_string()];
+        search.context_lines = Some(1);
+        let mut found = Vec::new();
+        let totals =
+            search_workspace_files_inner(&root, &search, &AtomicBool::new(false), |batch| {
+                found.extend(batch)
+            })
+            .expect("search");
+
+        assert_eq!(totals.match_count, 1);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].path, "src/lib.rs");
+        assert_eq!(found[0].line, 2);
+        assert_eq!(found[0].column, 13);
+        assert_eq!(found[0].length, 6);
+        assert_eq!(found[0].context_before, vec!["fn one() {}".to_string()]);
+        assert_eq!(found[0].context_after, vec!["fn two() {}".to_string()]);
+        assert_eq!(found[0].text_offset, 0);
+
+        let long_line = format!("{}needle{}", "a".repeat(1_000), "b".repeat(1_000));
+        let long = search_file_content(
+            "min.js",
+            &format!("{}\n{long_line}\n", "c".repeat(1_000)),
+            &build_matcher(&query("needle")).expect("matcher"),
+            1,
+            10,
+        );
+        assert_eq!(long.len(), 1);
+        assert_eq!(long[0].column, 1_001);
+        assert_eq!(long[0].text_offset, 1_000 - MATCH_LEAD_CHARS);
+        assert_eq!(long[0].text.chars().count(), MAX_MATCH_TEXT_CHARS);
+        assert_eq!(long[0].text.find("needle"), Some(MATCH_LEAD_CHARS));
+        assert_eq!(long[0].context_before, vec!["c".repeat(MATCH_LEAD_CHARS)]);
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn search_honours_case_regex_exclude_and_limits() {
+        let root = make_temp_dir();
+        std::fs::write(root.join(".gitignore"), "ignored.txt\n").expect("write gitignore");
+        std::fs::write(root.join("ignored.txt"), "TODO hidden\n").expect("write ignored");
+        std::fs::write(root.join("a.txt"), "TODO one\ntodo two\nTODO three\n").expect("write a");
+        std::fs::write(root.join("skip.log"), "TODO log\n").expect("write log");
+
+        let mut search = query("TODO \\w+");
+        search.regex = true;
+        search.case_sensitive = true;
+        search.exclude = vec!["*.log".to_string()];
+        let mut found = Vec::new();
+        let totals =
+            search_workspace_files_inner(&root, &search, &AtomicBool::new(false), |batch| {
+                found.extend(batch)
+            })
+            .expect("search");
+        assert_eq!(totals.match_count, 2);
+        assert!(found.iter().all(|item| item.path == "a.txt"));
+
+        search.max_results = Some(1);
+        let totals = search_workspace_files_inner(&root, &search, &AtomicBool::new(false), |_| {})
+            .expect("search");
+        assert_eq!(totals.match_count, 1);
+        assert!(totals.truncated);
+
+        search.max_results = Some(2);
+        let totals = search_workspace_files_inner(&root, &search, &AtomicBool::new(false), |_| {})
+            .expect("search");
+        assert_eq!(totals.match_count, 2);
+        assert!(!totals.truncated);
+
+        let totals = search_workspace_files_inner(&root, &search, &AtomicBool::new(true), |_| {})
+            .expect("search");
+        assert!(totals.cancelled);
+        assert_eq!(totals.match_count, 0);
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn fuzzy_path_score_prefers_file_names_and_tight_matches() {
+        let (name_score, positions) =
+            fuzzy_path_score("main", "src/services/main.ts").expect("match");
+        assert_eq!(positions, vec![13, 14, 15, 16]);
+        let (scattered_score, _) =
+            fuzzy_path_score("main", "src/models/address/index.ts").expect("match");
+        assert!(name_score > scattered_score);
+        assert!(fuzzy_path_score("xyz", "src/main.ts").is_none());
+        assert!(fuzzy_path_score("SM", "src/main.ts").is_some());
+    }
+}
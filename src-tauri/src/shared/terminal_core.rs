@@ -0,0 +1,193 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::types::AppSettings;
+
+// The daemon builds every shared module but opens no terminals, so each item
+// here allows dead code for that build.
+#[allow(dead_code)]
+pub(crate) const TERMINAL_EXIT_REASON_EXITED: &str = "exited";
+#[allow(dead_code)]
+pub(crate) const TERMINAL_EXIT_REASON_IDLE: &str = "idle";
+#[allow(dead_code)]
+pub(crate) const TERMINAL_EXIT_REASON_DISCONNECTED: &str = "disconnected";
+#[allow(dead_code)]
+pub(crate) const TERMINAL_REAPER_INTERVAL: Duration = Duration::from_secs(30);
+
+#[allow(dead_code)]
+pub(crate) struct TerminalActivity {
+    owner: Option<String>,
+    last_active_ms: AtomicU64,
+    exited: AtomicBool,
+}
+
+#[allow(dead_code)]
+impl TerminalActivity {
+    pub(crate) fn new(owner: Option<String>) -> Self {
+        Self {
+            owner,
+            last_active_ms: AtomicU64::new(now_ms()),
+            exited: AtomicBool::new(false),
+        }
+    }
+
+    pub(crate) fn owner(&self) -> Option<&str> {
+        self.owner.as_deref()
+    }
+
+    pub(crate) fn touch(&self) {
+        self.last_active_ms.store(now_ms(), Ordering::Relaxed);
+    }
+
+    pub(crate) fn idle_for(&self, now_ms: u64) -> Duration {
+        let last = self.last_active_ms.load(Ordering::Relaxed);
+        Duration::from_millis(now_ms.saturating_sub(last))
+    }
+
+    /// Returns true only for the first caller so each terminal reports a single exit.
+    pub(crate) fn mark_exited(&self) -> bool {
+        !self.exited.swap(true, Ordering::SeqCst)
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TerminalLimits {
+    pub(crate) max_per_workspace: Option<usize>,
+    pub(crate) max_total: Option<usize>,
+    pub(crate) idle_timeout: Option<Duration>,
+    pub(crate) close_on_disconnect: bool,
+}
+
+#[allow(dead_code)]
+impl TerminalLimits {
+    pub(crate) fn from_settings(settings: &AppSettings) -> Self {
+        let limit = |value: u32| (value > 0).then_some(value as usize);
+        Self {
+            max_per_workspace: limit(settings.terminal_max_per_workspace),
+            max_total: limit(settings.terminal_max_total),
+            idle_timeout: (settings.terminal_idle_timeout_minutes > 0).then(|| {
+                Duration::from_secs(u64::from(settings.terminal_idle_timeout_minutes) * 60)
+            }),
+            close_on_disconnect: settings.terminal_close_on_disconnect,
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub(crate) fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[allow(dead_code)]
+pub(crate) fn ensure_terminal_capacity<'a>(
+    keys: impl IntoIterator<Item = &'a String>,
+    workspace_id: &str,
+    limits: &TerminalLimits,
+) -> Result<(), String> {
+    let prefix = format!("{workspace_id}:");
+    let mut total = 0usize;
+    let mut in_workspace = 0usize;
+    for key in keys {
+        total += 1;
+        if key.starts_with(&prefix) {
+            in_workspace += 1;
+        }
+    }
+    if let Some(max) = limits.max_per_workspace {
+        if in_workspace >= max {
+            return Err(format!(
+                "Terminal limit reached for this workspace ({max} open)."
+            ));
+        }
+    }
+    if let Some(max) = limits.max_total {
+        if total >= max {
+            return Err(format!("Terminal limit reached ({max} open)."));
+        }
+    }
+    Ok(())
+}
+
+#[allow(dead_code)]
+pub(crate) fn idle_terminal_keys<'a>(
+    sessions: impl IntoIterator<Item = (&'a String, &'a TerminalActivity)>,
+    idle_timeout: Duration,
+    now_ms: u64,
+) -> Vec<String> {
+    sessions
+        .into_iter()
+        .filter(|(_, activity)| activity.idle_for(now_ms) >= idle_timeout)
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+#[allow(dead_code)]
+pub(crate) fn owned_terminal_keys<'a>(
+    sessions: impl IntoIterator<Item = (&'a String, &'a TerminalActivity)>,
+    owner: &str,
+) -> Vec<String> {
+    sessions
+        .into_iter()
+        .filter(|(_, activity)| activity.owner() == Some(owner))
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+#[allow(dead_code)]
+pub(crate) fn split_terminal_key(key: &str) -> Option<(&str, &str)> {
+    key.split_once(':')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(per_workspace: Option<usize>, total: Option<usize>) -> TerminalLimits {
+        TerminalLimits {
+            max_per_workspace: per_workspace,
+            max_total: total,
+            idle_timeout: None,
+            close_on_disconnect: false,
+        }
+    }
+
+    #[test]
+    fn enforces_per_workspace_and_total_caps() {
+        let keys = vec![
+            "ws-1:a".to_string(),
+            "ws-1:b".to_string(),
+            "ws-2:a".to_string(),
+        ];
+        assert!(ensure_terminal_capacity(&keys, "ws-1", &limits(Some(3), None)).is_ok());
+        assert!(ensure_terminal_capacity(&keys, "ws-1", &limits(Some(2), None)).is_err());
+        assert!(ensure_terminal_capacity(&keys, "ws-2", &limits(Some(2), Some(3))).is_err());
+        assert!(ensure_terminal_capacity(&keys, "ws-3", &limits(None, None)).is_ok());
+    }
+
+    #[test]
+    fn selects_idle_and_owned_terminals() {
+        let fresh = TerminalActivity::new(Some("client-1".to_string()));
+        let stale = TerminalActivity::new(Some("client-2".to_string()));
+        stale.last_active_ms.store(0, Ordering::Relaxed);
+        let fresh_key = "ws:fresh".to_string();
+        let stale_key = "ws:stale".to_string();
+        let sessions = vec![(&fresh_key, &fresh), (&stale_key, &stale)];
+
+        let idle = idle_terminal_keys(sessions.clone(), Duration::from_secs(60), now_ms());
+        assert_eq!(idle, vec![stale_key.clone()]);
+
+        let owned = owned_terminal_keys(sessions, "client-1");
+        assert_eq!(owned, vec![fresh_key]);
+    }
+
+    #[test]
+    fn reports_exit_once() {
+        let activity = TerminalActivity::new(None);
+        assert!(activity.mark_exited());
+        assert!(!activity.mark_exited());
+    }
+}
@@ -0,0 +1,491 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use git2::Repository;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::{Match, WalkBuilder};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde_json::json;
+
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::utils::normalize_git_path;
+
+const DEBOUNCE_QUIET: Duration = Duration::from_millis(250);
+const DEBOUNCE_MAX_DELAY: Duration = Duration::from_secs(2);
+const MAX_WATCHED_DIRS: usize = 20_000;
+const MAX_REPORTED_PATHS: usize = 500;
+/// Raw notify events buffered between the watcher and the debounce thread.
+/// Overflow drops events and forces a rescan instead of growing without bound.
+const EVENT_QUEUE_CAPACITY: usize = 4096;
+/// Files in the git dir whose changes can alter `get_git_status`.
+const GIT_STATE_FILES: &[&str] = &[
+    "HEAD",
+    "index",
+    "MERGE_HEAD",
+    "ORIG_HEAD",
+    "CHERRY_PICK_HEAD",
+    "REVERT_HEAD",
+    "REBASE_HEAD",
+    "packed-refs",
+];
+
+fn should_skip_dir(name: &str) -> bool {
+    matches!(
+        name,
+        ".git" | "node_modules" | "dist" | "target" | "release-artifacts"
+    )
+}
+
+struct WatcherHandle {
+    root: PathBuf,
+    /// Set to stop the watcher thread; the thread also sets it when it exits.
+    stop: Arc<AtomicBool>,
+}
+
+fn active_watchers() -> &'static Mutex<HashMap<String, WatcherHandle>> {
+    static WATCHERS: OnceLock<Mutex<HashMap<String, WatcherHandle>>> = OnceLock::new();
+    WATCHERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+#[derive(Default)]
+struct ChangeBatch {
+    paths: BTreeSet<String>,
+    git_status: bool,
+    reload_ignores: bool,
+    overflowed: bool,
+}
+
+struct WorkspaceWatch {
+    root: PathBuf,
+    git_dir: Option<PathBuf>,
+    watcher: RecommendedWatcher,
+    /// Ignore files ordered deepest directory first.
+    ignores: Vec<Gitignore>,
+    watched_dirs: HashSet<PathBuf>,
+}
+
+impl WorkspaceWatch {
+    fn start(
+        root: &Path,
+        tx: SyncSender<notify::Result<Event>>,
+        overflowed: Arc<AtomicBool>,
+    ) -> Result<Self, String> {
+        let root = root
+            .canonicalize()
+            .map_err(|err| format!("Failed to resolve workspace root: {err}"))?;
+        let watcher = notify::recommended_watcher(move |event| {
+            if let Err(TrySendError::Full(_)) = tx.try_send(event) {
+                overflowed.store(true, Ordering::Relaxed);
+            }
+        })
+        .map_err(|err| format!("Failed to create file watcher: {err}"))?;
+        let git_dir = Repository::discover(&root)
+            .ok()
+            .and_then(|repo| repo.path().canonicalize().ok());
+        let mut watch = Self {
+            root,
+            git_dir,
+            watcher,
+            ignores: Vec::new(),
+            watched_dirs: HashSet::new(),
+        };
+        watch.reload_ignores();
+        let root = watch.root.clone();
+        watch.watch_tree(&root);
+        if let Some(git_dir) = watch.git_dir.clone() {
+            // Only the top of the git dir and refs matter; objects churn constantly.
+            let _ = watch.watcher.watch(&git_dir, RecursiveMode::NonRecursive);
+            let refs = git_dir.join("refs");
+            if refs.is_dir() {
+                let _ = watch.watcher.watch(&refs, RecursiveMode::Recursive);
+            }
+        }
+        Ok(watch)
+    }
+
+    fn walker(&self, dir: &Path) -> ignore::Walk {
+        WalkBuilder::new(dir)
+            .hidden(false)
+            .follow_links(false)
+            .require_git(false)
+            .filter_entry(|entry| {
+                if entry.depth() == 0 {
+                    return true;
+                }
+                if entry.file_type().is_some_and(|ft| ft.is_dir()) {
+                    let name = entry.file_name().to_string_lossy();
+                    return !should_skip_dir(&name);
+                }
+                true
+            })
+            .build()
+    }
+
+    /// Adds a non-recursive watch for every directory the walker would
+    /// visit, so ignored trees such as build output are never watched.
+    fn watch_tree(&mut self, dir: &Path) {
+        let dirs: Vec<PathBuf> = self
+            .walker(dir)
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_some_and(|ft| ft.is_dir()))
+            .map(|entry| entry.into_path())
+            .collect();
+        for dir in dirs {
+            if self.watched_dirs.len() >= MAX_WATCHED_DIRS {
+                eprintln!(
+                    "watch_workspace: {} has more than {MAX_WATCHED_DIRS} directories; some changes will be missed",
+                    self.root.display()
+                );
+                return;
+            }
+            if self.watched_dirs.contains(&dir) {
+                continue;
+            }
+            if self
+                .watcher
+                .watch(&dir, RecursiveMode::NonRecursive)
+                .is_ok()
+            {
+                self.watched_dirs.insert(dir);
+            }
+        }
+    }
+
+    fn reload_ignores(&mut self) {
+        let mut ignores = Vec::new();
+        let mut root_builder = GitignoreBuilder::new(&self.root);
+        root_builder.add(self.root.join(".gitignore"));
+        if let Some(git_dir) = &self.git_dir {
+            root_builder.add(git_dir.join("info").join("exclude"));
+        }
+        if let Ok(root_ignore) = root_builder.build() {
+            ignores.push(root_ignore);
+        }
+        let nested: Vec<PathBuf> = self
+            .walker(&self.root)
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.depth() > 1 && entry.file_name() == ".gitignore")
+            .map(|entry| entry.into_path())
+            .collect();
+        for path in nested {
+            let (ignore, _) = Gitignore::new(&path);
+            ignores.push(ignore);
+        }
+        ignores.sort_by_key(|ignore| std::cmp::Reverse(ignore.path().components().count()));
+        self.ignores = ignores;
+    }
+
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return true;
+        };
+        if relative
+            .components()
+            .any(|component| should_skip_dir(&component.as_os_str().to_string_lossy()))
+        {
+            return true;
+        }
+        for ignore in &self.ignores {
+            if !path.starts_with(ignore.path()) {
+                continue;
+            }
+            match ignore.matched_path_or_any_parents(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+
+    fn is_git_state_path(&self, path: &Path) -> Option<bool> {
+        let git_dir = self.git_dir.as_ref()?;
+        let relative = path.strip_prefix(git_dir).ok()?;
+        if path.extension().is_some_and(|ext| ext == "lock") {
+            return Some(false);
+        }
+        let relevant = relative.starts_with("refs")
+            || relative
+                .to_str()
+                .is_some_and(|name| GIT_STATE_FILES.contains(&name));
+        Some(relevant)
+    }
+
+    fn absorb(&mut self, event: notify::Result<Event>, batch: &mut ChangeBatch) {
+        let Ok(event) = event else {
+            return;
+        };
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        for path in &event.paths {
+            if let Some(relevant) = self.is_git_state_path(path) {
+                batch.git_status |= relevant;
+                continue;
+            }
+            let is_dir = path.is_dir();
+            if self.is_ignored(path, is_dir) {
+                continue;
+            }
+            if path.file_name().is_some_and(|name| name == ".gitignore") {
+                batch.reload_ignores = true;
+            }
+            if is_dir && matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                self.watch_tree(path);
+            }
+            if !is_dir && !path.exists() {
+                self.watched_dirs.remove(path);
+            }
+            if let Ok(relative) = path.strip_prefix(&self.root) {
+                let relative = normalize_git_path(&relative.to_string_lossy());
+                if !relative.is_empty() {
+                    batch.paths.insert(relative);
+                    batch.git_status |= self.git_dir.is_some();
+                }
+            }
+        }
+    }
+}
+
+fn emit_batch<E: EventSink>(event_sink: &E, workspace_id: &str, batch: ChangeBatch) {
+    if !batch.paths.is_empty() || batch.overflowed {
+        let truncated = batch.overflowed || batch.paths.len() > MAX_REPORTED_PATHS;
+        let paths: Vec<String> = batch.paths.into_iter().take(MAX_REPORTED_PATHS).collect();
+        event_sink.emit_app_server_event(AppServerEvent {
+            workspace_id: workspace_id.to_string(),
+            message: json!({
+                "method": "workspace/filesChanged",
+                "params": {
+                    "workspaceId": workspace_id,
+                    "paths": paths,
+                    "truncated": truncated,
+                },
+            }),
+        });
+    }
+    if batch.git_status {
+        event_sink.emit_app_server_event(AppServerEvent {
+            workspace_id: workspace_id.to_string(),
+            message: json!({
+                "method": "git/statusChanged",
+                "params": { "workspaceId": workspace_id },
+            }),
+        });
+    }
+}
+
+/// Runs on a dedicated thread: directory walks and `.gitignore` reloads can
+/// take seconds on large trees and must not hold up async runtime workers.
+fn run_watch_loop<E: EventSink>(
+    workspace_id: &str,
+    root: &Path,
+    event_sink: &E,
+    stop: &AtomicBool,
+) -> Result<(), String> {
+    let (tx, rx): (_, Receiver<notify::Result<Event>>) = mpsc::sync_channel(EVENT_QUEUE_CAPACITY);
+    let overflowed = Arc::new(AtomicBool::new(false));
+    let mut watch = WorkspaceWatch::start(root, tx, overflowed.clone())?;
+
+    while !stop.load(Ordering::Relaxed) {
+        let first = match rx.recv_timeout(DEBOUNCE_QUIET) {
+            Ok(event) => Some(event),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        if first.is_none() && !overflowed.load(Ordering::Relaxed) {
+            continue;
+        }
+        let mut batch = ChangeBatch::default();
+        if let Some(event) = first {
+            watch.absorb(event, &mut batch);
+        }
+        let deadline = Instant::now() + DEBOUNCE_MAX_DELAY;
+        while Instant::now() < deadline {
+            if stop.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            match rx.recv_timeout(DEBOUNCE_QUIET) {
+                Ok(event) => watch.absorb(event, &mut batch),
+                Err(_) => break,
+            }
+        }
+        if overflowed.swap(false, Ordering::Relaxed) {
+            // Events were dropped, so new directories may have gone unwatched.
+            batch.overflowed = true;
+            batch.git_status |= watch.git_dir.is_some();
+            let root = watch.root.clone();
+            watch.watch_tree(&root);
+        }
+        if batch.reload_ignores {
+            watch.reload_ignores();
+        }
+        emit_batch(event_sink, workspace_id, batch);
+    }
+    Ok(())
+}
+
+/// Watches a connected workspace and emits debounced `workspace/filesChanged`
+/// and `git/statusChanged` app-server events. Calling it again for the same
+/// root is a no-op; a different root replaces the previous watcher.
+pub(crate) fn watch_workspace<E: EventSink>(workspace_id: String, root: PathBuf, event_sink: E) {
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let Ok(mut watchers) = active_watchers().lock() else {
+            return;
+        };
+        if watchers
+            .get(&workspace_id)
+            .is_some_and(|handle| handle.root == root && !handle.stop.load(Ordering::Relaxed))
+        {
+            return;
+        }
+        if let Some(previous) = watchers.insert(
+            workspace_id.clone(),
+            WatcherHandle {
+                root: root.clone(),
+                stop: stop.clone(),
+            },
+        ) {
+            previous.stop.store(true, Ordering::Relaxed);
+        }
+    }
+
+    let spawned = std::thread::Builder::new()
+        .name(format!("workspace-watcher-{workspace_id}"))
+        .spawn({
+            let stop = stop.clone();
+            move || {
+                if let Err(error) = run_watch_loop(&workspace_id, &root, &event_sink, &stop) {
+                    eprintln!("watch_workspace: {workspace_id}: {error}");
+                }
+                stop.store(true, Ordering::Relaxed);
+            }
+        });
+    if let Err(error) = spawned {
+        eprintln!("watch_workspace: failed to start watcher thread: {error}");
+        stop.store(true, Ordering::Relaxed);
+    }
+}
+
+pub(crate) fn unwatch_workspace(workspace_id: &str) {
+    let handle = active_watchers()
+        .lock()
+        .ok()
+        .and_then(|mut watchers| watchers.remove(workspace_id));
+    if let Some(handle) = handle {
+        handle.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{unwatch_workspace, watch_workspace};
+    use crate::backend::events::{
+        AppServerEvent, EventSink, GitHubChecksFinished, TerminalExit, TerminalOutput,
+        WorkspaceSearchMatches, WorktreeSetupOutput,
+    };
+    use serde_json::Value;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+    use uuid::Uuid;
+
+    #[derive(Clone, Default)]
+    struct RecordingEventSink {
+        messages: Arc<Mutex<Vec<Value>>>,
+    }
+
+    impl EventSink for RecordingEventSink {
+        fn emit_app_server_event(&self, event: AppServerEvent) {
+            self.messages.lock().unwrap().push(event.message);
+        }
+        fn emit_terminal_output(&self, _event: TerminalOutput) {}
+        fn emit_terminal_exit(&self, _event: TerminalExit) {}
+        fn emit_worktree_setup_output(&self, _event: WorktreeSetupOutput) {}
+        fn emit_github_checks_finished(&self, _event: GitHubChecksFinished) {}
+        fn emit_workspace_search_matches(&self, _event: WorkspaceSearchMatches) {}
+    }
+
+    impl RecordingEventSink {
+        fn changed_paths(&self) -> Vec<String> {
+            self.messages
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|message| message["method"] == "workspace/filesChanged")
+                .flat_map(|message| {
+                    message["params"]["paths"]
+                        .as_array()
+                        .cloned()
+                        .unwrap_or_default()
+                })
+                .filter_map(|path| path.as_str().map(str::to_string))
+                .collect()
+        }
+
+        fn saw_git_status(&self) -> bool {
+            self.messages
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|message| message["method"] == "git/statusChanged")
+        }
+    }
+
+    fn wait_until(mut condition: impl FnMut() -> bool) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while Instant::now() < deadline {
+            if condition() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        false
+    }
+
+    #[test]
+    fn reports_debounced_changes_and_skips_ignored_files() {
+        let root = std::env::temp_dir().join(format!("codex-monitor-watch-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(root.join("src")).expect("create src");
+        git2::Repository::init(&root).expect("init repo");
+        std::fs::write(root.join(".gitignore"), "*.log\n").expect("write gitignore");
+        let workspace_id = Uuid::new_v4().to_string();
+        let sink = RecordingEventSink::default();
+
+        watch_workspace(workspace_id.clone(), root.clone(), sink.clone());
+        // Touch a probe until the watcher has registered its directories.
+        let ready = wait_until(|| {
+            std::fs::write(root.join("ready.txt"), "ready").expect("write probe");
+            sink.changed_paths().contains(&"ready.txt".to_string())
+        });
+        assert!(ready, "watcher never reported the probe file");
+
+        std::fs::write(root.join("src/lib.rs"), "one").expect("write lib");
+        std::fs::write(root.join("src/lib.rs"), "two").expect("rewrite lib");
+        std::fs::write(root.join("debug.log"), "noise").expect("write log");
+        std::fs::create_dir_all(root.join("src/nested")).expect("create nested");
+        assert!(wait_until(|| sink
+            .changed_paths()
+            .contains(&"src/nested".to_string())));
+        std::fs::write(root.join("src/nested/mod.rs"), "mod").expect("write nested");
+        assert!(wait_until(|| sink
+            .changed_paths()
+            .contains(&"src/nested/mod.rs".to_string())));
+        unwatch_workspace(&workspace_id);
+
+        let paths = sink.changed_paths();
+        assert!(paths.contains(&"src/lib.rs".to_string()));
+        assert!(!paths.iter().any(|path| path.ends_with(".log")));
+        assert_eq!(
+            paths.iter().filter(|path| *path == "src/lib.rs").count(),
+            1,
+            "rapid writes should be coalesced"
+        );
+        assert!(sink.saw_git_status());
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
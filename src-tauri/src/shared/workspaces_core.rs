@@ -1,23 +1,30 @@
 use std::collections::HashMap;
 use std::future::Future;
 use std::path::PathBuf;
+use std::process::Stdio;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use tokio::sync::Mutex;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::sync::{mpsc, Mutex};
 
 use crate::backend::app_server::WorkspaceSession;
+use crate::backend::events::{EventSink, WorktreeSetupOutput};
 use crate::codex::args::resolve_workspace_codex_args;
 use crate::codex::home::resolve_workspace_codex_home;
-use crate::shared::process_core::kill_child_process_tree;
+use crate::shared::process_core::{kill_child_process_tree, tokio_command};
+use crate::shared::watcher_core;
 use crate::storage::write_workspaces;
 use crate::types::{
-    AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeInfo,
-    WorktreeSetupStatus,
+    AppSettings, WorkspaceEntry, WorkspaceInfo, WorkspaceIssueLink, WorkspaceKind,
+    WorkspaceSettings, WorktreeInfo, WorktreeSetupRunResult, WorktreeSetupStatus,
 };
 use uuid::Uuid;
 
 pub(crate) const WORKTREE_SETUP_MARKERS_DIR: &str = "worktree-setup";
 pub(crate) const WORKTREE_SETUP_MARKER_EXT: &str = "ran";
+pub(crate) const WORKTREE_SETUP_LOG_EXT: &str = "log";
+const WORKTREE_SETUP_DEFAULT_TIMEOUT_SECS: u64 = 15 * 60;
 const AGENTS_MD_FILE_NAME: &str = "AGENTS.md";
 
 fn copy_agents_md_from_parent_to_worktree(
@@ -71,6 +78,12 @@ pub(crate) fn worktree_setup_marker_path(data_dir: &PathBuf, workspace_id: &str)
         .join(format!("{workspace_id}.{WORKTREE_SETUP_MARKER_EXT}"))
 }
 
+pub(crate) fn worktree_setup_log_path(data_dir: &PathBuf, workspace_id: &str) -> PathBuf {
+    data_dir
+        .join(WORKTREE_SETUP_MARKERS_DIR)
+        .join(format!("{workspace_id}.{WORKTREE_SETUP_LOG_EXT}"))
+}
+
 pub(crate) fn is_workspace_path_dir_core(path: &str) -> bool {
     PathBuf::from(path).is_dir()
 }
@@ -167,7 +180,10 @@ pub(crate) async fn worktree_setup_mark_ran_core(
     if !entry.kind.is_worktree() {
         return Err("Not a worktree workspace.".to_string());
     }
-    let marker_path = worktree_setup_marker_path(data_dir, &entry.id);
+    write_worktree_setup_marker(&worktree_setup_marker_path(data_dir, &entry.id))
+}
+
+fn write_worktree_setup_marker(marker_path: &PathBuf) -> Result<(), String> {
     if let Some(parent) = marker_path.parent() {
         std::fs::create_dir_all(parent)
             .map_err(|err| format!("Failed to prepare worktree marker directory: {err}"))?;
@@ -176,11 +192,174 @@ pub(crate) async fn worktree_setup_mark_ran_core(
         .duration_since(std::time::UNIX_EPOCH)
         .map(|duration| duration.as_secs())
         .unwrap_or(0);
-    std::fs::write(&marker_path, format!("ran_at={ran_at}\n"))
+    std::fs::write(marker_path, format!("ran_at={ran_at}\n"))
         .map_err(|err| format!("Failed to write worktree setup marker: {err}"))?;
     Ok(())
 }
 
+fn worktree_setup_command(script: &str) -> tokio::process::Command {
+    #[cfg(windows)]
+    {
+        let mut command = tokio_command("cmd");
+        command.arg("/C").arg(script);
+        command
+    }
+    #[cfg(not(windows))]
+    {
+        let shell = std::env::var("SHELL")
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .unwrap_or_else(|| "/bin/sh".to_string());
+        let mut command = tokio_command(shell);
+        command.arg("-lc").arg(script);
+        command
+    }
+}
+
+fn spawn_worktree_setup_reader<R>(
+    reader: Option<R>,
+    stream: &'static str,
+    tx: mpsc::UnboundedSender<(&'static str, String)>,
+) where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    let Some(mut reader) = reader else {
+        return;
+    };
+    tokio::spawn(async move {
+        let mut buffer = [0u8; 4096];
+        loop {
+            match reader.read(&mut buffer).await {
+                Ok(0) | Err(_) => break,
+                Ok(count) => {
+                    let data = String::from_utf8_lossy(&buffer[..count]).to_string();
+                    if tx.send((stream, data)).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+pub(crate) async fn worktree_setup_run_core<E: EventSink>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+    data_dir: &PathBuf,
+    timeout_secs: Option<u64>,
+    event_sink: E,
+) -> Result<WorktreeSetupRunResult, String> {
+    let entry = {
+        let workspaces = workspaces.lock().await;
+        workspaces
+            .get(workspace_id)
+            .cloned()
+            .ok_or_else(|| "workspace not found".to_string())?
+    };
+    if !entry.kind.is_worktree() {
+        return Err("Not a worktree workspace.".to_string());
+    }
+    let script = normalize_setup_script(entry.settings.worktree_setup_script.clone())
+        .ok_or_else(|| "No worktree setup script configured.".to_string())?;
+
+    let marker_path = worktree_setup_marker_path(data_dir, &entry.id);
+    let log_path = worktree_setup_log_path(data_dir, &entry.id);
+    if let Some(parent) = log_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to prepare worktree marker directory: {err}"))?;
+    }
+
+    let mut command = worktree_setup_command(&script);
+    command
+        .current_dir(&entry.path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let started_at = Instant::now();
+    let mut child = command
+        .spawn()
+        .map_err(|err| format!("Failed to run worktree setup script: {err}"))?;
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    spawn_worktree_setup_reader(child.stdout.take(), "stdout", tx.clone());
+    spawn_worktree_setup_reader(child.stderr.take(), "stderr", tx);
+
+    let timeout = Duration::from_secs(
+        timeout_secs
+            .unwrap_or(WORKTREE_SETUP_DEFAULT_TIMEOUT_SECS)
+            .max(1),
+    );
+    let deadline = tokio::time::sleep(timeout);
+    tokio::pin!(deadline);
+
+    let mut log = format!("$ {script}\n");
+    let mut timed_out = false;
+    loop {
+        tokio::select! {
+            chunk = rx.recv() => match chunk {
+                Some((stream, data)) => {
+                    log.push_str(&data);
+                    event_sink.emit_worktree_setup_output(WorktreeSetupOutput {
+                        workspace_id: entry.id.clone(),
+                        stream: stream.to_string(),
+                        data,
+                    });
+                }
+                None => break,
+            },
+            _ = &mut deadline => {
+                timed_out = true;
+                kill_child_process_tree(&mut child).await;
+                break;
+            }
+        }
+    }
+
+    let exit_code = if timed_out {
+        None
+    } else {
+        tokio::select! {
+            status = child.wait() => status
+                .map_err(|err| format!("Failed to wait for worktree setup script: {err}"))?
+                .code(),
+            _ = &mut deadline => {
+                timed_out = true;
+                kill_child_process_tree(&mut child).await;
+                None
+            }
+        }
+    };
+    let success = !timed_out && exit_code == Some(0);
+    let duration_ms = started_at.elapsed().as_millis() as u64;
+
+    if !log.ends_with('\n') {
+        log.push('\n');
+    }
+    if timed_out {
+        log.push_str(&format!("[timed out after {}s]\n", timeout.as_secs()));
+    } else {
+        match exit_code {
+            Some(code) => log.push_str(&format!("[exit code {code}]\n")),
+            None => log.push_str("[terminated by signal]\n"),
+        }
+    }
+    std::fs::write(&log_path, log)
+        .map_err(|err| format!("Failed to write worktree setup log: {err}"))?;
+
+    if success {
+        write_worktree_setup_marker(&marker_path)?;
+    }
+
+    Ok(WorktreeSetupRunResult {
+        success,
+        exit_code,
+        timed_out,
+        log_path: log_path.to_string_lossy().to_string(),
+        duration_ms,
+    })
+}
+
 pub(crate) async fn add_workspace_core<F, Fut>(
     path: String,
     codex_bin: Option<String>,
@@ -288,6 +467,7 @@ pub(crate) async fn add_worktree_core<
     branch: String,
     name: Option<String>,
     copy_agents_md: bool,
+    pr_number: Option<u64>,
     data_dir: &PathBuf,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
@@ -395,7 +575,7 @@ where
         codex_bin: parent_entry.codex_bin.clone(),
         kind: WorkspaceKind::Worktree,
         parent_id: Some(parent_entry.id.clone()),
-        worktree: Some(WorktreeInfo { branch }),
+        worktree: Some(WorktreeInfo { branch, pr_number }),
         settings: WorkspaceSettings {
             worktree_setup_script: normalize_setup_script(
                 parent_entry.settings.worktree_setup_script.clone(),
@@ -465,6 +645,7 @@ async fn kill_session_by_id(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     id: &str,
 ) {
+    watcher_core::unwatch_workspace(id);
     if let Some(session) = sessions.lock().await.remove(id) {
         let mut child = session.child.lock().await;
         kill_child_process_tree(&mut child).await;
@@ -763,6 +944,7 @@ where
             None => {
                 entry.worktree = Some(WorktreeInfo {
                     branch: final_branch.clone(),
+                    pr_number: None,
                 });
             }
         }
@@ -1132,6 +1314,68 @@ pub(crate) async fn update_workspace_codex_bin_core(
     })
 }
 
+#[allow(dead_code)]
+pub(crate) async fn workspace_info_core(
+    id: &str,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+) -> Result<WorkspaceInfo, String> {
+    let entry = workspaces
+        .lock()
+        .await
+        .get(id)
+        .cloned()
+        .ok_or("workspace not found")?;
+    let connected = sessions.lock().await.contains_key(id);
+    Ok(WorkspaceInfo {
+        id: entry.id,
+        name: entry.name,
+        path: entry.path,
+        codex_bin: entry.codex_bin,
+        connected,
+        kind: entry.kind,
+        parent_id: entry.parent_id,
+        worktree: entry.worktree,
+        settings: entry.settings,
+    })
+}
+
+#[allow(dead_code)]
+pub(crate) async fn set_workspace_issue_core(
+    id: String,
+    issue: Option<WorkspaceIssueLink>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    storage_path: &PathBuf,
+) -> Result<WorkspaceInfo, String> {
+    let (entry_snapshot, list) = {
+        let mut workspaces = workspaces.lock().await;
+        let entry_snapshot = match workspaces.get_mut(&id) {
+            Some(entry) => {
+                entry.settings.issue = issue;
+                entry.clone()
+            }
+            None => return Err("workspace not found".to_string()),
+        };
+        let list: Vec<_> = workspaces.values().cloned().collect();
+        (entry_snapshot, list)
+    };
+    write_workspaces(storage_path, &list)?;
+
+    let connected = sessions.lock().await.contains_key(&id);
+    Ok(WorkspaceInfo {
+        id: entry_snapshot.id,
+        name: entry_snapshot.name,
+        path: entry_snapshot.path,
+        codex_bin: entry_snapshot.codex_bin,
+        connected,
+        kind: entry_snapshot.kind,
+        parent_id: entry_snapshot.parent_id,
+        worktree: entry_snapshot.worktree,
+        settings: entry_snapshot.settings,
+    })
+}
+
 pub(crate) async fn list_workspace_files_core<F>(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: &str,
@@ -1174,6 +1418,13 @@ fn sort_workspaces(workspaces: &mut [WorkspaceInfo]) {
 mod tests {
     use super::copy_agents_md_from_parent_to_worktree;
     use super::AGENTS_MD_FILE_NAME;
+    #[cfg(unix)]
+    use super::{worktree_setup_log_path, worktree_setup_marker_path, worktree_setup_run_core};
+    #[cfg(unix)]
+    use crate::backend::events::{
+        AppServerEvent, EventSink, GitHubChecksFinished, TerminalExit, TerminalOutput,
+        WorkspaceSearchMatches, WorktreeSetupOutput,
+    };
     use uuid::Uuid;
 
     fn make_temp_dir() -> std::path::PathBuf {
@@ -1221,4 +1472,83 @@ mod tests {
         let _ = std::fs::remove_dir_all(parent);
         let _ = std::fs::remove_dir_all(worktree);
     }
+
+    #[cfg(unix)]
+    #[derive(Clone)]
+    struct NoopEventSink;
+
+    #[cfg(unix)]
+    impl EventSink for NoopEventSink {
+        fn emit_app_server_event(&self, _event: AppServerEvent) {}
+        fn emit_terminal_output(&self, _event: TerminalOutput) {}
+        fn emit_terminal_exit(&self, _event: TerminalExit) {}
+        fn emit_worktree_setup_output(&self, _event: WorktreeSetupOutput) {}
+        fn emit_github_checks_finished(&self, _event: GitHubChecksFinished) {}
+        fn emit_workspace_search_matches(&self, _event: WorkspaceSearchMatches) {}
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn worktree_setup_run_writes_marker_only_on_success() {
+        use crate::types::{WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
+        use std::collections::HashMap;
+        use tokio::sync::Mutex;
+
+        let worktree = make_temp_dir();
+        let data_dir = make_temp_dir();
+        let entry = |id: &str, script: &str| WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: worktree.to_string_lossy().to_string(),
+            codex_bin: None,
+            kind: WorkspaceKind::Worktree,
+            parent_id: Some("parent".to_string()),
+            worktree: None,
+            settings: WorkspaceSettings {
+                worktree_setup_script: Some(script.to_string()),
+                ..WorkspaceSettings::default()
+            },
+        };
+        let workspaces = Mutex::new(HashMap::from([
+            ("ok".to_string(), entry("ok", "echo setup-done")),
+            ("fail".to_string(), entry("fail", "echo broken >&2; exit 3")),
+        ]));
+
+        let runtime = tokio::runtime::Runtime::new().expect("create runtime");
+        let ok = runtime
+            .block_on(worktree_setup_run_core(
+                &workspaces,
+                "ok",
+                &data_dir,
+                Some(30),
+                NoopEventSink,
+            ))
+            .expect("setup run");
+        assert!(ok.success);
+        assert_eq!(ok.exit_code, Some(0));
+        assert!(worktree_setup_marker_path(&data_dir, "ok").exists());
+        let log = std::fs::read_to_string(worktree_setup_log_path(&data_dir, "ok"))
+            .expect("read setup log");
+        assert!(log.contains("setup-done"));
+
+        let failed = runtime
+            .block_on(worktree_setup_run_core(
+                &workspaces,
+                "fail",
+                &data_dir,
+                Some(30),
+                NoopEventSink,
+            ))
+            .expect("setup run");
+        assert!(!failed.success);
+        assert_eq!(failed.exit_code, Some(3));
+        assert!(!worktree_setup_marker_path(&data_dir, "fail").exists());
+        let log = std::fs::read_to_string(worktree_setup_log_path(&data_dir, "fail"))
+            .expect("read setup log");
+        assert!(log.contains("broken"));
+        assert!(log.contains("[exit code 3]"));
+
+        let _ = std::fs::remove_dir_all(worktree);
+        let _ = std::fs::remove_dir_all(data_dir);
+    }
 }
@@ -4,11 +4,16 @@ use std::sync::Arc;
 
 use portable_pty::{native_pty_system, CommandBuilder, PtySize};
 use serde::Serialize;
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Manager, State};
 use tokio::sync::Mutex;
 
 use crate::backend::events::{EventSink, TerminalExit, TerminalOutput};
 use crate::event_sink::TauriEventSink;
+use crate::shared::terminal_core::{
+    ensure_terminal_capacity, idle_terminal_keys, now_ms, split_terminal_key, TerminalActivity,
+    TerminalLimits, TERMINAL_EXIT_REASON_EXITED, TERMINAL_EXIT_REASON_IDLE,
+    TERMINAL_REAPER_INTERVAL,
+};
 use crate::state::AppState;
 
 pub(crate) struct TerminalSession {
@@ -16,6 +21,7 @@ pub(crate) struct TerminalSession {
     pub(crate) master: Mutex<Box<dyn portable_pty::MasterPty + Send>>,
     pub(crate) writer: Mutex<Box<dyn Write + Send>>,
     pub(crate) child: Mutex<Box<dyn portable_pty::Child + Send>>,
+    pub(crate) activity: Arc<TerminalActivity>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -68,6 +74,7 @@ fn spawn_terminal_reader(
     event_sink: impl EventSink,
     workspace_id: String,
     terminal_id: String,
+    activity: Arc<TerminalActivity>,
     mut reader: Box<dyn Read + Send>,
 ) {
     std::thread::spawn(move || {
@@ -77,6 +84,7 @@ fn spawn_terminal_reader(
             match reader.read(&mut buffer) {
                 Ok(0) => break,
                 Ok(count) => {
+                    activity.touch();
                     pending.extend_from_slice(&buffer[..count]);
                     loop {
                         match std::str::from_utf8(&pending) {
@@ -124,10 +132,64 @@ fn spawn_terminal_reader(
                 Err(_) => break,
             }
         }
-        event_sink.emit_terminal_exit(TerminalExit {
-            workspace_id,
-            terminal_id,
-        });
+        if activity.mark_exited() {
+            event_sink.emit_terminal_exit(TerminalExit {
+                workspace_id,
+                terminal_id,
+                reason: TERMINAL_EXIT_REASON_EXITED.to_string(),
+            });
+        }
+    });
+}
+
+async fn kill_terminal_session(session: Arc<TerminalSession>) {
+    let _ = tokio::task::spawn_blocking(move || {
+        let mut child = session.child.blocking_lock();
+        let _ = child.kill();
+    })
+    .await;
+}
+
+async fn reap_idle_terminals(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let limits = TerminalLimits::from_settings(&*state.app_settings.lock().await);
+    let Some(idle_timeout) = limits.idle_timeout else {
+        return;
+    };
+    let reaped = {
+        let mut sessions = state.terminal_sessions.lock().await;
+        let keys = idle_terminal_keys(
+            sessions
+                .iter()
+                .map(|(key, session)| (key, session.activity.as_ref())),
+            idle_timeout,
+            now_ms(),
+        );
+        keys.into_iter()
+            .filter_map(|key| sessions.remove(&key).map(|session| (key, session)))
+            .collect::<Vec<_>>()
+    };
+    let event_sink = TauriEventSink::new(app.clone());
+    for (key, session) in reaped {
+        if session.activity.mark_exited() {
+            if let Some((workspace_id, terminal_id)) = split_terminal_key(&key) {
+                event_sink.emit_terminal_exit(TerminalExit {
+                    workspace_id: workspace_id.to_string(),
+                    terminal_id: terminal_id.to_string(),
+                    reason: TERMINAL_EXIT_REASON_IDLE.to_string(),
+                });
+            }
+        }
+        kill_terminal_session(session).await;
+    }
+}
+
+pub(crate) fn spawn_terminal_reaper(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TERMINAL_REAPER_INTERVAL).await;
+            reap_idle_terminals(&app).await;
+        }
     });
 }
 
@@ -155,6 +217,7 @@ pub(crate) async fn terminal_open(
         return Err("Terminal id is required".to_string());
     }
     let key = terminal_key(&workspace_id, &terminal_id);
+    let limits = TerminalLimits::from_settings(&*state.app_settings.lock().await);
     {
         let sessions = state.terminal_sessions.lock().await;
         if let Some(existing) = sessions.get(&key) {
@@ -162,6 +225,7 @@ pub(crate) async fn terminal_open(
                 id: existing.id.clone(),
             });
         }
+        ensure_terminal_capacity(sessions.keys(), &workspace_id, &limits)?;
     }
 
     let cwd = get_workspace_path(&workspace_id, &state).await?;
@@ -203,25 +267,32 @@ pub(crate) async fn terminal_open(
         master: Mutex::new(pair.master),
         writer: Mutex::new(writer),
         child: Mutex::new(child),
+        activity: Arc::new(TerminalActivity::new(None)),
     });
     let session_id = session.id.clone();
+    let activity = Arc::clone(&session.activity);
 
     {
         let mut sessions = state.terminal_sessions.lock().await;
-        if let Some(existing) = sessions.get(&key) {
-            let id = existing.id.clone();
+        // Concurrent opens may have taken this key or the last slot while the
+        // shell was starting.
+        let raced = match sessions.get(&key) {
+            Some(existing) => Some(Ok(TerminalSessionInfo {
+                id: existing.id.clone(),
+            })),
+            None => ensure_terminal_capacity(sessions.keys(), &workspace_id, &limits)
+                .err()
+                .map(Err),
+        };
+        if let Some(result) = raced {
             drop(sessions);
-            let _ = tokio::task::spawn_blocking(move || {
-                let mut child = session.child.blocking_lock();
-                let _ = child.kill();
-            })
-            .await;
-            return Ok(TerminalSessionInfo { id });
+            kill_terminal_session(session).await;
+            return result;
         }
         sessions.insert(key, session);
     }
     let event_sink = TauriEventSink::new(app);
-    spawn_terminal_reader(event_sink, workspace_id, terminal_id, reader);
+    spawn_terminal_reader(event_sink, workspace_id, terminal_id, activity, reader);
 
     Ok(TerminalSessionInfo {
         id: session_id,
@@ -237,6 +308,7 @@ pub(crate) async fn terminal_write(
 ) -> Result<(), String> {
     let key = terminal_key(&workspace_id, &terminal_id);
     let session = get_terminal_session(&state, &key).await?;
+    session.activity.touch();
     let write_result = tokio::task::spawn_blocking(move || {
         let mut writer = session.writer.blocking_lock();
         writer
@@ -306,10 +378,6 @@ pub(crate) async fn terminal_close(
         .remove(&key)
         .ok_or_else(|| "Terminal session not found".to_string())?;
     drop(sessions);
-    let _ = tokio::task::spawn_blocking(move || {
-        let mut child = session.child.blocking_lock();
-        let _ = child.kill();
-    })
-    .await;
+    kill_terminal_session(session).await;
     Ok(())
 }
@@ -28,6 +28,73 @@ pub(crate) struct GitFileDiff {
     pub(crate) old_image_mime: Option<String>,
     #[serde(rename = "newImageMime")]
     pub(crate) new_image_mime: Option<String>,
+    #[serde(default)]
+    pub(crate) submodule: Option<GitSubmoduleChange>,
+    #[serde(default)]
+    pub(crate) lfs: Option<GitLfsChange>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitSubmoduleChange {
+    #[serde(default, rename = "oldSha")]
+    pub(crate) old_sha: Option<String>,
+    #[serde(default, rename = "newSha")]
+    pub(crate) new_sha: Option<String>,
+    #[serde(default)]
+    pub(crate) dirty: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitLfsChange {
+    #[serde(default, rename = "oldOid")]
+    pub(crate) old_oid: Option<String>,
+    #[serde(default, rename = "newOid")]
+    pub(crate) new_oid: Option<String>,
+    #[serde(default, rename = "oldSize")]
+    pub(crate) old_size: Option<u64>,
+    #[serde(default, rename = "newSize")]
+    pub(crate) new_size: Option<u64>,
+    #[serde(default, rename = "oldAvailable")]
+    pub(crate) old_available: bool,
+    #[serde(default, rename = "newAvailable")]
+    pub(crate) new_available: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitLfsLock {
+    pub(crate) id: String,
+    pub(crate) path: String,
+    #[serde(default)]
+    pub(crate) owner: Option<String>,
+    #[serde(default, rename = "lockedAt")]
+    pub(crate) locked_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitSubmoduleInfo {
+    pub(crate) name: String,
+    pub(crate) path: String,
+    #[serde(default)]
+    pub(crate) url: Option<String>,
+    #[serde(default)]
+    pub(crate) branch: Option<String>,
+    #[serde(default, rename = "recordedSha")]
+    pub(crate) recorded_sha: Option<String>,
+    #[serde(default, rename = "checkedOutSha")]
+    pub(crate) checked_out_sha: Option<String>,
+    pub(crate) initialized: bool,
+    #[serde(default, rename = "outOfSync")]
+    pub(crate) out_of_sync: bool,
+    /// None when the listing skipped the working-tree scan.
+    #[serde(default)]
+    pub(crate) dirty: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHunkSelection {
+    pub(crate) header: String,
+    #[serde(default)]
+    pub(crate) lines: Option<Vec<usize>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -51,6 +118,10 @@ pub(crate) struct GitCommitDiff {
     pub(crate) old_image_mime: Option<String>,
     #[serde(rename = "newImageMime")]
     pub(crate) new_image_mime: Option<String>,
+    #[serde(default)]
+    pub(crate) submodule: Option<GitSubmoduleChange>,
+    #[serde(default)]
+    pub(crate) lfs: Option<GitLfsChange>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -59,6 +130,32 @@ pub(crate) struct GitLogEntry {
     pub(crate) summary: String,
     pub(crate) author: String,
     pub(crate) timestamp: i64,
+    #[serde(default)]
+    pub(crate) parents: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub(crate) struct GitLogQuery {
+    /// Sha of the last entry from the previous page.
+    #[serde(default)]
+    pub(crate) cursor: Option<String>,
+    #[serde(default)]
+    pub(crate) author: Option<String>,
+    #[serde(default)]
+    pub(crate) path: Option<String>,
+    #[serde(default)]
+    pub(crate) message: Option<String>,
+    /// Unix seconds, inclusive.
+    #[serde(default)]
+    pub(crate) since: Option<i64>,
+    #[serde(default)]
+    pub(crate) until: Option<i64>,
+    #[serde(default)]
+    pub(crate) branch: Option<String>,
+    #[serde(default, rename = "allBranches")]
+    pub(crate) all_branches: bool,
+    #[serde(default, rename = "includeTotal")]
+    pub(crate) include_total: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -75,6 +172,184 @@ pub(crate) struct GitLogResponse {
     pub(crate) behind_entries: Vec<GitLogEntry>,
     #[serde(default)]
     pub(crate) upstream: Option<String>,
+    #[serde(default, rename = "nextCursor")]
+    pub(crate) next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitFileLogEntry {
+    pub(crate) sha: String,
+    pub(crate) summary: String,
+    pub(crate) author: String,
+    pub(crate) timestamp: i64,
+    pub(crate) path: String,
+    pub(crate) status: String,
+    #[serde(default, rename = "previousPath")]
+    pub(crate) previous_path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitBlameHunk {
+    #[serde(rename = "startLine")]
+    pub(crate) start_line: usize,
+    #[serde(rename = "lineCount")]
+    pub(crate) line_count: usize,
+    pub(crate) sha: String,
+    pub(crate) author: String,
+    pub(crate) timestamp: i64,
+    pub(crate) summary: String,
+    #[serde(default, rename = "originalPath")]
+    pub(crate) original_path: Option<String>,
+    #[serde(rename = "originalStartLine")]
+    pub(crate) original_start_line: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitBlameResponse {
+    pub(crate) path: String,
+    pub(crate) sha: String,
+    pub(crate) lines: Vec<String>,
+    pub(crate) hunks: Vec<GitBlameHunk>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitBranchComparison {
+    pub(crate) base: String,
+    pub(crate) head: String,
+    #[serde(default, rename = "mergeBase")]
+    pub(crate) merge_base: Option<String>,
+    pub(crate) ahead: usize,
+    pub(crate) behind: usize,
+    #[serde(default, rename = "aheadEntries")]
+    pub(crate) ahead_entries: Vec<GitLogEntry>,
+    #[serde(default, rename = "behindEntries")]
+    pub(crate) behind_entries: Vec<GitLogEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct GitConflictMarker {
+    #[serde(rename = "startLine")]
+    pub(crate) start_line: usize,
+    #[serde(default, rename = "baseLine")]
+    pub(crate) base_line: Option<usize>,
+    #[serde(rename = "separatorLine")]
+    pub(crate) separator_line: usize,
+    #[serde(rename = "endLine")]
+    pub(crate) end_line: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitConflictFile {
+    pub(crate) path: String,
+    #[serde(default)]
+    pub(crate) base: Option<String>,
+    #[serde(default)]
+    pub(crate) ours: Option<String>,
+    #[serde(default)]
+    pub(crate) theirs: Option<String>,
+    #[serde(default)]
+    pub(crate) working: Option<String>,
+    #[serde(default)]
+    pub(crate) markers: Vec<GitConflictMarker>,
+    #[serde(default, rename = "oursDeleted")]
+    pub(crate) ours_deleted: bool,
+    #[serde(default, rename = "theirsDeleted")]
+    pub(crate) theirs_deleted: bool,
+    #[serde(default, rename = "isBinary")]
+    pub(crate) is_binary: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitConflictsResponse {
+    #[serde(default)]
+    pub(crate) operation: Option<String>,
+    pub(crate) files: Vec<GitConflictFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitPickResult {
+    pub(crate) success: bool,
+    #[serde(default, rename = "headSha")]
+    pub(crate) head_sha: Option<String>,
+    #[serde(default, rename = "conflictedFiles")]
+    pub(crate) conflicted_files: Vec<String>,
+    #[serde(default)]
+    pub(crate) operation: Option<String>,
+    #[serde(default)]
+    pub(crate) message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHookResult {
+    pub(crate) name: String,
+    pub(crate) passed: bool,
+    pub(crate) output: String,
+    #[serde(default, rename = "durationMs")]
+    pub(crate) duration_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitCommitSignature {
+    pub(crate) required: bool,
+    #[serde(default)]
+    pub(crate) format: Option<String>,
+    pub(crate) status: String,
+    #[serde(default)]
+    pub(crate) signer: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitCommitResult {
+    pub(crate) success: bool,
+    #[serde(default)]
+    pub(crate) sha: Option<String>,
+    #[serde(default)]
+    pub(crate) hooks: Vec<GitHookResult>,
+    #[serde(default)]
+    pub(crate) signature: Option<GitCommitSignature>,
+    #[serde(default)]
+    pub(crate) output: String,
+    #[serde(default)]
+    pub(crate) message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitTagInfo {
+    pub(crate) name: String,
+    pub(crate) sha: String,
+    pub(crate) annotated: bool,
+    #[serde(default)]
+    pub(crate) message: Option<String>,
+    #[serde(default)]
+    pub(crate) tagger: Option<String>,
+    pub(crate) timestamp: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitReleaseSection {
+    pub(crate) kind: String,
+    pub(crate) title: String,
+    pub(crate) entries: Vec<GitLogEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitReleaseNotes {
+    pub(crate) from: String,
+    pub(crate) to: String,
+    pub(crate) sections: Vec<GitReleaseSection>,
+    #[serde(default)]
+    pub(crate) breaking: Vec<GitLogEntry>,
+    pub(crate) markdown: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitStashEntry {
+    pub(crate) index: usize,
+    pub(crate) sha: String,
+    pub(crate) message: String,
+    #[serde(default)]
+    pub(crate) branch: Option<String>,
+    pub(crate) timestamp: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -86,10 +361,38 @@ pub(crate) struct GitHubIssue {
     pub(crate) updated_at: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubIssueDetails {
+    #[serde(flatten)]
+    pub(crate) issue: GitHubIssue,
+    #[serde(default)]
+    pub(crate) body: String,
+    #[serde(default)]
+    pub(crate) state: String,
+    #[serde(default)]
+    pub(crate) labels: Vec<String>,
+    #[serde(default)]
+    pub(crate) author: Option<GitHubPullRequestAuthor>,
+    #[serde(default)]
+    pub(crate) comments: Vec<GitHubPullRequestComment>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitHubIssuesResponse {
     pub(crate) total: usize,
     pub(crate) issues: Vec<GitHubIssue>,
+    #[serde(default, rename = "rateLimit")]
+    pub(crate) rate_limit: Option<GitHubRateLimit>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct GitHubRateLimit {
+    pub(crate) limit: u64,
+    pub(crate) remaining: u64,
+    #[serde(rename = "resetAt")]
+    pub(crate) reset_at: i64,
+    #[serde(default)]
+    pub(crate) resource: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -122,6 +425,8 @@ pub(crate) struct GitHubPullRequestsResponse {
     pub(crate) total: usize,
     #[serde(rename = "pullRequests")]
     pub(crate) pull_requests: Vec<GitHubPullRequest>,
+    #[serde(default, rename = "rateLimit")]
+    pub(crate) rate_limit: Option<GitHubRateLimit>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -142,6 +447,96 @@ pub(crate) struct GitHubPullRequestComment {
     pub(crate) url: String,
     #[serde(default)]
     pub(crate) author: Option<GitHubPullRequestAuthor>,
+    /// Inline review comments carry their diff anchor; issue comments do not.
+    #[serde(default)]
+    pub(crate) path: Option<String>,
+    #[serde(default)]
+    pub(crate) line: Option<u32>,
+    #[serde(default, rename = "startLine")]
+    pub(crate) start_line: Option<u32>,
+    #[serde(default, rename = "originalLine")]
+    pub(crate) original_line: Option<u32>,
+    #[serde(default)]
+    pub(crate) side: Option<String>,
+    #[serde(default, rename = "diffHunk")]
+    pub(crate) diff_hunk: Option<String>,
+    #[serde(default, rename = "inReplyToId")]
+    pub(crate) in_reply_to_id: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubReviewCommentDraft {
+    pub(crate) path: String,
+    pub(crate) line: u32,
+    #[serde(default, rename = "startLine")]
+    pub(crate) start_line: Option<u32>,
+    /// "RIGHT" (the new side, default) or "LEFT".
+    #[serde(default)]
+    pub(crate) side: Option<String>,
+    pub(crate) body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubPullRequestReview {
+    pub(crate) id: u64,
+    pub(crate) state: String,
+    #[serde(default)]
+    pub(crate) body: String,
+    #[serde(default)]
+    pub(crate) url: String,
+    #[serde(default, rename = "submittedAt")]
+    pub(crate) submitted_at: Option<String>,
+    #[serde(default)]
+    pub(crate) author: Option<GitHubPullRequestAuthor>,
+    /// Inline comments posted with the review.
+    #[serde(default, rename = "commentCount")]
+    pub(crate) comment_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubCheck {
+    /// Check run id, used to fetch logs; commit statuses have none.
+    #[serde(default)]
+    pub(crate) id: Option<u64>,
+    pub(crate) name: String,
+    /// "checkRun" or "status".
+    pub(crate) kind: String,
+    /// "queued", "in_progress" or "completed".
+    pub(crate) status: String,
+    #[serde(default)]
+    pub(crate) conclusion: Option<String>,
+    #[serde(default)]
+    pub(crate) url: String,
+    #[serde(default, rename = "startedAt")]
+    pub(crate) started_at: Option<String>,
+    #[serde(default, rename = "completedAt")]
+    pub(crate) completed_at: Option<String>,
+    #[serde(default, rename = "durationSecs")]
+    pub(crate) duration_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubChecksResponse {
+    #[serde(rename = "ref")]
+    pub(crate) git_ref: String,
+    pub(crate) sha: String,
+    /// "pending", "success", "failure" or "none" when nothing reported yet.
+    pub(crate) state: String,
+    pub(crate) checks: Vec<GitHubCheck>,
+    #[serde(default, rename = "rateLimit")]
+    pub(crate) rate_limit: Option<GitHubRateLimit>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitHubCheckLog {
+    pub(crate) id: u64,
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) conclusion: Option<String>,
+    #[serde(default)]
+    pub(crate) url: String,
+    pub(crate) excerpt: String,
+    pub(crate) truncated: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -191,6 +586,16 @@ pub(crate) struct LocalUsageSnapshot {
 pub(crate) struct BranchInfo {
     pub(crate) name: String,
     pub(crate) last_commit: i64,
+    #[serde(default)]
+    pub(crate) upstream: Option<String>,
+    #[serde(default)]
+    pub(crate) ahead: usize,
+    #[serde(default)]
+    pub(crate) behind: usize,
+    #[serde(default)]
+    pub(crate) remote: Option<String>,
+    #[serde(default)]
+    pub(crate) current: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -226,6 +631,22 @@ pub(crate) struct WorkspaceInfo {
     pub(crate) settings: WorkspaceSettings,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct PullRequestWorktree {
+    pub(crate) workspace: WorkspaceInfo,
+    #[serde(default, rename = "reviewThreadId")]
+    pub(crate) review_thread_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct IssueRun {
+    pub(crate) workspace: WorkspaceInfo,
+    #[serde(default, rename = "createdWorktree")]
+    pub(crate) created_worktree: bool,
+    #[serde(default, rename = "threadId")]
+    pub(crate) thread_id: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "lowercase")]
 pub(crate) enum WorkspaceKind {
@@ -248,6 +669,8 @@ impl WorkspaceKind {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct WorktreeInfo {
     pub(crate) branch: String,
+    #[serde(default, rename = "prNumber")]
+    pub(crate) pr_number: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -280,6 +703,114 @@ pub(crate) struct WorkspaceSettings {
     pub(crate) launch_scripts: Option<Vec<LaunchScriptEntry>>,
     #[serde(default, rename = "worktreeSetupScript")]
     pub(crate) worktree_setup_script: Option<String>,
+    /// The issue this workspace is working on, for `Fixes #N` in pull requests.
+    #[serde(default)]
+    pub(crate) issue: Option<WorkspaceIssueLink>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct WorkspaceIssueLink {
+    pub(crate) number: u64,
+    pub(crate) title: String,
+    pub(crate) url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub(crate) struct WorkspaceSearchQuery {
+    pub(crate) query: String,
+    #[serde(default)]
+    pub(crate) regex: bool,
+    #[serde(default, rename = "caseSensitive")]
+    pub(crate) case_sensitive: bool,
+    /// Globs relative to the workspace root; a leading `!` is not needed
+    /// for `exclude`.
+    #[serde(default)]
+    pub(crate) include: Vec<String>,
+    #[serde(default)]
+    pub(crate) exclude: Vec<String>,
+    #[serde(default, rename = "maxResults")]
+    pub(crate) max_results: Option<usize>,
+    #[serde(default, rename = "contextLines")]
+    pub(crate) context_lines: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct WorkspaceSearchMatch {
+    pub(crate) path: String,
+    /// 1-based line number.
+    pub(crate) line: usize,
+    /// 1-based column, counted in characters.
+    pub(crate) column: usize,
+    /// Match length in characters.
+    pub(crate) length: usize,
+    /// The matched line, clipped to a window around the match on long lines.
+    pub(crate) text: String,
+    /// Characters of the line cut before `text` and each context line.
+    #[serde(default, rename = "textOffset")]
+    pub(crate) text_offset: usize,
+    #[serde(default, rename = "contextBefore")]
+    pub(crate) context_before: Vec<String>,
+    #[serde(default, rename = "contextAfter")]
+    pub(crate) context_after: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct WorkspaceSearchSummary {
+    #[serde(rename = "searchId")]
+    pub(crate) search_id: String,
+    #[serde(rename = "matchCount")]
+    pub(crate) match_count: usize,
+    #[serde(rename = "fileCount")]
+    pub(crate) file_count: usize,
+    #[serde(rename = "filesSearched")]
+    pub(crate) files_searched: usize,
+    /// The search stopped at `maxResults`.
+    pub(crate) truncated: bool,
+    pub(crate) cancelled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct WorkspacePathMatch {
+    pub(crate) path: String,
+    pub(crate) score: i64,
+    /// Character positions in `path` that matched the query.
+    pub(crate) positions: Vec<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct WorkspaceDirEntry {
+    pub(crate) name: String,
+    /// Workspace-relative path using `/` separators.
+    pub(crate) path: String,
+    /// `file`, `dir` or `symlink`.
+    pub(crate) kind: String,
+    pub(crate) size: Option<u64>,
+    #[serde(rename = "modifiedMs")]
+    pub(crate) modified_ms: Option<i64>,
+    /// Same letters as `get_git_status`; directories report `M` when anything
+    /// below them changed.
+    #[serde(rename = "gitStatus")]
+    pub(crate) git_status: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct WorkspaceFileStat {
+    pub(crate) path: String,
+    pub(crate) size: u64,
+    #[serde(rename = "modifiedMs")]
+    pub(crate) modified_ms: i64,
+    /// Git blob id of the full file contents.
+    pub(crate) hash: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct WorkspaceTextFile {
+    #[serde(flatten)]
+    pub(crate) stat: WorkspaceFileStat,
+    pub(crate) content: String,
+    /// `utf-8`, `utf-8-bom`, `utf-16le`, `utf-16be` or `binary`.
+    pub(crate) encoding: String,
+    pub(crate) truncated: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -298,6 +829,19 @@ pub(crate) struct WorktreeSetupStatus {
     pub(crate) script: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct WorktreeSetupRunResult {
+    pub(crate) success: bool,
+    #[serde(rename = "exitCode")]
+    pub(crate) exit_code: Option<i32>,
+    #[serde(rename = "timedOut")]
+    pub(crate) timed_out: bool,
+    #[serde(rename = "logPath")]
+    pub(crate) log_path: String,
+    #[serde(rename = "durationMs")]
+    pub(crate) duration_ms: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct OpenAppTarget {
     pub(crate) id: String,
@@ -512,6 +1056,24 @@ pub(crate) struct AppSettings {
     pub(crate) open_app_targets: Vec<OpenAppTarget>,
     #[serde(default = "default_selected_open_app_id", rename = "selectedOpenAppId")]
     pub(crate) selected_open_app_id: String,
+    #[serde(
+        default = "default_terminal_max_per_workspace",
+        rename = "terminalMaxPerWorkspace"
+    )]
+    pub(crate) terminal_max_per_workspace: u32,
+    #[serde(default = "default_terminal_max_total", rename = "terminalMaxTotal")]
+    pub(crate) terminal_max_total: u32,
+    #[serde(default, rename = "terminalIdleTimeoutMinutes")]
+    pub(crate) terminal_idle_timeout_minutes: u32,
+    #[serde(default, rename = "terminalCloseOnDisconnect")]
+    pub(crate) terminal_close_on_disconnect: bool,
+    #[serde(default, rename = "githubToken")]
+    pub(crate) github_token: Option<String>,
+    #[serde(default, rename = "githubApiBaseUrl")]
+    pub(crate) github_api_base_url: Option<String>,
+    /// Self-hosted forges whose host names do not give away their kind.
+    #[serde(default, rename = "forgeHosts")]
+    pub(crate) forge_hosts: Vec<ForgeHostMapping>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -527,6 +1089,25 @@ impl Default for BackendMode {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ForgeKind {
+    GitHub,
+    GitLab,
+    #[serde(alias = "forgejo")]
+    Gitea,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct ForgeHostMapping {
+    pub(crate) host: String,
+    pub(crate) kind: ForgeKind,
+    #[serde(default, rename = "apiBaseUrl")]
+    pub(crate) api_base_url: Option<String>,
+    #[serde(default)]
+    pub(crate) token: Option<String>,
+}
+
 fn default_access_mode() -> String {
     "current".to_string()
 }
@@ -919,6 +1500,14 @@ fn default_selected_open_app_id() -> String {
     }
 }
 
+fn default_terminal_max_per_workspace() -> u32 {
+    8
+}
+
+fn default_terminal_max_total() -> u32 {
+    32
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
@@ -980,6 +1569,13 @@ impl Default for AppSettings {
             workspace_groups: default_workspace_groups(),
             open_app_targets: default_open_app_targets(),
             selected_open_app_id: default_selected_open_app_id(),
+            terminal_max_per_workspace: default_terminal_max_per_workspace(),
+            terminal_max_total: default_terminal_max_total(),
+            terminal_idle_timeout_minutes: 0,
+            terminal_close_on_disconnect: false,
+            github_token: None,
+            github_api_base_url: None,
+            forge_hosts: Vec::new(),
         }
     }
 }
@@ -997,6 +1593,9 @@ mod tests {
         assert!(matches!(settings.backend_mode, BackendMode::Local));
         assert_eq!(settings.remote_backend_host, "127.0.0.1:4732");
         assert!(settings.remote_backend_token.is_none());
+        assert!(settings.github_token.is_none());
+        assert!(settings.github_api_base_url.is_none());
+        assert!(settings.forge_hosts.is_empty());
         assert_eq!(settings.default_access_mode, "current");
         assert_eq!(settings.review_delivery_mode, "inline");
         let expected_primary = if cfg!(target_os = "macos") { "cmd" } else { "ctrl" };
@@ -1114,6 +1713,10 @@ mod tests {
         assert_eq!(settings.selected_open_app_id, expected_open_id);
         assert_eq!(settings.open_app_targets.len(), 6);
         assert_eq!(settings.open_app_targets[0].id, "vscode");
+        assert_eq!(settings.terminal_max_per_workspace, 8);
+        assert_eq!(settings.terminal_max_total, 32);
+        assert_eq!(settings.terminal_idle_timeout_minutes, 0);
+        assert!(!settings.terminal_close_on_disconnect);
     }
 
     #[test]
@@ -25,19 +25,24 @@ use super::worktree::{
 };
 
 use crate::backend::app_server::WorkspaceSession;
+use crate::backend::events::EventSink;
 use crate::codex::spawn_workspace_session;
 use crate::codex::args::resolve_workspace_codex_args;
 use crate::codex::home::resolve_workspace_codex_home;
+use crate::event_sink::TauriEventSink;
+use crate::git;
 use crate::git_utils::resolve_git_root;
 use crate::remote_backend;
 use crate::shared::process_core::{kill_child_process_tree, tokio_command};
 #[cfg(target_os = "windows")]
 use crate::shared::process_core::{build_cmd_c_command, resolve_windows_executable};
-use crate::shared::workspaces_core;
+use crate::shared::{codex_core, search_core, workspaces_core};
 use crate::state::AppState;
 use crate::storage::write_workspaces;
 use crate::types::{
-    WorkspaceEntry, WorkspaceInfo, WorkspaceKind, WorkspaceSettings, WorktreeSetupStatus,
+    IssueRun, PullRequestWorktree, WorkspaceEntry, WorkspaceInfo, WorkspaceIssueLink,
+    WorkspaceKind, WorkspacePathMatch, WorkspaceSearchQuery, WorkspaceSearchSummary,
+    WorkspaceSettings, WorktreeSetupRunResult, WorktreeSetupStatus,
 };
 use crate::utils::{git_env_path, resolve_git_binary};
 
@@ -321,6 +326,7 @@ pub(crate) async fn add_worktree(
         branch,
         name,
         copy_agents_md,
+        None,
         &data_dir,
         &state.workspaces,
         &state.sessions,
@@ -346,6 +352,203 @@ pub(crate) async fn add_worktree(
     .await
 }
 
+#[tauri::command]
+pub(crate) async fn add_pr_worktree(
+    parent_id: String,
+    pr_number: u64,
+    start_review: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<PullRequestWorktree, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("Pull request worktrees are not available in remote mode.".to_string());
+    }
+
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    let parent_entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&parent_id)
+            .cloned()
+            .ok_or("parent workspace not found")?
+    };
+    let repo_root = resolve_git_root(&parent_entry)?;
+    let settings = state.app_settings.lock().await.clone();
+    let prepared = git::prepare_pull_request_branch(&repo_root, &settings, pr_number).await?;
+    let name = format!("#{pr_number} {}", prepared.title)
+        .trim()
+        .to_string();
+
+    let workspace = workspaces_core::add_worktree_core(
+        parent_id,
+        prepared.branch,
+        Some(name),
+        true,
+        Some(pr_number),
+        &data_dir,
+        &state.workspaces,
+        &state.sessions,
+        &state.app_settings,
+        &state.storage_path,
+        |value| sanitize_worktree_name(value),
+        |root, name| Ok(unique_worktree_path(root, name)),
+        |root, branch| {
+            let root = root.clone();
+            let branch = branch.to_string();
+            async move { git_branch_exists(&root, &branch).await }
+        },
+        None::<fn(&PathBuf, &str) -> std::future::Ready<Result<Option<String>, String>>>,
+        |root, args| {
+            workspaces_core::run_git_command_unit(root, args, |repo, args_owned| {
+                run_git_command_owned(repo, args_owned)
+            })
+        },
+        |entry, default_bin, codex_args, codex_home| {
+            spawn_with_app(&app, entry, default_bin, codex_args, codex_home)
+        },
+    )
+    .await?;
+
+    // The worktree is usable even when the review cannot start, so a review
+    // failure is logged rather than returned.
+    let review_thread_id = if start_review.unwrap_or(false) {
+        match codex_core::start_base_branch_review_core(
+            &state.sessions,
+            workspace.id.clone(),
+            prepared.base_ref,
+            None,
+        )
+        .await
+        {
+            Ok(thread_id) => Some(thread_id),
+            Err(error) => {
+                eprintln!("add_pr_worktree: review for #{pr_number} failed to start: {error}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    Ok(PullRequestWorktree {
+        workspace,
+        review_thread_id,
+    })
+}
+
+/// Starts an agent thread on an issue, in a new worktree unless `worktree`
+/// is false. A new worktree remembers the issue so pull requests opened from
+/// it reference it.
+#[tauri::command]
+pub(crate) async fn start_issue_run(
+    workspace_id: String,
+    issue_number: u64,
+    worktree: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<IssueRun, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return Err("Issue runs are not available in remote mode.".to_string());
+    }
+
+    let entry = {
+        let workspaces = state.workspaces.lock().await;
+        workspaces
+            .get(&workspace_id)
+            .cloned()
+            .ok_or("workspace not found")?
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let settings = state.app_settings.lock().await.clone();
+    let issue = git::fetch_issue(&repo_root, &settings, issue_number).await?;
+    let link = WorkspaceIssueLink {
+        number: issue.issue.number,
+        title: issue.issue.title.clone(),
+        url: issue.issue.url.clone(),
+    };
+
+    let created_worktree = worktree.unwrap_or(true);
+    let run_workspace_id = if created_worktree {
+        let data_dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+        let name = format!("#{issue_number} {}", link.title).trim().to_string();
+        workspaces_core::add_worktree_core(
+            workspace_id,
+            git::issue_branch_name(&issue),
+            Some(name),
+            true,
+            None,
+            &data_dir,
+            &state.workspaces,
+            &state.sessions,
+            &state.app_settings,
+            &state.storage_path,
+            |value| sanitize_worktree_name(value),
+            |root, name| Ok(unique_worktree_path(root, name)),
+            |root, branch| {
+                let root = root.clone();
+                let branch = branch.to_string();
+                async move { git_branch_exists(&root, &branch).await }
+            },
+            None::<fn(&PathBuf, &str) -> std::future::Ready<Result<Option<String>, String>>>,
+            |root, args| {
+                workspaces_core::run_git_command_unit(root, args, |repo, args_owned| {
+                    run_git_command_owned(repo, args_owned)
+                })
+            },
+            |entry, default_bin, codex_args, codex_home| {
+                spawn_with_app(&app, entry, default_bin, codex_args, codex_home)
+            },
+        )
+        .await?
+        .id
+    } else {
+        workspace_id
+    };
+    // Only the issue's own worktree keeps the link; a run in place must not
+    // tag every later pull request from this workspace.
+    let workspace = if created_worktree {
+        workspaces_core::set_workspace_issue_core(
+            run_workspace_id.clone(),
+            Some(link),
+            &state.workspaces,
+            &state.sessions,
+            &state.storage_path,
+        )
+        .await?
+    } else {
+        workspaces_core::workspace_info_core(&run_workspace_id, &state.workspaces, &state.sessions)
+            .await?
+    };
+
+    let thread = codex_core::start_thread_with_message_core(
+        &state.sessions,
+        run_workspace_id,
+        git::issue_run_prompt(&issue),
+    )
+    .await;
+    let thread_id = match thread {
+        Ok(thread_id) => Some(thread_id),
+        // Keep the new worktree usable even when the thread cannot start.
+        Err(error) if created_worktree => {
+            eprintln!("start_issue_run: thread for #{issue_number} failed to start: {error}");
+            None
+        }
+        Err(error) => return Err(error),
+    };
+
+    Ok(IssueRun {
+        workspace,
+        created_worktree,
+        thread_id,
+    })
+}
+
 #[tauri::command]
 pub(crate) async fn worktree_setup_status(
     workspace_id: String,
@@ -370,6 +573,38 @@ pub(crate) async fn worktree_setup_status(
     workspaces_core::worktree_setup_status_core(&state.workspaces, &workspace_id, &data_dir).await
 }
 
+#[tauri::command]
+pub(crate) async fn worktree_setup_run(
+    workspace_id: String,
+    timeout_secs: Option<u64>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorktreeSetupRunResult, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "worktree_setup_run",
+            json!({ "workspaceId": workspace_id, "timeoutSecs": timeout_secs }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("Failed to resolve app data dir: {err}"))?;
+    workspaces_core::worktree_setup_run_core(
+        &state.workspaces,
+        &workspace_id,
+        &data_dir,
+        timeout_secs,
+        TauriEventSink::new(app),
+    )
+    .await
+}
+
 #[tauri::command]
 pub(crate) async fn worktree_setup_mark_ran(
     workspace_id: String,
@@ -681,24 +916,20 @@ pub(crate) async fn apply_worktree_changes(
     } else {
         stderr.trim()
     };
-    if detail.is_empty() {
-        return Err("Git apply failed.".to_string());
-    }
-
-    if detail.contains("Applied patch to") {
+    let message = if detail.is_empty() {
+        "Git apply failed.".to_string()
+    } else if detail.contains("Applied patch to") {
         if detail.contains("with conflicts") {
-            return Err(
-                "Applied with conflicts. Resolve conflicts in the parent repo before retrying."
-                    .to_string(),
-            );
-        }
-        return Err(
+            "Applied with conflicts. Resolve conflicts in the parent repo before retrying."
+                .to_string()
+        } else {
             "Patch applied partially. Resolve changes in the parent repo before retrying."
-                .to_string(),
-        );
-    }
-
-    Err(detail.to_string())
+                .to_string()
+        }
+    } else {
+        detail.to_string()
+    };
+    Err(git::with_conflict_context(&parent_root, message))
 }
 
 
@@ -816,6 +1047,93 @@ pub(crate) async fn list_workspace_files(
     .await
 }
 
+#[tauri::command]
+pub(crate) async fn search_workspace_files(
+    workspace_id: String,
+    search_id: Option<String>,
+    query: String,
+    regex: Option<bool>,
+    case_sensitive: Option<bool>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    max_results: Option<usize>,
+    context_lines: Option<usize>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<WorkspaceSearchSummary, String> {
+    let query = WorkspaceSearchQuery {
+        query,
+        regex: regex.unwrap_or(false),
+        case_sensitive: case_sensitive.unwrap_or(false),
+        include: include.unwrap_or_default(),
+        exclude: exclude.unwrap_or_default(),
+        max_results,
+        context_lines,
+    };
+    if remote_backend::is_remote_mode(&*state).await {
+        let mut params = serde_json::to_value(&query).map_err(|err| err.to_string())?;
+        if let Some(params) = params.as_object_mut() {
+            params.insert("workspaceId".to_string(), json!(workspace_id));
+            params.insert("searchId".to_string(), json!(search_id));
+        }
+        let response =
+            remote_backend::call_remote(&*state, app, "search_workspace_files", params).await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    let event_sink = TauriEventSink::new(app);
+    search_core::search_workspace_files_core(
+        &state.workspaces,
+        workspace_id,
+        search_id,
+        query,
+        move |event| event_sink.emit_workspace_search_matches(event),
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn cancel_workspace_search(
+    search_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<bool, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "cancel_workspace_search",
+            json!({ "searchId": search_id }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    Ok(search_core::cancel_workspace_search_core(&search_id))
+}
+
+#[tauri::command]
+pub(crate) async fn find_workspace_paths(
+    workspace_id: String,
+    query: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<WorkspacePathMatch>, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        let response = remote_backend::call_remote(
+            &*state,
+            app,
+            "find_workspace_paths",
+            json!({ "workspaceId": workspace_id, "query": query, "limit": limit }),
+        )
+        .await?;
+        return serde_json::from_value(response).map_err(|err| err.to_string());
+    }
+
+    search_core::find_workspace_paths_core(&state.workspaces, &workspace_id, query, limit).await
+}
+
 
 #[tauri::command]
 pub(crate) async fn open_workspace_in(
@@ -32,6 +32,7 @@ fn workspace_with_id_and_kind(
             Some("parent".to_string()),
             Some(WorktreeInfo {
                 branch: name.to_string(),
+                pr_number: None,
             }),
         )
     } else {
@@ -56,6 +57,7 @@ fn workspace_with_id_and_kind(
             launch_script: None,
             launch_scripts: None,
             worktree_setup_script: None,
+            issue: None,
         },
     }
 }
@@ -272,6 +274,7 @@ fn rename_worktree_preserves_custom_name() {
         parent_id: Some(parent.id.clone()),
         worktree: Some(WorktreeInfo {
             branch: "feature/old".to_string(),
+            pr_number: None,
         }),
         settings: WorkspaceSettings::default(),
     };
@@ -342,6 +345,7 @@ fn rename_worktree_updates_name_when_unmodified() {
         parent_id: Some(parent.id.clone()),
         worktree: Some(WorktreeInfo {
             branch: "feature/old".to_string(),
+            pr_number: None,
         }),
         settings: WorkspaceSettings::default(),
     };
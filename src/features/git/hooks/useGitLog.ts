@@ -35,6 +35,9 @@ export function useGitLog(
   const [state, setState] = useState<GitLogState>(emptyState);
   const requestIdRef = useRef(0);
   const workspaceIdRef = useRef<string | null>(activeWorkspace?.id ?? null);
+  // Counting walks the whole history, so only recount when HEAD moves.
+  const countedHeadRef = useRef<string | null | undefined>(undefined);
+  const totalRef = useRef(0);
 
   const refresh = useCallback(async () => {
     if (!activeWorkspace) {
@@ -46,16 +49,36 @@ export function useGitLog(
     requestIdRef.current = requestId;
     setState((prev) => ({ ...prev, isLoading: true, error: null }));
     try {
-      const response = await getGitLog(workspaceId);
+      const needsTotal = countedHeadRef.current === undefined;
+      const response = await getGitLog(
+        workspaceId,
+        40,
+        needsTotal ? { includeTotal: true } : undefined,
+      );
       if (
         requestIdRef.current !== requestId ||
         workspaceIdRef.current !== workspaceId
       ) {
         return;
       }
+      const head = response.entries[0]?.sha ?? null;
+      if (needsTotal) {
+        totalRef.current = response.total;
+        countedHeadRef.current = head;
+      } else if (head !== countedHeadRef.current) {
+        const counted = await getGitLog(workspaceId, 1, { includeTotal: true });
+        if (
+          requestIdRef.current !== requestId ||
+          workspaceIdRef.current !== workspaceId
+        ) {
+          return;
+        }
+        totalRef.current = counted.total;
+        countedHeadRef.current = head;
+      }
       setState({
         entries: response.entries,
-        total: response.total,
+        total: totalRef.current,
         ahead: response.ahead,
         behind: response.behind,
         aheadEntries: response.aheadEntries,
@@ -72,6 +95,8 @@ export function useGitLog(
       ) {
         return;
       }
+      countedHeadRef.current = undefined;
+      totalRef.current = 0;
       setState({
         entries: [],
         total: 0,
@@ -91,6 +116,8 @@ export function useGitLog(
     if (workspaceIdRef.current !== workspaceId) {
       workspaceIdRef.current = workspaceId;
       requestIdRef.current += 1;
+      countedHeadRef.current = undefined;
+      totalRef.current = 0;
       setState(emptyState);
     }
   }, [activeWorkspace?.id]);
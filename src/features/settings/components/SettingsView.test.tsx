@@ -89,6 +89,13 @@ const baseSettings: AppSettings = {
     },
   ],
   selectedOpenAppId: "vscode",
+  terminalMaxPerWorkspace: 8,
+  terminalMaxTotal: 32,
+  terminalIdleTimeoutMinutes: 0,
+  terminalCloseOnDisconnect: false,
+  githubToken: null,
+  githubApiBaseUrl: null,
+  forgeHosts: [],
 };
 
 const createDoctorResult = () => ({
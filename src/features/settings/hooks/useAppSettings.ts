@@ -82,6 +82,13 @@ function buildDefaultSettings(): AppSettings {
     workspaceGroups: [],
     openAppTargets: DEFAULT_OPEN_APP_TARGETS,
     selectedOpenAppId: DEFAULT_OPEN_APP_ID,
+    terminalMaxPerWorkspace: 8,
+    terminalMaxTotal: 32,
+    terminalIdleTimeoutMinutes: 0,
+    terminalCloseOnDisconnect: false,
+    githubToken: null,
+    githubApiBaseUrl: null,
+    forgeHosts: [],
   };
 }
 
@@ -1,6 +1,12 @@
 import { subscribeDictationWeb } from "../platform/dictation";
 import { subscribeRpcNotification } from "../platform/rpcClient";
-import type { AppServerEvent, DictationEvent, DictationModelStatus } from "../types";
+import type {
+  AppServerEvent,
+  DictationEvent,
+  DictationModelStatus,
+  GitHubChecksFinishedEvent,
+  WorkspaceSearchMatchesEvent,
+} from "../types";
 
 export type Unsubscribe = () => void;
 
@@ -13,6 +19,7 @@ export type TerminalOutputEvent = {
 export type TerminalExitEvent = {
   workspaceId: string;
   terminalId: string;
+  reason?: "exited" | "idle" | "disconnected";
 };
 
 type SubscriptionOptions = {
@@ -78,6 +85,12 @@ const dictationDownloadHub = createEventHub<DictationModelStatus>("dictation-dow
 const dictationEventHub = createEventHub<DictationEvent>("dictation-event");
 const terminalOutputHub = createEventHub<TerminalOutputEvent>("terminal-output");
 const terminalExitHub = createEventHub<TerminalExitEvent>("terminal-exit");
+const githubChecksFinishedHub = createEventHub<GitHubChecksFinishedEvent>(
+  "github-checks-finished",
+);
+const workspaceSearchMatchesHub = createEventHub<WorkspaceSearchMatchesEvent>(
+  "workspace-search-matches",
+);
 const updaterCheckHub = createEventHub<void>("updater-check");
 const menuNewAgentHub = createEventHub<void>("menu-new-agent");
 const menuNewWorktreeAgentHub = createEventHub<void>("menu-new-worktree-agent");
@@ -143,6 +156,20 @@ export function subscribeTerminalExit(
   return terminalExitHub.subscribe(onEvent, options);
 }
 
+export function subscribeGitHubChecksFinished(
+  onEvent: (event: GitHubChecksFinishedEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return githubChecksFinishedHub.subscribe(onEvent, options);
+}
+
+export function subscribeWorkspaceSearchMatches(
+  onEvent: (event: WorkspaceSearchMatchesEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return workspaceSearchMatchesHub.subscribe(onEvent, options);
+}
+
 export function subscribeUpdaterCheck(
   onEvent: () => void,
   options?: SubscriptionOptions,
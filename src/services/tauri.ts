@@ -13,19 +13,50 @@ import type {
   CodexDoctorResult,
   DictationModelStatus,
   DictationSessionState,
+  IssueRun,
   LocalUsageSnapshot,
+  PullRequestWorktree,
+  WorkspaceDirEntry,
+  WorkspaceFileEncoding,
+  WorkspaceFileStat,
   WorkspaceInfo,
+  WorkspacePathMatch,
+  WorkspaceSearchOptions,
+  WorkspaceSearchSummary,
   WorkspaceSettings,
+  WorkspaceTextFile,
 } from "../types";
 import type {
+  GitBlameResponse,
+  GitBranchComparison,
   GitCommitDiff,
+  GitCommitResult,
+  GitConflictsResponse,
   GitFileDiff,
+  GitFileLogEntry,
   GitFileStatus,
+  GitHookResult,
+  GitHubCheckLog,
+  GitHubChecksResponse,
+  GitHubIssueDetails,
   GitHubIssuesResponse,
+  GitHubPullRequest,
   GitHubPullRequestComment,
   GitHubPullRequestDiff,
+  GitHubPullRequestReview,
   GitHubPullRequestsResponse,
+  GitHubRateLimit,
+  GitHubReviewCommentDraft,
+  GitHubReviewEvent,
+  GitLfsLock,
+  GitLogQuery,
   GitLogResponse,
+  GitOperation,
+  GitPickResult,
+  GitReleaseNotes,
+  GitStashEntry,
+  GitSubmoduleInfo,
+  GitTagInfo,
   ReviewTarget,
 } from "../types";
 
@@ -229,6 +260,30 @@ export async function addWorktree(
   return rpcCall<WorkspaceInfo>("add_worktree", { parentId, branch, name, copyAgentsMd });
 }
 
+export async function addPrWorktree(
+  parentId: string,
+  prNumber: number,
+  startReview = false,
+): Promise<PullRequestWorktree> {
+  return rpcCall<PullRequestWorktree>("add_pr_worktree", {
+    parentId,
+    prNumber,
+    startReview,
+  });
+}
+
+export async function startIssueRun(
+  workspaceId: string,
+  issueNumber: number,
+  worktree = true,
+): Promise<IssueRun> {
+  return rpcCall<IssueRun>("start_issue_run", {
+    workspaceId,
+    issueNumber,
+    worktree,
+  });
+}
+
 export type WorktreeSetupStatus = {
   shouldRun: boolean;
   script: string | null;
@@ -244,6 +299,24 @@ export async function markWorktreeSetupRan(workspaceId: string): Promise<void> {
   await rpcCall("worktree_setup_mark_ran", { workspaceId });
 }
 
+export type WorktreeSetupRunResult = {
+  success: boolean;
+  exitCode: number | null;
+  timedOut: boolean;
+  logPath: string;
+  durationMs: number;
+};
+
+export async function runWorktreeSetup(
+  workspaceId: string,
+  timeoutSecs?: number,
+): Promise<WorktreeSetupRunResult> {
+  return rpcCall<WorktreeSetupRunResult>("worktree_setup_run", {
+    workspaceId,
+    timeoutSecs: timeoutSecs ?? null,
+  });
+}
+
 export async function updateWorkspaceSettings(
   id: string,
   settings: WorkspaceSettings,
@@ -409,6 +482,9 @@ export async function getGitStatus(workspace_id: string): Promise<{
   files: GitFileStatus[];
   stagedFiles: GitFileStatus[];
   unstagedFiles: GitFileStatus[];
+  conflictedFiles?: string[];
+  operation?: GitOperation | null;
+  submodules?: GitSubmoduleInfo[];
   totalAdditions: number;
   totalDeletions: number;
 }> {
@@ -431,8 +507,13 @@ export async function getGitDiffs(
 export async function getGitLog(
   workspace_id: string,
   limit = 40,
+  query?: GitLogQuery,
 ): Promise<GitLogResponse> {
-  return rpcCall("get_git_log", { workspaceId: workspace_id, limit });
+  return rpcCall("get_git_log", {
+    workspaceId: workspace_id,
+    limit,
+    ...(query ? { query } : {}),
+  });
 }
 
 export async function getGitCommitDiff(
@@ -466,15 +547,69 @@ export async function revertGitAll(workspaceId: string) {
   return rpcCall("revert_git_all", { workspaceId });
 }
 
+export type GitHunkSelection = {
+  header: string;
+  lines?: number[];
+};
+
+export async function stageGitHunks(
+  workspaceId: string,
+  path: string,
+  hunks: GitHunkSelection[],
+): Promise<void> {
+  await rpcCall("stage_git_hunks", { workspaceId, path, hunks });
+}
+
+export async function unstageGitHunks(
+  workspaceId: string,
+  path: string,
+  hunks: GitHunkSelection[],
+): Promise<void> {
+  await rpcCall("unstage_git_hunks", { workspaceId, path, hunks });
+}
+
+export async function revertGitHunks(
+  workspaceId: string,
+  path: string,
+  hunks: GitHunkSelection[],
+): Promise<void> {
+  await rpcCall("revert_git_hunks", { workspaceId, path, hunks });
+}
+
 export async function commitGit(
   workspaceId: string,
   message: string,
-): Promise<void> {
-  await rpcCall("commit_git", { workspaceId, message });
+  options?: { noVerify?: boolean },
+): Promise<GitCommitResult> {
+  const result = await rpcCall<GitCommitResult>("commit_git", {
+    workspaceId,
+    message,
+    ...options,
+  });
+  if (!result.success) {
+    const failedHook = result.hooks.find((hook) => !hook.passed);
+    const detail = failedHook?.output ? `\n${failedHook.output}` : "";
+    throw new Error(`${result.message ?? "Commit failed."}${detail}`);
+  }
+  return result;
+}
+
+export async function checkGitCommitHooks(
+  workspaceId: string,
+  message?: string,
+): Promise<GitHookResult[]> {
+  return rpcCall("check_git_commit_hooks", { workspaceId, message });
 }
 
-export async function pushGit(workspaceId: string): Promise<void> {
+export async function pushGit(
+  workspaceId: string,
+  options: { watchChecks?: boolean } = {},
+): Promise<void> {
   await rpcCall("push_git", { workspaceId });
+  if (options.watchChecks) {
+    // Results arrive through subscribeGitHubChecksFinished.
+    await watchGitHubChecks(workspaceId).catch(() => null);
+  }
 }
 
 export async function pullGit(workspaceId: string): Promise<void> {
@@ -495,6 +630,16 @@ export async function getGitHubIssues(
   return rpcCall("get_github_issues", { workspaceId: workspace_id });
 }
 
+export async function getGitHubIssue(
+  workspace_id: string,
+  issueNumber: number,
+): Promise<GitHubIssueDetails> {
+  return rpcCall("get_github_issue", {
+    workspaceId: workspace_id,
+    issueNumber,
+  });
+}
+
 export async function getGitHubPullRequests(
   workspace_id: string,
 ): Promise<GitHubPullRequestsResponse> {
@@ -521,6 +666,166 @@ export async function getGitHubPullRequestComments(
   });
 }
 
+export async function getGitHubRateLimit(): Promise<GitHubRateLimit> {
+  return rpcCall("get_github_rate_limit");
+}
+
+export async function createGitHubPullRequest(
+  workspace_id: string,
+  options: {
+    title?: string | null;
+    body?: string | null;
+    base?: string | null;
+    draft?: boolean;
+  } = {},
+): Promise<GitHubPullRequest> {
+  return rpcCall("create_github_pull_request", {
+    workspaceId: workspace_id,
+    title: options.title ?? null,
+    body: options.body ?? null,
+    base: options.base ?? null,
+    draft: options.draft ?? false,
+  });
+}
+
+export async function updateGitHubPullRequest(
+  workspace_id: string,
+  prNumber: number,
+  changes: { title?: string | null; body?: string | null; base?: string | null },
+): Promise<GitHubPullRequest> {
+  return rpcCall("update_github_pull_request", {
+    workspaceId: workspace_id,
+    prNumber,
+    title: changes.title ?? null,
+    body: changes.body ?? null,
+    base: changes.base ?? null,
+  });
+}
+
+export async function markGitHubPullRequestReady(
+  workspace_id: string,
+  prNumber: number,
+): Promise<GitHubPullRequest> {
+  return rpcCall("mark_github_pull_request_ready", {
+    workspaceId: workspace_id,
+    prNumber,
+  });
+}
+
+export async function requestGitHubPullRequestReviewers(
+  workspace_id: string,
+  prNumber: number,
+  reviewers: string[],
+): Promise<GitHubPullRequest> {
+  return rpcCall("request_github_pull_request_reviewers", {
+    workspaceId: workspace_id,
+    prNumber,
+    reviewers,
+  });
+}
+
+export async function getGitHubPullRequestReviewComments(
+  workspace_id: string,
+  prNumber: number,
+): Promise<GitHubPullRequestComment[]> {
+  return rpcCall("get_github_pull_request_review_comments", {
+    workspaceId: workspace_id,
+    prNumber,
+  });
+}
+
+export async function postGitHubPullRequestComment(
+  workspace_id: string,
+  prNumber: number,
+  body: string,
+): Promise<GitHubPullRequestComment> {
+  return rpcCall("post_github_pull_request_comment", {
+    workspaceId: workspace_id,
+    prNumber,
+    body,
+  });
+}
+
+export async function postGitHubReviewComment(
+  workspace_id: string,
+  prNumber: number,
+  comment: GitHubReviewCommentDraft,
+): Promise<GitHubPullRequestComment> {
+  return rpcCall("post_github_review_comment", {
+    workspaceId: workspace_id,
+    prNumber,
+    comment,
+  });
+}
+
+export async function replyGitHubReviewComment(
+  workspace_id: string,
+  prNumber: number,
+  commentId: number,
+  body: string,
+): Promise<GitHubPullRequestComment> {
+  return rpcCall("reply_github_review_comment", {
+    workspaceId: workspace_id,
+    prNumber,
+    commentId,
+    body,
+  });
+}
+
+export async function submitGitHubPullRequestReview(
+  workspace_id: string,
+  prNumber: number,
+  event: GitHubReviewEvent,
+  options: {
+    body?: string | null;
+    comments?: GitHubReviewCommentDraft[] | null;
+    reviewText?: string | null;
+  } = {},
+): Promise<GitHubPullRequestReview> {
+  return rpcCall("submit_github_pull_request_review", {
+    workspaceId: workspace_id,
+    prNumber,
+    event,
+    body: options.body ?? null,
+    comments: options.comments ?? null,
+    reviewText: options.reviewText ?? null,
+  });
+}
+
+export async function getGitHubChecks(
+  workspace_id: string,
+  target: { gitRef?: string | null; prNumber?: number | null } = {},
+): Promise<GitHubChecksResponse> {
+  return rpcCall("get_github_checks", {
+    workspaceId: workspace_id,
+    gitRef: target.gitRef ?? null,
+    prNumber: target.prNumber ?? null,
+  });
+}
+
+export async function getGitHubCheckLogs(
+  workspace_id: string,
+  target: { gitRef?: string | null; prNumber?: number | null; checkId?: number | null } = {},
+): Promise<GitHubCheckLog[]> {
+  return rpcCall("get_github_check_logs", {
+    workspaceId: workspace_id,
+    gitRef: target.gitRef ?? null,
+    prNumber: target.prNumber ?? null,
+    checkId: target.checkId ?? null,
+  });
+}
+
+export async function watchGitHubChecks(
+  workspace_id: string,
+  target: { gitRef?: string | null; prNumber?: number | null } = {},
+): Promise<GitHubChecksResponse> {
+  return rpcCall("watch_github_checks", {
+    workspaceId: workspace_id,
+    gitRef: target.gitRef ?? null,
+    prNumber: target.prNumber ?? null,
+  });
+}
+
 export async function localUsageSnapshot(
   days?: number,
   workspacePath?: string | null,
@@ -686,6 +991,104 @@ export async function readWorkspaceFile(
   return rpcCall("read_workspace_file", { workspaceId, path });
 }
 
+export async function listWorkspaceDirectory(
+  workspaceId: string,
+  path?: string,
+): Promise<WorkspaceDirEntry[]> {
+  return rpcCall<WorkspaceDirEntry[]>("list_workspace_directory", {
+    workspaceId,
+    path: path ?? null,
+  });
+}
+
+export async function readWorkspaceTextFile(
+  workspaceId: string,
+  path: string,
+): Promise<WorkspaceTextFile> {
+  return rpcCall<WorkspaceTextFile>("read_workspace_text_file", { workspaceId, path });
+}
+
+export async function writeWorkspaceTextFile(
+  workspaceId: string,
+  path: string,
+  content: string,
+  options: {
+    encoding?: WorkspaceFileEncoding;
+    expectedHash?: string | null;
+    expectedModifiedMs?: number | null;
+  } = {},
+): Promise<WorkspaceFileStat> {
+  return rpcCall<WorkspaceFileStat>("write_workspace_text_file", {
+    workspaceId,
+    path,
+    content,
+    encoding: options.encoding ?? null,
+    expectedHash: options.expectedHash ?? null,
+    expectedModifiedMs: options.expectedModifiedMs ?? null,
+  });
+}
+
+export async function createWorkspaceEntry(
+  workspaceId: string,
+  path: string,
+  directory = false,
+  content?: string,
+): Promise<WorkspaceDirEntry> {
+  return rpcCall<WorkspaceDirEntry>("create_workspace_entry", {
+    workspaceId,
+    path,
+    directory,
+    content: content ?? null,
+  });
+}
+
+export async function renameWorkspaceEntry(
+  workspaceId: string,
+  from: string,
+  to: string,
+): Promise<WorkspaceDirEntry> {
+  return rpcCall<WorkspaceDirEntry>("rename_workspace_entry", { workspaceId, from, to });
+}
+
+export async function deleteWorkspaceEntry(workspaceId: string, path: string): Promise<void> {
+  await rpcCall("delete_workspace_entry", { workspaceId, path });
+}
+
+export async function searchWorkspaceFiles(
+  workspaceId: string,
+  searchId: string,
+  query: string,
+  options: WorkspaceSearchOptions = {},
+): Promise<WorkspaceSearchSummary> {
+  return rpcCall<WorkspaceSearchSummary>("search_workspace_files", {
+    workspaceId,
+    searchId,
+    query,
+    regex: options.regex ?? false,
+    caseSensitive: options.caseSensitive ?? false,
+    include: options.include ?? [],
+    exclude: options.exclude ?? [],
+    maxResults: options.maxResults ?? null,
+    contextLines: options.contextLines ?? null,
+  });
+}
+
+export async function cancelWorkspaceSearch(searchId: string): Promise<boolean> {
+  return rpcCall<boolean>("cancel_workspace_search", { searchId });
+}
+
+export async function findWorkspacePaths(
+  workspaceId: string,
+  query: string,
+  limit?: number,
+): Promise<WorkspacePathMatch[]> {
+  return rpcCall<WorkspacePathMatch[]>("find_workspace_paths", {
+    workspaceId,
+    query,
+    limit: limit ?? null,
+  });
+}
+
 export async function readAgentMd(workspaceId: string): Promise<AgentMdResponse> {
   return fileRead("workspace", "agents", workspaceId);
 }
@@ -702,8 +1105,237 @@ export async function checkoutGitBranch(workspaceId: string, name: string) {
   return rpcCall("checkout_git_branch", { workspaceId, name });
 }
 
-export async function createGitBranch(workspaceId: string, name: string) {
-  return rpcCall("create_git_branch", { workspaceId, name });
+export async function createGitBranch(
+  workspaceId: string,
+  name: string,
+  options?: { startPoint?: string; checkout?: boolean },
+) {
+  return rpcCall("create_git_branch", { workspaceId, name, ...options });
+}
+
+export async function deleteGitBranch(
+  workspaceId: string,
+  name: string,
+  options?: { remote?: boolean; force?: boolean },
+): Promise<void> {
+  await rpcCall("delete_git_branch", { workspaceId, name, ...options });
+}
+
+export async function renameGitBranch(
+  workspaceId: string,
+  name: string,
+  newName: string,
+): Promise<void> {
+  await rpcCall("rename_git_branch", { workspaceId, name, newName });
+}
+
+export async function setGitBranchUpstream(
+  workspaceId: string,
+  name: string | null,
+  upstream: string | null,
+): Promise<void> {
+  await rpcCall("set_git_branch_upstream", { workspaceId, name, upstream });
+}
+
+export async function mergeGitBranch(
+  workspaceId: string,
+  name: string,
+  noFf?: boolean,
+): Promise<GitPickResult> {
+  return rpcCall("merge_git_branch", { workspaceId, name, noFf });
+}
+
+export async function listGitSubmodules(workspaceId: string): Promise<GitSubmoduleInfo[]> {
+  return rpcCall("list_git_submodules", { workspaceId });
+}
+
+export async function updateGitSubmodules(
+  workspaceId: string,
+  options?: { paths?: string[]; init?: boolean; sync?: boolean; recursive?: boolean },
+): Promise<GitSubmoduleInfo[]> {
+  return rpcCall("update_git_submodules", { workspaceId, ...options });
+}
+
+export async function getGitLfsLocks(
+  workspaceId: string,
+  local?: boolean,
+): Promise<GitLfsLock[]> {
+  return rpcCall("get_git_lfs_locks", { workspaceId, local });
+}
+
+export async function listGitStashes(workspaceId: string): Promise<GitStashEntry[]> {
+  return rpcCall("list_git_stashes", { workspaceId });
+}
+
+export async function stashGitPush(
+  workspaceId: string,
+  options?: { message?: string; includeUntracked?: boolean; paths?: string[] },
+): Promise<GitStashEntry | null> {
+  return rpcCall("stash_git_push", { workspaceId, ...options });
+}
+
+export async function stashGitApply(workspaceId: string, index: number): Promise<void> {
+  await rpcCall("stash_git_apply", { workspaceId, index });
+}
+
+export async function stashGitPop(workspaceId: string, index: number): Promise<void> {
+  await rpcCall("stash_git_pop", { workspaceId, index });
+}
+
+export async function stashGitDrop(workspaceId: string, index: number): Promise<void> {
+  await rpcCall("stash_git_drop", { workspaceId, index });
+}
+
+export async function getGitStashDiff(
+  workspaceId: string,
+  index: number,
+): Promise<GitCommitDiff[]> {
+  return rpcCall("get_git_stash_diff", { workspaceId, index });
+}
+
+export async function getGitConflicts(
+  workspaceId: string,
+): Promise<GitConflictsResponse> {
+  return rpcCall("get_git_conflicts", { workspaceId });
+}
+
+export async function resolveGitConflict(
+  workspaceId: string,
+  path: string,
+  resolution: "ours" | "theirs" | "content",
+  content?: string,
+): Promise<void> {
+  await rpcCall("resolve_git_conflict", { workspaceId, path, resolution, content });
+}
+
+export async function abortGitMerge(workspaceId: string): Promise<void> {
+  await rpcCall("abort_git_merge", { workspaceId });
+}
+
+export async function continueGitMerge(workspaceId: string): Promise<void> {
+  await rpcCall("continue_git_merge", { workspaceId });
+}
+
+export async function abortGitRebase(workspaceId: string): Promise<void> {
+  await rpcCall("abort_git_rebase", { workspaceId });
+}
+
+export async function continueGitRebase(workspaceId: string): Promise<void> {
+  await rpcCall("continue_git_rebase", { workspaceId });
+}
+
+export async function getGitRangeDiff(
+  workspaceId: string,
+  base: string,
+  head: string,
+  threeDot = true,
+): Promise<GitFileDiff[]> {
+  return rpcCall("get_git_range_diff", { workspaceId, base, head, threeDot });
+}
+
+export async function getGitBranchComparison(
+  workspaceId: string,
+  base: string,
+  head: string,
+  limit?: number,
+): Promise<GitBranchComparison> {
+  return rpcCall("get_git_branch_comparison", { workspaceId, base, head, limit });
+}
+
+export async function getGitFileLog(
+  workspaceId: string,
+  path: string,
+  limit?: number,
+): Promise<GitFileLogEntry[]> {
+  return rpcCall("get_git_file_log", { workspaceId, path, limit });
+}
+
+export async function getGitBlame(
+  workspaceId: string,
+  path: string,
+  rev?: string,
+): Promise<GitBlameResponse> {
+  return rpcCall("get_git_blame", { workspaceId, path, rev });
+}
+
+export async function amendGitCommit(
+  workspaceId: string,
+  message?: string,
+): Promise<void> {
+  await rpcCall("amend_git_commit", { workspaceId, message });
+}
+
+export async function fixupGitCommit(workspaceId: string, sha: string): Promise<void> {
+  await rpcCall("fixup_git_commit", { workspaceId, sha });
+}
+
+export async function autosquashGitRebase(
+  workspaceId: string,
+  base: string,
+): Promise<void> {
+  await rpcCall("autosquash_git_rebase", { workspaceId, base });
+}
+
+export async function squashWorktreeCommits(
+  workspaceId: string,
+  options?: { message?: string; base?: string },
+): Promise<void> {
+  await rpcCall("squash_worktree_commits", { workspaceId, ...options });
+}
+
+export async function cherryPickGitCommits(
+  workspaceId: string,
+  shas: string[],
+  sourceWorkspaceId?: string,
+): Promise<GitPickResult> {
+  return rpcCall("cherry_pick_git_commits", { workspaceId, shas, sourceWorkspaceId });
+}
+
+export async function revertGitCommit(
+  workspaceId: string,
+  sha: string,
+): Promise<GitPickResult> {
+  return rpcCall("revert_git_commit", { workspaceId, sha });
+}
+
+export async function continueGitOperation(workspaceId: string): Promise<void> {
+  await rpcCall("continue_git_operation", { workspaceId });
+}
+
+export async function abortGitOperation(workspaceId: string): Promise<void> {
+  await rpcCall("abort_git_operation", { workspaceId });
+}
+
+export async function listGitTags(workspaceId: string): Promise<GitTagInfo[]> {
+  return rpcCall("list_git_tags", { workspaceId });
+}
+
+export async function createGitTag(
+  workspaceId: string,
+  name: string,
+  options?: { target?: string; message?: string },
+): Promise<GitTagInfo> {
+  return rpcCall("create_git_tag", { workspaceId, name, ...options });
+}
+
+export async function deleteGitTag(workspaceId: string, name: string): Promise<void> {
+  await rpcCall("delete_git_tag", { workspaceId, name });
+}
+
+export async function pushGitTag(
+  workspaceId: string,
+  name: string,
+  remote?: string,
+): Promise<void> {
+  await rpcCall("push_git_tag", { workspaceId, name, remote });
+}
+
+export async function getGitReleaseNotes(
+  workspaceId: string,
+  fromTag: string,
+  toRef?: string,
+): Promise<GitReleaseNotes> {
+  return rpcCall("get_git_release_notes", { workspaceId, fromTag, toRef });
 }
 
 function withModelId(modelId?: string | null) {
@@ -8,6 +8,13 @@ export type WorkspaceSettings = {
   launchScript?: string | null;
   launchScripts?: LaunchScriptEntry[] | null;
   worktreeSetupScript?: string | null;
+  issue?: WorkspaceIssueLink | null;
+};
+
+export type WorkspaceIssueLink = {
+  number: number;
+  title: string;
+  url: string;
 };
 
 export type LaunchScriptIconId =
@@ -45,6 +52,7 @@ export type WorkspaceKind = "main" | "worktree";
 
 export type WorktreeInfo = {
   branch: string;
+  prNumber?: number | null;
 };
 
 export type WorkspaceInfo = {
@@ -59,6 +67,87 @@ export type WorkspaceInfo = {
   settings: WorkspaceSettings;
 };
 
+export type PullRequestWorktree = {
+  workspace: WorkspaceInfo;
+  reviewThreadId?: string | null;
+};
+
+export type IssueRun = {
+  workspace: WorkspaceInfo;
+  createdWorktree: boolean;
+  threadId?: string | null;
+};
+
+export type WorkspaceSearchOptions = {
+  regex?: boolean;
+  caseSensitive?: boolean;
+  include?: string[];
+  exclude?: string[];
+  maxResults?: number | null;
+  contextLines?: number | null;
+};
+
+export type WorkspaceSearchMatch = {
+  path: string;
+  line: number;
+  column: number;
+  length: number;
+  text: string;
+  textOffset: number;
+  contextBefore: string[];
+  contextAfter: string[];
+};
+
+export type WorkspaceSearchMatchesEvent = {
+  workspaceId: string;
+  searchId: string;
+  matches: WorkspaceSearchMatch[];
+};
+
+export type WorkspaceSearchSummary = {
+  searchId: string;
+  matchCount: number;
+  fileCount: number;
+  filesSearched: number;
+  truncated: boolean;
+  cancelled: boolean;
+};
+
+export type WorkspacePathMatch = {
+  path: string;
+  score: number;
+  positions: number[];
+};
+
+export type WorkspaceDirEntry = {
+  name: string;
+  path: string;
+  kind: "file" | "dir" | "symlink";
+  size: number | null;
+  modifiedMs: number | null;
+  gitStatus: string | null;
+};
+
+export type WorkspaceFileEncoding =
+  | "utf-8"
+  | "utf-8-bom"
+  | "utf-16le"
+  | "utf-16be"
+  | "binary";
+
+export type WorkspaceFileStat = {
+  path: string;
+  size: number;
+  modifiedMs: number;
+  hash: string;
+};
+
+export type WorkspaceTextFile = WorkspaceFileStat & {
+  content: string;
+  encoding: WorkspaceFileEncoding;
+  truncated: boolean;
+};
+
 export type AppServerEvent = {
   workspace_id: string;
   message: Record<string, unknown>;
@@ -201,6 +290,22 @@ export type AppSettings = {
   workspaceGroups: WorkspaceGroup[];
   openAppTargets: OpenAppTarget[];
   selectedOpenAppId: string;
+  terminalMaxPerWorkspace: number;
+  terminalMaxTotal: number;
+  terminalIdleTimeoutMinutes: number;
+  terminalCloseOnDisconnect: boolean;
+  githubToken: string | null;
+  githubApiBaseUrl: string | null;
+  forgeHosts: ForgeHostMapping[];
+};
+
+export type ForgeKind = "github" | "gitlab" | "gitea";
+
+export type ForgeHostMapping = {
+  host: string;
+  kind: ForgeKind;
+  apiBaseUrl?: string | null;
+  token?: string | null;
 };
 
 export type CodexDoctorResult = {
@@ -274,6 +379,42 @@ export type GitFileDiff = {
   newImageData?: string | null;
   oldImageMime?: string | null;
   newImageMime?: string | null;
+  submodule?: GitSubmoduleChange | null;
+  lfs?: GitLfsChange | null;
+};
+
+export type GitSubmoduleChange = {
+  oldSha: string | null;
+  newSha: string | null;
+  dirty: boolean;
+};
+
+export type GitLfsChange = {
+  oldOid: string | null;
+  newOid: string | null;
+  oldSize: number | null;
+  newSize: number | null;
+  oldAvailable: boolean;
+  newAvailable: boolean;
+};
+
+export type GitLfsLock = {
+  id: string;
+  path: string;
+  owner: string | null;
+  lockedAt: string | null;
+};
+
+export type GitSubmoduleInfo = {
+  name: string;
+  path: string;
+  url: string | null;
+  branch: string | null;
+  recordedSha: string | null;
+  checkedOutSha: string | null;
+  initialized: boolean;
+  outOfSync: boolean;
+  dirty: boolean | null;
 };
 
 export type GitCommitDiff = {
@@ -288,6 +429,8 @@ export type GitCommitDiff = {
   newImageData?: string | null;
   oldImageMime?: string | null;
   newImageMime?: string | null;
+  submodule?: GitSubmoduleChange | null;
+  lfs?: GitLfsChange | null;
 };
 
 export type GitLogEntry = {
@@ -295,6 +438,19 @@ export type GitLogEntry = {
   summary: string;
   author: string;
   timestamp: number;
+  parents?: string[];
+};
+
+export type GitLogQuery = {
+  cursor?: string | null;
+  author?: string;
+  path?: string;
+  message?: string;
+  since?: number;
+  until?: number;
+  branch?: string;
+  allBranches?: boolean;
+  includeTotal?: boolean;
 };
 
 export type GitLogResponse = {
@@ -305,6 +461,139 @@ export type GitLogResponse = {
   aheadEntries: GitLogEntry[];
   behindEntries: GitLogEntry[];
   upstream: string | null;
+  nextCursor?: string | null;
+};
+
+export type GitFileLogEntry = GitLogEntry & {
+  path: string;
+  status: string;
+  previousPath: string | null;
+};
+
+export type GitBlameHunk = {
+  startLine: number;
+  lineCount: number;
+  sha: string;
+  author: string;
+  timestamp: number;
+  summary: string;
+  originalPath: string | null;
+  originalStartLine: number;
+};
+
+export type GitBlameResponse = {
+  path: string;
+  sha: string;
+  lines: string[];
+  hunks: GitBlameHunk[];
+};
+
+export type GitBranchComparison = {
+  base: string;
+  head: string;
+  mergeBase: string | null;
+  ahead: number;
+  behind: number;
+  aheadEntries: GitLogEntry[];
+  behindEntries: GitLogEntry[];
+};
+
+export type GitOperation = "merge" | "rebase" | "cherry-pick" | "revert";
+
+export type GitConflictMarker = {
+  startLine: number;
+  baseLine: number | null;
+  separatorLine: number;
+  endLine: number;
+};
+
+export type GitConflictFile = {
+  path: string;
+  base: string | null;
+  ours: string | null;
+  theirs: string | null;
+  working: string | null;
+  markers: GitConflictMarker[];
+  oursDeleted: boolean;
+  theirsDeleted: boolean;
+  isBinary: boolean;
+};
+
+export type GitConflictsResponse = {
+  operation: GitOperation | null;
+  files: GitConflictFile[];
+};
+
+export type GitPickResult = {
+  success: boolean;
+  headSha: string | null;
+  conflictedFiles: string[];
+  operation: GitOperation | null;
+  message: string | null;
+};
+
+export type GitHookResult = {
+  name: string;
+  passed: boolean;
+  output: string;
+  durationMs: number;
+};
+
+export type GitSignatureStatus =
+  | "good"
+  | "untrusted"
+  | "bad"
+  | "expired"
+  | "revoked"
+  | "unverifiable"
+  | "unsigned"
+  | "failed";
+
+export type GitCommitSignature = {
+  required: boolean;
+  format: string | null;
+  status: GitSignatureStatus;
+  signer: string | null;
+};
+
+export type GitCommitResult = {
+  success: boolean;
+  sha: string | null;
+  hooks: GitHookResult[];
+  signature: GitCommitSignature | null;
+  output: string;
+  message: string | null;
+};
+
+export type GitTagInfo = {
+  name: string;
+  sha: string;
+  annotated: boolean;
+  message: string | null;
+  tagger: string | null;
+  timestamp: number;
+};
+
+export type GitReleaseSection = {
+  kind: string;
+  title: string;
+  entries: GitLogEntry[];
+};
+
+export type GitReleaseNotes = {
+  from: string;
+  to: string;
+  sections: GitReleaseSection[];
+  breaking: GitLogEntry[];
+  markdown: string;
+};
+
+export type GitStashEntry = {
+  index: number;
+  sha: string;
+  message: string;
+  branch: string | null;
+  timestamp: number;
 };
 
 export type GitHubIssue = {
@@ -314,9 +603,25 @@ export type GitHubIssue = {
   updatedAt: string;
 };
 
+export type GitHubIssueDetails = GitHubIssue & {
+  body: string;
+  state: string;
+  labels: string[];
+  author: GitHubUser | null;
+  comments: GitHubPullRequestComment[];
+};
+
+export type GitHubRateLimit = {
+  limit: number;
+  remaining: number;
+  resetAt: number;
+  resource?: string | null;
+};
+
 export type GitHubIssuesResponse = {
   total: number;
   issues: GitHubIssue[];
+  rateLimit?: GitHubRateLimit | null;
 };
 
 export type GitHubUser = {
@@ -339,6 +644,7 @@ export type GitHubPullRequest = {
 export type GitHubPullRequestsResponse = {
   total: number;
   pullRequests: GitHubPullRequest[];
+  rateLimit?: GitHubRateLimit | null;
 };
 
 export type GitHubPullRequestDiff = {
@@ -353,6 +659,68 @@ export type GitHubPullRequestComment = {
   createdAt: string;
   url: string;
   author: GitHubUser | null;
+  path?: string | null;
+  line?: number | null;
+  startLine?: number | null;
+  originalLine?: number | null;
+  side?: "LEFT" | "RIGHT" | null;
+  diffHunk?: string | null;
+  inReplyToId?: number | null;
+};
+
+export type GitHubReviewCommentDraft = {
+  path: string;
+  line: number;
+  startLine?: number | null;
+  side?: "LEFT" | "RIGHT" | null;
+  body: string;
+};
+
+export type GitHubReviewEvent = "approve" | "request_changes" | "comment";
+
+export type GitHubCheck = {
+  id?: number | null;
+  name: string;
+  kind: "checkRun" | "status";
+  status: "queued" | "in_progress" | "completed" | string;
+  conclusion?: string | null;
+  url: string;
+  startedAt?: string | null;
+  completedAt?: string | null;
+  durationSecs?: number | null;
+};
+
+export type GitHubChecksState = "pending" | "success" | "failure" | "none";
+
+export type GitHubChecksResponse = {
+  ref: string;
+  sha: string;
+  state: GitHubChecksState;
+  checks: GitHubCheck[];
+  rateLimit?: GitHubRateLimit | null;
+};
+
+export type GitHubChecksFinishedEvent = GitHubChecksResponse & {
+  workspaceId: string;
+};
+
+export type GitHubCheckLog = {
+  id: number;
+  name: string;
+  conclusion?: string | null;
+  url: string;
+  excerpt: string;
+  truncated: boolean;
+};
+
+export type GitHubPullRequestReview = {
+  id: number;
+  state: string;
+  body: string;
+  url: string;
+  submittedAt: string | null;
+  author: GitHubUser | null;
+  commentCount: number;
 };
 
 export type TokenUsageBreakdown = {
@@ -494,6 +862,11 @@ export type CustomPromptOption = {
 export type BranchInfo = {
   name: string;
   lastCommit: number;
+  upstream?: string | null;
+  ahead?: number;
+  behind?: number;
+  remote?: string | null;
+  current?: boolean;
 };
 
 export type DebugEntry = {